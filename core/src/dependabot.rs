@@ -0,0 +1,396 @@
+use std::fmt::{self, Display, Formatter};
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DependabotState {
+    AutoDismissed,
+    Dismissed,
+    Fixed,
+    Open,
+}
+
+impl Display for DependabotState {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "lowercase")]
+pub enum DependabotSeverity {
+    Low,
+    Medium,
+    High,
+    Critical,
+}
+
+impl Display for DependabotSeverity {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+/// Whether a dependency is needed at runtime or only for development, e.g. an npm
+/// `devDependency`.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DependencyScope {
+    Runtime,
+    Development,
+}
+
+impl Display for DependencyScope {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            DependencyScope::Runtime => write!(f, "runtime"),
+            DependencyScope::Development => write!(f, "development"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DismissReason {
+    FixStarted,
+    Inaccurate,
+    NoBandwidth,
+    NotUsed,
+    TolerableRisk,
+}
+
+impl DismissReason {
+    pub const ALL: [DismissReason; 5] = [
+        DismissReason::FixStarted,
+        DismissReason::Inaccurate,
+        DismissReason::NoBandwidth,
+        DismissReason::NotUsed,
+        DismissReason::TolerableRisk,
+    ];
+
+    /// The reason GitHub reports as `value`, e.g. `tolerable_risk`.
+    pub fn from_api(value: &str) -> Option<DismissReason> {
+        match value {
+            "fix_started" => Some(DismissReason::FixStarted),
+            "inaccurate" => Some(DismissReason::Inaccurate),
+            "no_bandwidth" => Some(DismissReason::NoBandwidth),
+            "not_used" => Some(DismissReason::NotUsed),
+            "tolerable_risk" => Some(DismissReason::TolerableRisk),
+            _ => None,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            DismissReason::FixStarted => "A fix has already been started",
+            DismissReason::Inaccurate => "This alert is inaccurate or incorrect",
+            DismissReason::NoBandwidth => "No bandwidth to fix this",
+            DismissReason::NotUsed => "Vulnerable code is not actually used",
+            DismissReason::TolerableRisk => "Risk is tolerable to this project",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct GithubDependabot {
+    pub number: u32,
+    pub state: DependabotState,
+    pub security_vulnerability: SecurityVulnerability,
+    pub html_url: String,
+    pub created_at: String,
+    pub updated_at: String,
+    pub dismissed_at: Option<String>,
+    pub auto_dismissed_at: Option<String>,
+    pub fixed_at: Option<String>,
+    pub dismissed_by: Option<AlertUser>,
+    pub dismissed_reason: Option<String>,
+    pub dismissed_comment: Option<String>,
+    pub dependency: Option<AlertDependency>,
+    pub security_advisory: Option<SecurityAdvisory>,
+    // only included by the organization-wide alerts endpoint
+    #[serde(default)]
+    pub repository: Option<AlertRepository>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct AlertRepository {
+    pub full_name: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct AlertUser {
+    pub login: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct AlertDependency {
+    pub manifest_path: Option<String>,
+    // `None` when GitHub can't tell
+    pub scope: Option<DependencyScope>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct SecurityVulnerability {
+    pub severity: DependabotSeverity,
+    pub package: Package,
+    pub vulnerable_version_range: Option<String>,
+    pub first_patched_version: Option<FirstPatchedVersion>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct FirstPatchedVersion {
+    pub identifier: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct SecurityAdvisory {
+    pub ghsa_id: String,
+    pub cve_id: Option<String>,
+    pub summary: String,
+    pub description: String,
+    pub cvss: Option<Cvss>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Cvss {
+    // GitHub reports 0.0 when the advisory hasn't been scored
+    pub score: Option<f64>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Package {
+    pub ecosystem: String,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Dependabot {
+    pub number: u32,
+    pub state: DependabotState,
+    pub severity: DependabotSeverity,
+    pub html_url: String,
+    pub created_at: String,
+    pub updated_at: String,
+    pub dismissed_at: Option<String>,
+    #[serde(default)]
+    pub auto_dismissed_at: Option<String>,
+    #[serde(default)]
+    pub fixed_at: Option<String>,
+    // the login of whoever dismissed the alert
+    #[serde(default)]
+    pub dismissed_by: Option<String>,
+    // as GitHub reports it, e.g. `tolerable_risk`
+    #[serde(default)]
+    pub dismissed_reason: Option<String>,
+    #[serde(default)]
+    pub dismissed_comment: Option<String>,
+    // the lockfile or manifest the vulnerable dependency was found in
+    #[serde(default)]
+    pub manifest_path: Option<String>,
+    // whether the dependency is needed at runtime or only for development, if GitHub knows
+    #[serde(default)]
+    pub scope: Option<DependencyScope>,
+    pub dependency_ecosystem: String,
+    pub dependency_name: String,
+    #[serde(default)]
+    pub vulnerable_version_range: Option<String>,
+    #[serde(default)]
+    pub ghsa_id: Option<String>,
+    #[serde(default)]
+    pub cve_id: Option<String>,
+    #[serde(default)]
+    pub cvss_score: Option<f64>,
+    // the advisory's one-line summary and full (markdown) description
+    #[serde(default)]
+    pub summary: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+    // the earliest version that fixes the vulnerability, if one has been released
+    #[serde(default)]
+    pub first_patched_version: Option<String>,
+    // the package's license, from the repository's dependency graph
+    #[serde(default)]
+    pub license: Option<String>,
+}
+
+/// An alert that's open in one or more repositories, identified by its package,
+/// vulnerable version range, and advisory.
+#[derive(Debug, Clone)]
+pub struct DistinctAlert {
+    pub dependency_name: String,
+    pub dependency_ecosystem: String,
+    pub vulnerable_version_range: Option<String>,
+    pub ghsa_id: Option<String>,
+    pub severity: DependabotSeverity,
+    pub repositories: Vec<String>,
+}
+
+impl DistinctAlert {
+    pub fn matches(&self, dependabot: &Dependabot) -> bool {
+        self.dependency_name == dependabot.dependency_name
+            && self.dependency_ecosystem == dependabot.dependency_ecosystem
+            && self.vulnerable_version_range == dependabot.vulnerable_version_range
+            && self.ghsa_id == dependabot.ghsa_id
+    }
+}
+
+/// A package with open alerts, along with every repository the alerts are in.
+#[derive(Debug, Clone)]
+pub struct VulnerableDependency {
+    pub dependency_name: String,
+    pub dependency_ecosystem: String,
+    // the worst severity among the package's open alerts
+    pub severity: DependabotSeverity,
+    pub repositories: Vec<AffectedRepository>,
+}
+
+impl VulnerableDependency {
+    pub fn alert_count(&self) -> usize {
+        self.repositories.iter().map(|repo| repo.alert_count).sum()
+    }
+}
+
+/// A repository with open alerts on a `VulnerableDependency`.
+#[derive(Debug, Clone)]
+pub struct AffectedRepository {
+    pub id: u32,
+    pub name: String,
+    // the worst severity among the package's open alerts in this repository
+    pub severity: DependabotSeverity,
+    pub alert_count: usize,
+}
+
+impl From<GithubDependabot> for Dependabot {
+    fn from(github_dependabot: GithubDependabot) -> Self {
+        let security_advisory = github_dependabot.security_advisory;
+        Dependabot {
+            number: github_dependabot.number,
+            state: github_dependabot.state,
+            severity: github_dependabot.security_vulnerability.severity,
+            html_url: github_dependabot.html_url,
+            created_at: github_dependabot.created_at,
+            updated_at: github_dependabot.updated_at,
+            dismissed_at: github_dependabot.dismissed_at,
+            auto_dismissed_at: github_dependabot.auto_dismissed_at,
+            fixed_at: github_dependabot.fixed_at,
+            dismissed_by: github_dependabot.dismissed_by.map(|user| user.login),
+            dismissed_reason: github_dependabot.dismissed_reason,
+            dismissed_comment: github_dependabot.dismissed_comment,
+            manifest_path: github_dependabot
+                .dependency
+                .as_ref()
+                .and_then(|dependency| dependency.manifest_path.clone()),
+            scope: github_dependabot
+                .dependency
+                .and_then(|dependency| dependency.scope),
+            dependency_ecosystem: github_dependabot.security_vulnerability.package.ecosystem,
+            dependency_name: github_dependabot.security_vulnerability.package.name,
+            vulnerable_version_range: github_dependabot
+                .security_vulnerability
+                .vulnerable_version_range,
+            first_patched_version: github_dependabot
+                .security_vulnerability
+                .first_patched_version
+                .map(|first_patched_version| first_patched_version.identifier),
+            ghsa_id: security_advisory
+                .as_ref()
+                .map(|security_advisory| security_advisory.ghsa_id.clone()),
+            cve_id: security_advisory
+                .as_ref()
+                .and_then(|security_advisory| security_advisory.cve_id.clone()),
+            cvss_score: security_advisory
+                .as_ref()
+                .and_then(|security_advisory| security_advisory.cvss.as_ref())
+                .and_then(|cvss| cvss.score)
+                .filter(|score| *score > 0.0),
+            summary: security_advisory
+                .as_ref()
+                .map(|security_advisory| security_advisory.summary.clone()),
+            description: security_advisory.map(|security_advisory| security_advisory.description),
+            license: None,
+        }
+    }
+}
+
+impl Dependabot {
+    /// Whether GitHub auto-dismissed the alert within `window` of `now`.
+    pub fn auto_dismissed_within(&self, window: Duration, now: DateTime<Utc>) -> bool {
+        self.state == DependabotState::AutoDismissed
+            && self
+                .auto_dismissed_at
+                .as_deref()
+                .and_then(|auto_dismissed_at| DateTime::parse_from_rfc3339(auto_dismissed_at).ok())
+                .is_some_and(|auto_dismissed_at| {
+                    now.signed_duration_since(auto_dismissed_at.with_timezone(&Utc)) <= window
+                })
+    }
+
+    /// When the alert was fixed or dismissed, or `None` while it's open.
+    pub fn resolved_at(&self) -> Option<&str> {
+        match self.state {
+            DependabotState::Open => None,
+            DependabotState::Fixed => self.fixed_at.as_deref(),
+            DependabotState::Dismissed => self.dismissed_at.as_deref(),
+            DependabotState::AutoDismissed => self.auto_dismissed_at.as_deref(),
+        }
+    }
+
+    /// Why the alert was dismissed, in words, e.g. `Risk is tolerable to this project`.
+    pub fn dismissed_reason_label(&self) -> Option<String> {
+        let dismissed_reason = self.dismissed_reason.as_deref()?;
+        Some(
+            DismissReason::from_api(dismissed_reason)
+                .map(|reason| reason.label().to_string())
+                .unwrap_or_else(|| dismissed_reason.to_string()),
+        )
+    }
+
+    /// Whether a patched version is available, so the alert can be fixed by upgrading.
+    pub fn is_fixable(&self) -> bool {
+        self.first_patched_version.is_some()
+    }
+
+    /// What to do to fix the alert, e.g. `upgrade foo from < 1.2.3 to 1.2.3`, or `None` if no
+    /// patched version has been released.
+    pub fn upgrade_hint(&self) -> Option<String> {
+        let first_patched_version = self.first_patched_version.as_deref()?;
+        Some(match self.vulnerable_version_range.as_deref() {
+            Some(vulnerable_version_range) => format!(
+                "upgrade {} from {} to {}",
+                self.dependency_name, vulnerable_version_range, first_patched_version
+            ),
+            None => format!(
+                "upgrade {} to {}",
+                self.dependency_name, first_patched_version
+            ),
+        })
+    }
+
+    /// How long the alert has existed, or `None` if `created_at` can't be parsed.
+    pub fn age(&self, now: DateTime<Utc>) -> Option<Duration> {
+        DateTime::parse_from_rfc3339(&self.created_at)
+            .ok()
+            .map(|created_at| now.signed_duration_since(created_at.with_timezone(&Utc)))
+    }
+}
+
+/// Whether an SPDX license expression includes a copyleft license.
+pub fn is_copyleft(license: &str) -> bool {
+    const COPYLEFT_LICENSES: [&str; 8] = [
+        "GPL", "MPL", "EPL", "CDDL", "OSL", "EUPL", "CC-BY-SA", "CPAL",
+    ];
+    let license = license.to_uppercase();
+
+    COPYLEFT_LICENSES
+        .iter()
+        .any(|copyleft| license.contains(copyleft))
+}
+
+pub fn format_age(age: Option<Duration>) -> String {
+    match age {
+        Some(age) => format!("{}d", age.num_days()),
+        None => "N/A".to_string(),
+    }
+}
@@ -0,0 +1,196 @@
+use chrono::{DateTime, Local, TimeZone, Utc};
+use reqwest::header::HeaderMap;
+use reqwest::{Response, StatusCode};
+use thiserror::Error;
+
+/// Everything that can go wrong talking to GitHub or GitLab, or reading and saving the data,
+/// split up so the UI can explain what happened and whether trying again would help.
+#[derive(Debug, Error)]
+pub enum DependabotTrackerError {
+    // the server couldn't be reached, or the connection dropped or stalled
+    #[error("{0}")]
+    Network(reqwest::Error),
+    // GitHub or GitLab answered with an error status
+    #[error("{message}")]
+    GitHubApi { status: StatusCode, message: String },
+    // GitHub won't take more requests until `reset_at`
+    #[error("GitHub's rate limit was reached, it resets at {}", reset_at.with_timezone(&Local).format("%H:%M"))]
+    RateLimited { reset_at: DateTime<Utc> },
+    // a response or saved file wasn't in the expected shape
+    #[error("{0}")]
+    Deserialize(String),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
+    Database(#[from] rusqlite::Error),
+    // the OS keyring couldn't read or save the token
+    #[error("{0}")]
+    Keyring(String),
+    #[error("{0}")]
+    Config(String),
+    // the device flow login was refused, expired, or isn't set up
+    #[error("{0}")]
+    Login(String),
+    // a GraphQL query came back without any data
+    #[error("{0}")]
+    GraphQl(String),
+    #[error("{0}")]
+    Other(String),
+}
+
+impl From<reqwest::Error> for DependabotTrackerError {
+    fn from(error: reqwest::Error) -> Self {
+        if error.is_decode() {
+            return DependabotTrackerError::Deserialize(error.to_string());
+        }
+        match error.status() {
+            Some(status) => DependabotTrackerError::GitHubApi {
+                status,
+                message: error.to_string(),
+            },
+            None => DependabotTrackerError::Network(error),
+        }
+    }
+}
+
+impl From<serde_json::Error> for DependabotTrackerError {
+    fn from(error: serde_json::Error) -> Self {
+        DependabotTrackerError::Deserialize(error.to_string())
+    }
+}
+
+impl DependabotTrackerError {
+    /// Whether the error might go away on its own: a server error, a dropped connection, or a
+    /// timeout. Anything else, like a 404 or a response that doesn't parse, would only fail the
+    /// same way again, and a rate limit lasts longer than retries wait.
+    pub fn is_transient(&self) -> bool {
+        match self {
+            DependabotTrackerError::Network(_) => true,
+            DependabotTrackerError::GitHubApi { status, .. } => status.is_server_error(),
+            _ => false,
+        }
+    }
+
+    /// What the user can do about the error, shown after it in the error popup.
+    pub fn advice(&self) -> Option<String> {
+        match self {
+            DependabotTrackerError::Network(_) => Some(
+                "Check your connection. The saved data is still shown; start with --offline to browse it without fetching"
+                    .to_string(),
+            ),
+            DependabotTrackerError::GitHubApi { status, .. } => match *status {
+                StatusCode::UNAUTHORIZED => Some(
+                    "The token was rejected, it may have expired. Log in again or set a new PAT"
+                        .to_string(),
+                ),
+                StatusCode::FORBIDDEN | StatusCode::NOT_FOUND => Some(
+                    "The token may be missing the Dependabot alerts permission or the repo and security_events scopes"
+                        .to_string(),
+                ),
+                status if status.is_server_error() => {
+                    Some("GitHub is having trouble, try again in a few minutes".to_string())
+                }
+                _ => None,
+            },
+            DependabotTrackerError::RateLimited { reset_at } => Some(format!(
+                "Try again after {}, or refresh only the stale repositories to use fewer requests",
+                reset_at.with_timezone(&Local).format("%H:%M")
+            )),
+            DependabotTrackerError::Database(_) => Some(
+                "The saved data may be damaged; fetching everything again writes a new snapshot"
+                    .to_string(),
+            ),
+            DependabotTrackerError::Config(_) => {
+                Some("Fix the config file and start the app again".to_string())
+            }
+            _ => None,
+        }
+    }
+}
+
+/// The response, or the error for its status: `RateLimited` when GitHub says no requests are
+/// left, otherwise `GitHubApi` for any 4xx or 5xx.
+pub fn check_response(response: Response) -> Result<Response, DependabotTrackerError> {
+    Ok(check_rate_limit(response)?.error_for_status()?)
+}
+
+/// The response, or `RateLimited` if GitHub says no requests are left, for responses whose
+/// other errors are handled by the caller.
+pub fn check_rate_limit(response: Response) -> Result<Response, DependabotTrackerError> {
+    let status = response.status();
+    if status == StatusCode::FORBIDDEN || status == StatusCode::TOO_MANY_REQUESTS {
+        if let Some(reset_at) = rate_limit_reset(response.headers()) {
+            return Err(DependabotTrackerError::RateLimited { reset_at });
+        }
+    }
+
+    Ok(response)
+}
+
+/// When GitHub's rate limit resets, if the `x-ratelimit-*` headers say it's used up.
+fn rate_limit_reset(headers: &HeaderMap) -> Option<DateTime<Utc>> {
+    let header = |name: &str| headers.get(name)?.to_str().ok()?.parse::<i64>().ok();
+    if header("x-ratelimit-remaining")? != 0 {
+        return None;
+    }
+
+    Utc.timestamp_opt(header("x-ratelimit-reset")?, 0).single()
+}
+
+#[cfg(test)]
+mod tests {
+    use reqwest::header::HeaderValue;
+
+    use super::*;
+
+    #[test]
+    fn server_errors_are_transient() {
+        let error = DependabotTrackerError::GitHubApi {
+            status: StatusCode::BAD_GATEWAY,
+            message: "Bad Gateway".to_string(),
+        };
+
+        assert!(error.is_transient());
+        assert!(error.advice().is_some());
+    }
+
+    #[test]
+    fn client_errors_are_not_transient() {
+        let error = DependabotTrackerError::GitHubApi {
+            status: StatusCode::NOT_FOUND,
+            message: "Not Found".to_string(),
+        };
+
+        assert!(!error.is_transient());
+    }
+
+    #[test]
+    fn rate_limit_is_read_from_headers() {
+        let mut headers = HeaderMap::new();
+        headers.insert("x-ratelimit-remaining", HeaderValue::from_static("0"));
+        headers.insert("x-ratelimit-reset", HeaderValue::from_static("1700000000"));
+
+        assert_eq!(
+            rate_limit_reset(&headers),
+            Utc.timestamp_opt(1_700_000_000, 0).single()
+        );
+    }
+
+    #[test]
+    fn remaining_requests_are_not_a_rate_limit() {
+        let mut headers = HeaderMap::new();
+        headers.insert("x-ratelimit-remaining", HeaderValue::from_static("12"));
+        headers.insert("x-ratelimit-reset", HeaderValue::from_static("1700000000"));
+
+        assert_eq!(rate_limit_reset(&headers), None);
+    }
+
+    #[test]
+    fn malformed_json_is_a_deserialize_error() {
+        let error = DependabotTrackerError::from(
+            serde_json::from_str::<serde_json::Value>("{").unwrap_err(),
+        );
+
+        assert!(matches!(error, DependabotTrackerError::Deserialize(_)));
+    }
+}
@@ -0,0 +1,138 @@
+use std::fmt::{self, Display, Formatter};
+use std::fs::{self, OpenOptions};
+use std::io::{BufWriter, Write};
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use crate::dependabot::{Dependabot, DependabotSeverity, DependabotState};
+use crate::error::DependabotTrackerError;
+use crate::repository::Repository;
+use crate::storage::data_directory;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AlertEventKind {
+    Opened,
+    Fixed,
+    Dismissed,
+    SeverityChanged,
+}
+
+impl Display for AlertEventKind {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            AlertEventKind::Opened => write!(f, "New"),
+            AlertEventKind::Fixed => write!(f, "Fixed"),
+            AlertEventKind::Dismissed => write!(f, "Dismissed"),
+            AlertEventKind::SeverityChanged => write!(f, "Severity"),
+        }
+    }
+}
+
+/// A change in an alert's lifecycle, recorded as one line of the event log.
+#[derive(Debug, Clone, Serialize)]
+pub struct AlertEvent {
+    pub timestamp: DateTime<Utc>,
+    pub kind: AlertEventKind,
+    pub repository: String,
+    pub alert_number: u32,
+    pub dependency_name: String,
+    pub severity: DependabotSeverity,
+    pub previous_severity: Option<DependabotSeverity>,
+}
+
+impl AlertEvent {
+    /// A one-line description for the What's New popup and Changes screen, e.g.
+    /// `my-repo #12 lodash (High -> Critical)`.
+    pub fn describe(&self) -> String {
+        let severity = match self.previous_severity {
+            Some(previous_severity) if self.kind == AlertEventKind::SeverityChanged => {
+                format!("{} -> {}", previous_severity, self.severity)
+            }
+            _ => self.severity.to_string(),
+        };
+
+        format!(
+            "{} #{} {} ({})",
+            self.repository, self.alert_number, self.dependency_name, severity
+        )
+    }
+}
+
+/// How many of the events are of `kind`.
+pub fn count_events(events: &[AlertEvent], kind: AlertEventKind) -> usize {
+    events.iter().filter(|event| event.kind == kind).count()
+}
+
+/// How an alert changed since it was last seen, if it changed in a way worth recording.
+fn alert_transition(previous: Option<&Dependabot>, current: &Dependabot) -> Option<AlertEventKind> {
+    let Some(previous) = previous else {
+        return (current.state == DependabotState::Open).then_some(AlertEventKind::Opened);
+    };
+
+    if previous.state != current.state {
+        return match current.state {
+            DependabotState::Open => Some(AlertEventKind::Opened),
+            DependabotState::Fixed => Some(AlertEventKind::Fixed),
+            DependabotState::Dismissed | DependabotState::AutoDismissed => {
+                Some(AlertEventKind::Dismissed)
+            }
+        };
+    }
+    (previous.severity != current.severity).then_some(AlertEventKind::SeverityChanged)
+}
+
+/// The lifecycle changes between the previous and updated alerts of each repository.
+pub fn detect_alert_events(previous: &[Repository], updated: &[Repository]) -> Vec<AlertEvent> {
+    let timestamp = Utc::now();
+    let mut events = Vec::new();
+
+    for repo in updated {
+        let previous_repo = previous.iter().find(|previous| previous.id == repo.id);
+        for dependabot in repo.dependabots.iter() {
+            let previous_dependabot = previous_repo.and_then(|previous_repo| {
+                previous_repo
+                    .dependabots
+                    .iter()
+                    .find(|previous_dependabot| previous_dependabot.number == dependabot.number)
+            });
+            if let Some(kind) = alert_transition(previous_dependabot, dependabot) {
+                events.push(AlertEvent {
+                    timestamp,
+                    kind,
+                    repository: repo.full_name.clone(),
+                    alert_number: dependabot.number,
+                    dependency_name: dependabot.dependency_name.clone(),
+                    severity: dependabot.severity,
+                    previous_severity: previous_dependabot
+                        .map(|previous_dependabot| previous_dependabot.severity),
+                });
+            }
+        }
+    }
+
+    events
+}
+
+/// Append events to `alert_events.jsonl` in the data directory, one JSON object per line.
+pub fn append_alert_events(events: &[AlertEvent]) -> Result<(), DependabotTrackerError> {
+    if events.is_empty() {
+        return Ok(());
+    }
+
+    fs::create_dir_all(data_directory())?;
+    let file_location = data_directory().join("alert_events.jsonl");
+    let file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(file_location)?;
+    let mut writer = BufWriter::new(file);
+    for event in events {
+        serde_json::to_writer(&mut writer, event)?;
+        writeln!(writer)?;
+    }
+    writer.flush()?;
+
+    Ok(())
+}
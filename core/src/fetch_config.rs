@@ -0,0 +1,124 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::Deserialize;
+
+use crate::repository::FetchMethod;
+use crate::retry::RetryConfig;
+
+/// Which repositories to fetch and how: the settings at the top level of the config file that
+/// the GitHub and GitLab clients read.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct FetchConfig {
+    // how many results to request per page from the GitHub API (max 100)
+    pub per_page: u32,
+    // the maximum number of repositories to fetch alerts for
+    pub max_repositories: Option<usize>,
+    // `owner/repo` entries for repositories to track that the user doesn't own
+    pub watched_repositories: Vec<String>,
+    // organizations whose repositories are tracked alongside the user's own
+    pub organizations: Vec<String>,
+    // names or `owner/repo` entries for repositories to leave out of the fetch and the list,
+    // which can use `*` and `?` wildcards, e.g. `*-deprecated`
+    pub excluded_repositories: Vec<String>,
+    // whether to leave out archived repositories
+    pub exclude_archived: bool,
+    // whether to leave out forks
+    pub exclude_forks: bool,
+    // which of the user's repositories to fetch: a comma-separated list of `owner`,
+    // `collaborator`, and `organization_member`
+    pub affiliation: String,
+    // leave out repositories that haven't been pushed to in this many months
+    pub pushed_within_months: Option<u32>,
+    // how many times to try fetching a repository's alerts, and how long to wait in between
+    pub retry: RetryConfig,
+    // how to list the repositories and their alerts from GitHub, `rest` or `graphql`
+    pub fetch_method: FetchMethod,
+}
+
+impl Default for FetchConfig {
+    fn default() -> Self {
+        FetchConfig {
+            per_page: 100,
+            max_repositories: None,
+            watched_repositories: Vec::new(),
+            organizations: Vec::new(),
+            excluded_repositories: Vec::new(),
+            exclude_archived: false,
+            exclude_forks: false,
+            affiliation: "owner".to_string(),
+            pushed_within_months: None,
+            retry: RetryConfig::default(),
+            fetch_method: FetchMethod::default(),
+        }
+    }
+}
+
+impl FetchConfig {
+    pub fn per_page(&self) -> u32 {
+        self.per_page.clamp(1, 100)
+    }
+
+    /// Whether a repository is left out by `excluded_repositories`, `exclude_archived`, or
+    /// `exclude_forks`. Patterns without an owner are matched against the repository's name.
+    pub fn is_excluded(&self, full_name: &str, archived: bool, fork: bool) -> bool {
+        let name = full_name
+            .split_once('/')
+            .map_or(full_name, |(_, name)| name);
+
+        (self.exclude_archived && archived)
+            || (self.exclude_forks && fork)
+            || self.excluded_repositories.iter().any(|pattern| {
+                if pattern.contains('/') {
+                    matches_pattern(pattern, full_name)
+                } else {
+                    matches_pattern(pattern, name)
+                }
+            })
+    }
+
+    /// Whether a repository was pushed to recently enough to fetch, per `pushed_within_months`.
+    /// Repositories that have never been pushed to only count as recent without the setting.
+    pub fn is_recently_pushed(&self, pushed_at: Option<DateTime<Utc>>) -> bool {
+        let Some(months) = self.pushed_within_months else {
+            return true;
+        };
+
+        pushed_at.is_some_and(|pushed_at| {
+            Utc::now().signed_duration_since(pushed_at) <= Duration::days(i64::from(months) * 30)
+        })
+    }
+
+    /// Whether any repositories could be excluded, so the list needs filtering.
+    pub fn has_exclusions(&self) -> bool {
+        self.exclude_archived || self.exclude_forks || !self.excluded_repositories.is_empty()
+    }
+}
+
+/// Case-insensitively match `text` against a pattern where `*` matches any run of characters
+/// and `?` matches any one character.
+fn matches_pattern(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.to_lowercase().chars().collect();
+    let text: Vec<char> = text.to_lowercase().chars().collect();
+    let (mut p, mut t) = (0, 0);
+    // where the last `*` was in the pattern, and how much of the text it had matched
+    let mut backtrack = None;
+
+    while t < text.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == text[t]) {
+            p += 1;
+            t += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            backtrack = Some((p, t));
+            p += 1;
+        } else if let Some((star, matched)) = backtrack {
+            // let the last `*` swallow one more character and try again
+            backtrack = Some((star, matched + 1));
+            p = star + 1;
+            t = matched + 1;
+        } else {
+            return false;
+        }
+    }
+
+    pattern[p..].iter().all(|c| *c == '*')
+}
@@ -0,0 +1,87 @@
+use reqwest::{Client, Method, RequestBuilder};
+
+pub const GITHUB_API_URL: &str = "https://api.github.com";
+
+/// Where requests to the GitHub API are sent. Paths like `/user/repos` are joined to the
+/// client's API URL, so the fetch can be pointed somewhere other than GitHub (like a mock
+/// server in tests), while full URLs such as the next page from a `Link` header are requested
+/// as they are.
+pub trait GithubClient: Send + Sync {
+    /// The root API paths are joined to, without a trailing slash.
+    fn api_url(&self) -> &str;
+
+    /// Start a request to a full URL.
+    fn request(&self, method: Method, url: &str) -> RequestBuilder;
+
+    /// Whether a full URL, like the next page from a `Link` header, is on the client's API, so
+    /// it can be sent the token.
+    fn is_api_url(&self, url: &str) -> bool {
+        url.strip_prefix(self.api_url())
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with(['/', '?']))
+    }
+
+    /// The full URL for an API path, or `path` itself if it's already a full URL.
+    fn url(&self, path: &str) -> String {
+        if path.starts_with("https://") || path.starts_with("http://") {
+            path.to_string()
+        } else {
+            format!("{}{}", self.api_url(), path)
+        }
+    }
+
+    fn get(&self, path: &str) -> RequestBuilder {
+        self.request(Method::GET, &self.url(path))
+    }
+
+    fn post(&self, path: &str) -> RequestBuilder {
+        self.request(Method::POST, &self.url(path))
+    }
+
+    fn put(&self, path: &str) -> RequestBuilder {
+        self.request(Method::PUT, &self.url(path))
+    }
+
+    fn patch(&self, path: &str) -> RequestBuilder {
+        self.request(Method::PATCH, &self.url(path))
+    }
+}
+
+/// Sends GitHub API requests with a reqwest client.
+#[derive(Debug, Clone)]
+pub struct HttpGithubClient {
+    client: Client,
+    api_url: String,
+}
+
+impl HttpGithubClient {
+    pub fn new(client: Client, api_url: &str) -> Self {
+        HttpGithubClient {
+            client,
+            api_url: api_url.trim_end_matches('/').to_string(),
+        }
+    }
+}
+
+impl GithubClient for HttpGithubClient {
+    fn api_url(&self) -> &str {
+        &self.api_url
+    }
+
+    fn request(&self, method: Method, url: &str) -> RequestBuilder {
+        self.client.request(method, url)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_urls_on_the_api_are_api_urls() {
+        let client = HttpGithubClient::new(Client::new(), GITHUB_API_URL);
+
+        assert!(client.is_api_url("https://api.github.com/user/repos?page=2"));
+        assert!(!client.is_api_url("https://api.github.com.example.com/user/repos"));
+        assert!(!client.is_api_url("https://example.com/user/repos"));
+    }
+}
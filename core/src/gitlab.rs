@@ -0,0 +1,517 @@
+use chrono::{DateTime, Utc};
+use futures::stream::{self, StreamExt};
+use reqwest::header::{HeaderMap, HeaderValue, USER_AGENT};
+use reqwest::{Client, Response};
+use serde::de::{DeserializeOwned, IgnoredAny};
+use serde::Deserialize;
+use tokio_util::sync::CancellationToken;
+
+use crate::dependabot::{Dependabot, DependabotSeverity, DependabotState};
+use crate::error::DependabotTrackerError;
+use crate::fetch_config::FetchConfig;
+use crate::provider::{ProviderKind, SecurityProvider};
+use crate::repository::{
+    invalid_token, AlertsStatus, FetchOutcome, FetchProgress, FetchReport, PageProgress,
+    Repository, MAX_CONCURRENT_REQUESTS,
+};
+use crate::repository_list::RepositoryList;
+use crate::retry::with_retry;
+use crate::scanning::{
+    CodeScanningAlert, CodeScanningState, SecretScanningAlert, SecretScanningState,
+};
+use crate::storage::save_snapshot;
+use crate::trace_dbg;
+
+pub const GITLAB_URL: &str = "https://gitlab.com";
+
+/// Fetches the projects the user is a member of and their vulnerability reports from the
+/// GitLab API.
+#[derive(Debug, Clone)]
+pub struct GitLabProvider {
+    pub token: String,
+    // the instance to fetch from, e.g. `https://gitlab.com` or a self-managed one
+    pub base_url: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitLabProject {
+    id: u32,
+    name: String,
+    path_with_namespace: String,
+    web_url: String,
+    // `public`, `internal`, or `private`
+    visibility: Option<String>,
+    #[serde(default)]
+    archived: bool,
+    // only set on forks
+    forked_from_project: Option<IgnoredAny>,
+    last_activity_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    topics: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitLabVulnerability {
+    id: u32,
+    title: String,
+    description: Option<String>,
+    // `detected`, `confirmed`, `resolved`, or `dismissed`
+    state: String,
+    // `critical`, `high`, `medium`, `low`, `info`, or `unknown`
+    severity: String,
+    // the scanner that found it, e.g. `dependency_scanning` or `sast`
+    report_type: String,
+    created_at: String,
+    updated_at: String,
+    dismissed_at: Option<String>,
+    resolved_at: Option<String>,
+    finding: Option<GitLabFinding>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitLabFinding {
+    // the scanner's report on the finding, as a JSON string
+    raw_metadata: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct FindingMetadata {
+    location: FindingLocation,
+    identifiers: Vec<FindingIdentifier>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct FindingLocation {
+    file: Option<String>,
+    start_line: Option<u32>,
+    dependency: Option<FindingDependency>,
+}
+
+#[derive(Debug, Deserialize)]
+struct FindingDependency {
+    package: FindingPackage,
+    version: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct FindingPackage {
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct FindingIdentifier {
+    // e.g. `cve`, `ghsa`, or `cwe`
+    #[serde(rename = "type")]
+    identifier_type: String,
+    value: String,
+}
+
+impl FindingMetadata {
+    fn identifier(&self, identifier_type: &str) -> Option<String> {
+        self.identifiers
+            .iter()
+            .find(|identifier| {
+                identifier
+                    .identifier_type
+                    .eq_ignore_ascii_case(identifier_type)
+            })
+            .map(|identifier| identifier.value.clone())
+    }
+}
+
+impl GitLabVulnerability {
+    fn metadata(&self) -> FindingMetadata {
+        self.finding
+            .as_ref()
+            .and_then(|finding| finding.raw_metadata.as_deref())
+            .and_then(|raw_metadata| serde_json::from_str(raw_metadata).ok())
+            .unwrap_or_default()
+    }
+
+    /// The severity, or `None` for findings GitLab rates as `info` or `unknown`.
+    fn severity(&self) -> Option<DependabotSeverity> {
+        match self.severity.as_str() {
+            "critical" => Some(DependabotSeverity::Critical),
+            "high" => Some(DependabotSeverity::High),
+            "medium" => Some(DependabotSeverity::Medium),
+            "low" => Some(DependabotSeverity::Low),
+            _ => None,
+        }
+    }
+
+    fn html_url(&self, project: &GitLabProject) -> String {
+        format!("{}/-/security/vulnerabilities/{}", project.web_url, self.id)
+    }
+
+    fn to_dependabot(&self, project: &GitLabProject) -> Dependabot {
+        let metadata = self.metadata();
+        let dependency = metadata.location.dependency.as_ref();
+        Dependabot {
+            number: self.id,
+            state: match self.state.as_str() {
+                "resolved" => DependabotState::Fixed,
+                "dismissed" => DependabotState::Dismissed,
+                _ => DependabotState::Open,
+            },
+            severity: self.severity().unwrap_or(DependabotSeverity::Low),
+            html_url: self.html_url(project),
+            created_at: self.created_at.clone(),
+            updated_at: self.updated_at.clone(),
+            dismissed_at: self.dismissed_at.clone(),
+            auto_dismissed_at: None,
+            fixed_at: self.resolved_at.clone(),
+            dismissed_by: None,
+            dismissed_reason: None,
+            dismissed_comment: None,
+            manifest_path: metadata.location.file.clone(),
+            scope: None,
+            // `dependency_scanning` or `container_scanning`
+            dependency_ecosystem: self.report_type.trim_end_matches("_scanning").to_string(),
+            dependency_name: dependency.map_or_else(
+                || self.title.clone(),
+                |dependency| dependency.package.name.clone(),
+            ),
+            vulnerable_version_range: dependency
+                .and_then(|dependency| dependency.version.as_ref())
+                .map(|version| format!("= {}", version)),
+            ghsa_id: metadata.identifier("ghsa"),
+            cve_id: metadata.identifier("cve"),
+            cvss_score: None,
+            summary: Some(self.title.clone()),
+            description: self.description.clone(),
+            first_patched_version: None,
+            license: None,
+        }
+    }
+
+    fn to_code_scanning_alert(&self, project: &GitLabProject) -> CodeScanningAlert {
+        let metadata = self.metadata();
+        CodeScanningAlert {
+            number: self.id,
+            state: match self.state.as_str() {
+                "resolved" => CodeScanningState::Fixed,
+                "dismissed" => CodeScanningState::Dismissed,
+                _ => CodeScanningState::Open,
+            },
+            severity: self.severity(),
+            html_url: self.html_url(project),
+            created_at: self.created_at.clone(),
+            rule_id: metadata
+                .identifiers
+                .first()
+                .map(|identifier| identifier.value.clone()),
+            description: self.title.clone(),
+            tool: "GitLab SAST".to_string(),
+            location: metadata
+                .location
+                .file
+                .map(|file| match metadata.location.start_line {
+                    Some(start_line) => format!("{}:{}", file, start_line),
+                    None => file,
+                }),
+        }
+    }
+
+    fn to_secret_scanning_alert(&self, project: &GitLabProject) -> SecretScanningAlert {
+        let open = matches!(self.state.as_str(), "detected" | "confirmed");
+        SecretScanningAlert {
+            number: self.id,
+            state: if open {
+                SecretScanningState::Open
+            } else {
+                SecretScanningState::Resolved
+            },
+            html_url: self.html_url(project),
+            created_at: self.created_at.clone(),
+            secret_type_display_name: Some(self.title.clone()),
+            resolution: (!open).then(|| self.state.clone()),
+            push_protection_bypassed: None,
+        }
+    }
+}
+
+/// The number in a pagination header like `x-next-page`, which GitLab leaves empty on the last
+/// page.
+fn header_number(response: &Response, name: &str) -> Option<usize> {
+    response.headers().get(name)?.to_str().ok()?.parse().ok()
+}
+
+impl GitLabProvider {
+    fn api_url(&self) -> String {
+        format!("{}/api/v4", self.base_url.trim_end_matches('/'))
+    }
+
+    fn headers(&self) -> Result<HeaderMap, DependabotTrackerError> {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "PRIVATE-TOKEN",
+            HeaderValue::from_str(&self.token).map_err(|_| invalid_token())?,
+        );
+        headers.insert(USER_AGENT, HeaderValue::from_static("reqwest"));
+
+        Ok(headers)
+    }
+
+    async fn get(&self, client: &Client, url: &str) -> Result<Response, DependabotTrackerError> {
+        client
+            .get(url)
+            .headers(self.headers()?)
+            .send()
+            .await
+            .map_err(DependabotTrackerError::from)
+    }
+
+    /// Read a JSON array from `response`, the first page of `url` (which has a query string),
+    /// and every page after it, following the `x-next-page` header.
+    async fn read_all_pages<T: DeserializeOwned>(
+        &self,
+        client: &Client,
+        url: &str,
+        mut response: Response,
+        label: &str,
+        progress: &(dyn Fn(FetchProgress) + Sync),
+    ) -> Result<Vec<T>, DependabotTrackerError> {
+        let mut items = Vec::new();
+        let mut page = 1;
+        loop {
+            response = response.error_for_status()?;
+            let next_page = header_number(&response, "x-next-page");
+            progress(FetchProgress::Page(PageProgress {
+                label: label.to_string(),
+                page,
+                last_page: header_number(&response, "x-total-pages"),
+            }));
+            items.extend(response.json::<Vec<T>>().await?);
+
+            let Some(next_page) = next_page else {
+                return Ok(items);
+            };
+            page = next_page;
+            response = self.get(client, &format!("{}&page={}", url, page)).await?;
+        }
+    }
+
+    /// Fetch a project's vulnerability report. Dependency and container scanning findings
+    /// become its alerts, SAST findings its code scanning alerts, and secret detection findings
+    /// its secret scanning alerts. Findings from other scanners, like DAST, are left out.
+    async fn fetch_project_alerts(
+        &self,
+        client: &Client,
+        project: &GitLabProject,
+        config: &FetchConfig,
+        progress: &(dyn Fn(FetchProgress) + Sync),
+    ) -> Result<Repository, DependabotTrackerError> {
+        let fetch_project_vulnerabilities_trace = format!(
+            "fetching vulnerabilities for {}",
+            project.path_with_namespace
+        );
+        trace_dbg!(level: tracing::Level::INFO, fetch_project_vulnerabilities_trace);
+
+        let url = format!(
+            "{}/projects/{}/vulnerabilities?per_page={}",
+            self.api_url(),
+            project.id,
+            config.per_page()
+        );
+        let response = self.get(client, &url).await?;
+
+        let mut repository = Repository {
+            id: project.id,
+            name: project.name.clone(),
+            full_name: project.path_with_namespace.clone(),
+            private: project.visibility.as_deref() != Some("public"),
+            url: project.web_url.clone(),
+            archived: project.archived,
+            fork: project.forked_from_project.is_some(),
+            visibility: project.visibility.clone(),
+            pushed_at: project.last_activity_at,
+            dependabots: Vec::new(),
+            low_alerts: 0,
+            medium_alerts: 0,
+            high_alerts: 0,
+            critical_alerts: 0,
+            total_active_alerts: 0,
+            alerts_status: AlertsStatus::Enabled,
+            watched: false,
+            has_security_policy: None,
+            last_fetched_at: Some(Utc::now()),
+            topics: project.topics.clone(),
+            alerts_etag: None,
+            pull_requests: Vec::new(),
+            code_scanning_alerts: Vec::new(),
+            secret_scanning_alerts: Vec::new(),
+            provider: ProviderKind::GitLab,
+        };
+
+        // the vulnerability report needs GitLab Ultimate, and projects without it respond
+        // with a 403, so only a 404 means the token can't see the project
+        if response.status().is_client_error() {
+            repository.alerts_status = if response.status() == reqwest::StatusCode::NOT_FOUND {
+                AlertsStatus::NoAccess
+            } else {
+                AlertsStatus::Disabled
+            };
+            let vulnerabilities_unavailable = format!(
+                "Vulnerabilities unavailable for {}",
+                project.path_with_namespace
+            );
+            trace_dbg!(level: tracing::Level::WARN, vulnerabilities_unavailable);
+            return Ok(repository);
+        }
+
+        let label = format!("{} vulnerabilities", project.name);
+        let vulnerabilities: Vec<GitLabVulnerability> = self
+            .read_all_pages(client, &url, response, &label, progress)
+            .await?;
+        for vulnerability in vulnerabilities.iter() {
+            match vulnerability.report_type.as_str() {
+                "dependency_scanning" | "container_scanning" => repository
+                    .dependabots
+                    .push(vulnerability.to_dependabot(project)),
+                "sast" => repository
+                    .code_scanning_alerts
+                    .push(vulnerability.to_code_scanning_alert(project)),
+                "secret_detection" => repository
+                    .secret_scanning_alerts
+                    .push(vulnerability.to_secret_scanning_alert(project)),
+                _ => {}
+            }
+        }
+        repository.update_alert_counts();
+
+        Ok(repository)
+    }
+}
+
+impl SecurityProvider for GitLabProvider {
+    async fn fetch_repos(
+        &self,
+        previous_repos: &[Repository],
+        config: &FetchConfig,
+        progress: &(dyn Fn(FetchProgress) + Sync),
+        cancellation: &CancellationToken,
+    ) -> Result<FetchOutcome, DependabotTrackerError> {
+        let client = config.retry.client();
+        let url = format!(
+            "{}/projects?membership=true&per_page={}",
+            self.api_url(),
+            config.per_page()
+        );
+        let list = async {
+            let response = self.get(&client, &url).await?;
+            self.read_all_pages(&client, &url, response, "Projects", progress)
+                .await
+        };
+        let mut projects: Vec<GitLabProject> = tokio::select! {
+            projects = list => projects?,
+            _ = cancellation.cancelled() => return Ok(FetchOutcome::cancelled(previous_repos)),
+        };
+
+        projects.retain(|project| {
+            !config.is_excluded(
+                &project.path_with_namespace,
+                project.archived,
+                project.forked_from_project.is_some(),
+            ) && config.is_recently_pushed(project.last_activity_at)
+        });
+        let truncated = config
+            .max_repositories
+            .is_some_and(|max_repositories| projects.len() > max_repositories);
+        if let Some(max_repositories) = config.max_repositories {
+            projects.truncate(max_repositories);
+        }
+
+        progress(FetchProgress::RepositoriesQueued(projects.len()));
+        let requests: Vec<_> = projects
+            .iter()
+            .map(|project| {
+                let client = &client;
+                async move {
+                    let fetch = with_retry(&config.retry, &project.name, || {
+                        self.fetch_project_alerts(client, project, config, progress)
+                    });
+                    // a cancelled fetch leaves the projects it hasn't finished with their
+                    // saved data
+                    let result = tokio::select! {
+                        result = fetch => result,
+                        _ = cancellation.cancelled() => return (project, None),
+                    };
+                    if let Ok(repository) = &result {
+                        progress(FetchProgress::RepositoryFetched(Box::new(
+                            repository.clone(),
+                        )));
+                    }
+                    progress(FetchProgress::RepositoryDone {
+                        name: project.name.clone(),
+                        failed: result.is_err(),
+                    });
+                    (project, Some(result))
+                }
+            })
+            .collect();
+        let results: Vec<_> = stream::iter(requests)
+            .buffered(MAX_CONCURRENT_REQUESTS)
+            .collect()
+            .await;
+
+        let mut report = FetchReport::default();
+        let repositories: Vec<Repository> = results
+            .into_iter()
+            .filter_map(|(project, result)| {
+                let Some(result) = result else {
+                    return previous_repos
+                        .iter()
+                        .find(|previous| previous.id == project.id)
+                        .cloned();
+                };
+                report.record(&project.path_with_namespace, &result);
+                result
+                    .map_err(|e| {
+                        let fetch_error = format!(
+                            "Failed to fetch vulnerabilities for {}: {}",
+                            project.name, e
+                        );
+                        trace_dbg!(level: tracing::Level::WARN, fetch_error);
+                    })
+                    .ok()
+            })
+            .collect();
+
+        save_snapshot(&repositories)?;
+
+        Ok(FetchOutcome {
+            repositories: RepositoryList::with_respositories(repositories),
+            token_expiration: None,
+            truncated,
+            partial: cancellation.is_cancelled(),
+            cancelled: cancellation.is_cancelled(),
+            warnings: Vec::new(),
+            report,
+        })
+    }
+
+    async fn fetch_alerts(
+        &self,
+        repository: &Repository,
+        config: &FetchConfig,
+        progress: &(dyn Fn(FetchProgress) + Sync),
+    ) -> Result<Repository, DependabotTrackerError> {
+        let client = config.retry.client();
+        let url = format!("{}/projects/{}", self.api_url(), repository.id);
+        let response = self.get(&client, &url).await?;
+        if !response.status().is_success() {
+            let project_not_found = format!("Unable to fetch project {}", repository.full_name);
+            trace_dbg!(level: tracing::Level::WARN, project_not_found);
+            return Ok(Repository {
+                alerts_status: AlertsStatus::NoAccess,
+                ..repository.clone()
+            });
+        }
+        let project: GitLabProject = response.json().await?;
+
+        self.fetch_project_alerts(&client, &project, config, progress)
+            .await
+    }
+}
@@ -0,0 +1,618 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use futures::stream::{self, StreamExt};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::dependabot::{
+    AlertDependency, AlertRepository, AlertUser, Cvss, DependabotSeverity, DependabotState,
+    DependencyScope, FirstPatchedVersion, GithubDependabot, Package, SecurityAdvisory,
+    SecurityVulnerability,
+};
+use crate::error::{check_response, DependabotTrackerError};
+use crate::fetch_config::FetchConfig;
+use crate::github_client::GithubClient;
+use crate::repository::{
+    github_headers, token_expiration, FetchProgress, GitHubRepository, PageProgress,
+    RepositoryListing, RepositoryOwner, MAX_CONCURRENT_REQUESTS,
+};
+use crate::trace_dbg;
+
+const GRAPHQL_PATH: &str = "/graphql";
+
+// each repository brings up to a page of alerts with it, so fewer are listed per query than
+// the REST API's page size to stay well within GitHub's limits on how much a query can return
+const REPOSITORIES_PER_QUERY: u32 = 50;
+const ALERTS_PER_QUERY: u32 = 100;
+
+const REPOSITORY_FIELDS: &str = "
+fragment RepositoryFields on Repository {
+  id
+  databaseId
+  name
+  nameWithOwner
+  isPrivate
+  url
+  isArchived
+  isFork
+  visibility
+  pushedAt
+  owner { login }
+  repositoryTopics(first: 20) { nodes { topic { name } } }
+  vulnerabilityAlerts(first: $alertsFirst) { ...AlertPage }
+}
+";
+
+const ALERT_FIELDS: &str = "
+fragment AlertPage on RepositoryVulnerabilityAlertConnection {
+  pageInfo { hasNextPage endCursor }
+  nodes {
+    number
+    state
+    createdAt
+    dismissedAt
+    autoDismissedAt
+    fixedAt
+    dismisser { login }
+    dismissReason
+    dismissComment
+    vulnerableManifestPath
+    dependencyScope
+    securityVulnerability {
+      severity
+      package { ecosystem name }
+      vulnerableVersionRange
+      firstPatchedVersion { identifier }
+    }
+    securityAdvisory {
+      ghsaId
+      summary
+      description
+      identifiers { type value }
+      cvss { score }
+    }
+  }
+}
+";
+
+const VIEWER_QUERY: &str = "
+query($first: Int!, $after: String, $affiliations: [RepositoryAffiliation], $alertsFirst: Int!) {
+  viewer {
+    repositories(first: $first, after: $after, affiliations: $affiliations, ownerAffiliations: $affiliations) {
+      totalCount
+      pageInfo { hasNextPage endCursor }
+      nodes { ...RepositoryFields }
+    }
+  }
+}
+";
+
+const ORGANIZATION_QUERY: &str = "
+query($login: String!, $first: Int!, $after: String, $alertsFirst: Int!) {
+  organization(login: $login) {
+    repositories(first: $first, after: $after) {
+      totalCount
+      pageInfo { hasNextPage endCursor }
+      nodes { ...RepositoryFields }
+    }
+  }
+}
+";
+
+const ALERTS_QUERY: &str = "
+query($id: ID!, $after: String, $alertsFirst: Int!) {
+  node(id: $id) {
+    ... on Repository {
+      vulnerabilityAlerts(first: $alertsFirst, after: $after) { ...AlertPage }
+    }
+  }
+}
+";
+
+#[derive(Debug, Deserialize)]
+struct GraphqlResponse<T> {
+    data: Option<T>,
+    #[serde(default)]
+    errors: Vec<GraphqlError>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GraphqlError {
+    message: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PageInfo {
+    has_next_page: bool,
+    end_cursor: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ViewerData {
+    viewer: RepositoryOwnerNode,
+}
+
+#[derive(Debug, Deserialize)]
+struct OrganizationData {
+    // `None` if there's no organization with the login or the token can't see it
+    organization: Option<RepositoryOwnerNode>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RepositoryOwnerNode {
+    repositories: RepositoryConnection,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RepositoryConnection {
+    total_count: usize,
+    page_info: PageInfo,
+    // a repository the query partly failed for comes back as `null`
+    nodes: Vec<Option<GraphqlRepository>>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GraphqlRepository {
+    // the global node ID, to page through the rest of the repository's alerts
+    id: String,
+    database_id: u32,
+    name: String,
+    name_with_owner: String,
+    is_private: bool,
+    url: String,
+    is_archived: bool,
+    is_fork: bool,
+    // `PUBLIC`, `PRIVATE`, or `INTERNAL`
+    visibility: String,
+    pushed_at: Option<DateTime<Utc>>,
+    owner: GraphqlOwner,
+    repository_topics: TopicConnection,
+    // `None` if the token can't read the alerts or Dependabot alerts are turned off
+    vulnerability_alerts: Option<AlertConnection>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GraphqlOwner {
+    login: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct TopicConnection {
+    nodes: Vec<TopicNode>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TopicNode {
+    topic: Topic,
+}
+
+#[derive(Debug, Deserialize)]
+struct Topic {
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct AlertsData {
+    node: Option<AlertsNode>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AlertsNode {
+    vulnerability_alerts: Option<AlertConnection>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AlertConnection {
+    page_info: PageInfo,
+    nodes: Vec<GraphqlAlert>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GraphqlAlert {
+    number: u32,
+    // `OPEN`, `FIXED`, `DISMISSED`, or `AUTO_DISMISSED`
+    state: String,
+    created_at: String,
+    dismissed_at: Option<String>,
+    auto_dismissed_at: Option<String>,
+    fixed_at: Option<String>,
+    dismisser: Option<AlertUser>,
+    dismiss_reason: Option<String>,
+    dismiss_comment: Option<String>,
+    vulnerable_manifest_path: Option<String>,
+    // `RUNTIME` or `DEVELOPMENT`
+    dependency_scope: Option<String>,
+    security_vulnerability: GraphqlVulnerability,
+    security_advisory: Option<GraphqlAdvisory>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GraphqlVulnerability {
+    // `LOW`, `MODERATE`, `HIGH`, or `CRITICAL`
+    severity: String,
+    package: Package,
+    vulnerable_version_range: Option<String>,
+    first_patched_version: Option<FirstPatchedVersion>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GraphqlAdvisory {
+    ghsa_id: String,
+    summary: String,
+    description: String,
+    identifiers: Vec<AdvisoryIdentifier>,
+    cvss: Option<GraphqlCvss>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AdvisoryIdentifier {
+    // `GHSA` or `CVE`
+    #[serde(rename = "type")]
+    identifier_type: String,
+    value: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct GraphqlCvss {
+    score: f64,
+}
+
+impl GraphqlRepository {
+    fn to_github_repository(&self) -> GitHubRepository {
+        GitHubRepository {
+            id: self.database_id,
+            name: self.name.clone(),
+            full_name: self.name_with_owner.clone(),
+            private: self.is_private,
+            html_url: self.url.clone(),
+            archived: self.is_archived,
+            fork: self.is_fork,
+            visibility: Some(self.visibility.to_lowercase()),
+            pushed_at: self.pushed_at,
+            topics: self
+                .repository_topics
+                .nodes
+                .iter()
+                .map(|node| node.topic.name.clone())
+                .collect(),
+            owner: RepositoryOwner {
+                login: self.owner.login.clone(),
+            },
+        }
+    }
+}
+
+impl GraphqlAlert {
+    /// The alert as the REST API would've reported it, or `None` if it has a state or severity
+    /// the app doesn't know about.
+    fn into_github_dependabot(self, repository: &GitHubRepository) -> Option<GithubDependabot> {
+        let state = match self.state.as_str() {
+            "OPEN" => DependabotState::Open,
+            "FIXED" => DependabotState::Fixed,
+            "DISMISSED" => DependabotState::Dismissed,
+            "AUTO_DISMISSED" => DependabotState::AutoDismissed,
+            _ => return None,
+        };
+        let severity = match self.security_vulnerability.severity.as_str() {
+            "LOW" => DependabotSeverity::Low,
+            "MODERATE" => DependabotSeverity::Medium,
+            "HIGH" => DependabotSeverity::High,
+            "CRITICAL" => DependabotSeverity::Critical,
+            _ => return None,
+        };
+        let scope = match self.dependency_scope.as_deref() {
+            Some("RUNTIME") => Some(DependencyScope::Runtime),
+            Some("DEVELOPMENT") => Some(DependencyScope::Development),
+            _ => None,
+        };
+        // there's no update time, so it's taken to be the alert's latest change
+        let updated_at = [&self.dismissed_at, &self.auto_dismissed_at, &self.fixed_at]
+            .into_iter()
+            .flatten()
+            .chain([&self.created_at])
+            .max()
+            .cloned()
+            .unwrap_or_default();
+
+        Some(GithubDependabot {
+            number: self.number,
+            state,
+            security_vulnerability: SecurityVulnerability {
+                severity,
+                package: Package {
+                    ecosystem: self.security_vulnerability.package.ecosystem.to_lowercase(),
+                    name: self.security_vulnerability.package.name,
+                },
+                vulnerable_version_range: self.security_vulnerability.vulnerable_version_range,
+                first_patched_version: self.security_vulnerability.first_patched_version,
+            },
+            html_url: format!(
+                "{}/security/dependabot/{}",
+                repository.html_url, self.number
+            ),
+            created_at: self.created_at,
+            updated_at,
+            dismissed_at: self.dismissed_at,
+            auto_dismissed_at: self.auto_dismissed_at,
+            fixed_at: self.fixed_at,
+            dismissed_by: self.dismisser,
+            // GraphQL reports the reason as e.g. `tolerable risk`
+            dismissed_reason: self
+                .dismiss_reason
+                .map(|reason| reason.to_lowercase().replace(' ', "_")),
+            dismissed_comment: self.dismiss_comment,
+            dependency: Some(AlertDependency {
+                manifest_path: self.vulnerable_manifest_path,
+                scope,
+            }),
+            security_advisory: self.security_advisory.map(|advisory| SecurityAdvisory {
+                cve_id: advisory
+                    .identifiers
+                    .iter()
+                    .find(|identifier| identifier.identifier_type == "CVE")
+                    .map(|identifier| identifier.value.clone()),
+                ghsa_id: advisory.ghsa_id,
+                summary: advisory.summary,
+                description: advisory.description,
+                cvss: advisory.cvss.map(|cvss| Cvss {
+                    score: Some(cvss.score),
+                }),
+            }),
+            repository: Some(AlertRepository {
+                full_name: repository.full_name.clone(),
+            }),
+        })
+    }
+}
+
+fn graphql_error(message: String) -> DependabotTrackerError {
+    DependabotTrackerError::GraphQl(message)
+}
+
+/// Run a GraphQL query. Errors alongside data are logged rather than failing the query, as
+/// GitHub reports the parts it couldn't resolve that way, e.g. alerts the token can't read.
+async fn query<T: DeserializeOwned>(
+    token: &str,
+    client: &dyn GithubClient,
+    query: &str,
+    variables: Value,
+) -> Result<(T, reqwest::header::HeaderMap), DependabotTrackerError> {
+    let response = client
+        .post(GRAPHQL_PATH)
+        .headers(github_headers(token)?)
+        .json(&json!({
+            "query": query,
+            "variables": variables,
+        }))
+        .send()
+        .await
+        .map_err(DependabotTrackerError::from)
+        .and_then(check_response)?;
+    let headers = response.headers().clone();
+    let body: GraphqlResponse<T> = response.json().await?;
+
+    let messages: Vec<String> = body.errors.into_iter().map(|error| error.message).collect();
+    match body.data {
+        Some(data) => {
+            if !messages.is_empty() {
+                let partial_errors =
+                    format!("GraphQL query partly failed: {}", messages.join("; "));
+                trace_dbg!(level: tracing::Level::WARN, partial_errors);
+            }
+            Ok((data, headers))
+        }
+        None => Err(graphql_error(format!(
+            "GraphQL query failed: {}",
+            messages.join("; ")
+        ))),
+    }
+}
+
+/// Page through a repository listing, with `owner` picking the viewer's or an organization's
+/// repositories out of each response.
+async fn list_repositories<T: DeserializeOwned>(
+    token: &str,
+    client: &dyn GithubClient,
+    graphql_query: &str,
+    mut variables: Value,
+    label: &str,
+    progress: &(dyn Fn(FetchProgress) + Sync),
+    owner: impl Fn(T) -> Option<RepositoryOwnerNode>,
+) -> Result<(Vec<GraphqlRepository>, Option<DateTime<Utc>>), DependabotTrackerError> {
+    let graphql_query = format!("{}{}{}", graphql_query, REPOSITORY_FIELDS, ALERT_FIELDS);
+    let mut repositories = Vec::new();
+    let mut expiration = None;
+    let mut page = 1;
+
+    loop {
+        let (data, headers) = query::<T>(token, client, &graphql_query, variables.clone()).await?;
+        expiration = expiration.or_else(|| token_expiration(&headers));
+        let Some(owner) = owner(data) else {
+            return Err(graphql_error(format!("{} couldn't be listed", label)));
+        };
+        let connection = owner.repositories;
+        progress(FetchProgress::Page(PageProgress {
+            label: label.to_string(),
+            page,
+            last_page: Some(
+                connection
+                    .total_count
+                    .div_ceil(REPOSITORIES_PER_QUERY as usize),
+            ),
+        }));
+        repositories.extend(connection.nodes.into_iter().flatten());
+
+        if !connection.page_info.has_next_page {
+            break;
+        }
+        variables["after"] = json!(connection.page_info.end_cursor);
+        page += 1;
+    }
+
+    Ok((repositories, expiration))
+}
+
+/// The rest of a repository's alerts, after the first page that came with the listing.
+async fn fetch_remaining_alerts(
+    token: &str,
+    client: &dyn GithubClient,
+    repository: &GraphqlRepository,
+    mut end_cursor: Option<String>,
+) -> Result<Vec<GraphqlAlert>, DependabotTrackerError> {
+    let graphql_query = format!("{}{}", ALERTS_QUERY, ALERT_FIELDS);
+    let mut alerts = Vec::new();
+
+    loop {
+        let variables = json!({
+            "id": repository.id,
+            "after": end_cursor,
+            "alertsFirst": ALERTS_PER_QUERY,
+        });
+        let (data, _) = query::<AlertsData>(token, client, &graphql_query, variables).await?;
+        let Some(connection) = data.node.and_then(|node| node.vulnerability_alerts) else {
+            return Err(graphql_error(format!(
+                "Unable to page through {}'s alerts",
+                repository.name
+            )));
+        };
+        alerts.extend(connection.nodes);
+
+        if !connection.page_info.has_next_page {
+            break;
+        }
+        end_cursor = connection.page_info.end_cursor;
+    }
+
+    Ok(alerts)
+}
+
+/// List the user's and the configured organizations' repositories along with their Dependabot
+/// alerts through the GraphQL API, in a query per page of repositories rather than a request
+/// per repository. Repositories whose alerts couldn't be read this way are left out of the
+/// listing's alerts, so they're fetched through the REST API instead.
+pub async fn list_github_repos_graphql(
+    token: &str,
+    client: &dyn GithubClient,
+    config: &FetchConfig,
+    progress: &(dyn Fn(FetchProgress) + Sync),
+    warnings: &mut Vec<String>,
+) -> Result<RepositoryListing, DependabotTrackerError> {
+    let affiliations: Vec<String> = config
+        .affiliation
+        .split(',')
+        .map(|affiliation| affiliation.trim().to_uppercase())
+        .collect();
+    let (mut repositories, token_expiration) = list_repositories(
+        token,
+        client,
+        VIEWER_QUERY,
+        json!({
+            "first": REPOSITORIES_PER_QUERY,
+            "affiliations": affiliations,
+            "alertsFirst": ALERTS_PER_QUERY,
+        }),
+        "Repositories",
+        progress,
+        |data: ViewerData| Some(data.viewer),
+    )
+    .await?;
+
+    for organization in config.organizations.iter() {
+        let label = format!("{} repositories", organization);
+        let organization_repositories = list_repositories(
+            token,
+            client,
+            ORGANIZATION_QUERY,
+            json!({
+                "login": organization,
+                "first": REPOSITORIES_PER_QUERY,
+                "alertsFirst": ALERTS_PER_QUERY,
+            }),
+            &label,
+            progress,
+            |data: OrganizationData| data.organization,
+        )
+        .await;
+        match organization_repositories {
+            Ok((organization_repositories, _)) => {
+                for repository in organization_repositories {
+                    if !repositories
+                        .iter()
+                        .any(|existing| existing.database_id == repository.database_id)
+                    {
+                        repositories.push(repository);
+                    }
+                }
+            }
+            Err(e) => {
+                let organization_error =
+                    format!("Unable to fetch repositories for {}: {}", organization, e);
+                trace_dbg!(level: tracing::Level::WARN, &organization_error);
+                warnings.push(organization_error);
+            }
+        }
+    }
+
+    // the few repositories with more than a page of alerts have the rest fetched separately
+    let requests: Vec<_> = repositories
+        .iter_mut()
+        .map(|repository| async move {
+            let Some(connection) = repository.vulnerability_alerts.as_mut() else {
+                return;
+            };
+            if !connection.page_info.has_next_page {
+                return;
+            }
+            let end_cursor = connection.page_info.end_cursor.take();
+            let remaining = fetch_remaining_alerts(token, client, repository, end_cursor).await;
+            match remaining {
+                Ok(remaining) => {
+                    if let Some(connection) = repository.vulnerability_alerts.as_mut() {
+                        connection.nodes.extend(remaining);
+                    }
+                }
+                Err(e) => {
+                    trace_dbg!(level: tracing::Level::WARN, e.to_string());
+                    repository.vulnerability_alerts = None;
+                }
+            }
+        })
+        .collect();
+    stream::iter(requests)
+        .buffer_unordered(MAX_CONCURRENT_REQUESTS)
+        .collect::<Vec<()>>()
+        .await;
+
+    let mut alerts = HashMap::new();
+    let repositories = repositories
+        .into_iter()
+        .map(|repository| {
+            let github_repository = repository.to_github_repository();
+            if let Some(connection) = repository.vulnerability_alerts {
+                let repository_alerts = connection
+                    .nodes
+                    .into_iter()
+                    .filter_map(|alert| alert.into_github_dependabot(&github_repository))
+                    .collect();
+                alerts.insert(github_repository.id, repository_alerts);
+            }
+            github_repository
+        })
+        .collect();
+
+    Ok(RepositoryListing {
+        repositories,
+        alerts,
+        token_expiration,
+    })
+}
@@ -0,0 +1,243 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use crate::dependabot::{Dependabot, DependabotSeverity, DependabotState, DependencyScope};
+use crate::repository::{AlertsStatus, Repository};
+
+/// The version of the documents `--output json` prints. Bump it whenever a field is renamed,
+/// removed, or changes meaning; adding a field doesn't need a bump.
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// The repositories, their alerts, and the totals across them, as printed by `--output json`.
+/// This is kept separate from `Repository` so the saved data can change without breaking
+/// scripts that read it.
+#[derive(Serialize)]
+pub struct JsonDocument<'a> {
+    schema_version: u32,
+    generated_at: DateTime<Utc>,
+    totals: JsonTotals,
+    repositories: Vec<JsonRepository<'a>>,
+    // only for `check`, the repositories over its thresholds
+    #[serde(skip_serializing_if = "Option::is_none")]
+    violations: Option<Vec<JsonViolation<'a>>>,
+    // only for `check`, the repositories whose alerts couldn't be read
+    #[serde(skip_serializing_if = "Option::is_none")]
+    unchecked: Option<Vec<JsonUnchecked<'a>>>,
+}
+
+#[derive(Serialize)]
+struct JsonTotals {
+    // the repositories the user owns, leaving out watched ones
+    repositories: usize,
+    open_alerts: JsonAlertCounts,
+}
+
+#[derive(Serialize, Default)]
+struct JsonAlertCounts {
+    critical: usize,
+    high: usize,
+    medium: usize,
+    low: usize,
+    total: usize,
+}
+
+impl JsonAlertCounts {
+    fn add(&mut self, counts: &JsonAlertCounts) {
+        self.critical += counts.critical;
+        self.high += counts.high;
+        self.medium += counts.medium;
+        self.low += counts.low;
+        self.total += counts.total;
+    }
+}
+
+impl From<&Repository> for JsonAlertCounts {
+    fn from(repo: &Repository) -> Self {
+        JsonAlertCounts {
+            critical: repo.critical_alerts,
+            high: repo.high_alerts,
+            medium: repo.medium_alerts,
+            low: repo.low_alerts,
+            total: repo.total_active_alerts,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct JsonRepository<'a> {
+    name: &'a str,
+    url: &'a str,
+    private: bool,
+    archived: bool,
+    watched: bool,
+    alerts_status: AlertsStatus,
+    last_fetched_at: Option<DateTime<Utc>>,
+    open_alerts: JsonAlertCounts,
+    alerts: Vec<JsonAlert<'a>>,
+}
+
+impl<'a> From<&'a Repository> for JsonRepository<'a> {
+    fn from(repo: &'a Repository) -> Self {
+        JsonRepository {
+            name: &repo.full_name,
+            url: &repo.url,
+            private: repo.private,
+            archived: repo.archived,
+            watched: repo.watched,
+            alerts_status: repo.alerts_status,
+            last_fetched_at: repo.last_fetched_at,
+            open_alerts: JsonAlertCounts::from(repo),
+            alerts: repo.dependabots.iter().map(JsonAlert::from).collect(),
+        }
+    }
+}
+
+/// An alert as printed by `--output json`, and by `watch` along with its repository.
+#[derive(Serialize)]
+struct JsonAlert<'a> {
+    number: u32,
+    state: &'a DependabotState,
+    severity: DependabotSeverity,
+    package: JsonPackage<'a>,
+    manifest_path: Option<&'a str>,
+    scope: Option<DependencyScope>,
+    vulnerable_version_range: Option<&'a str>,
+    first_patched_version: Option<&'a str>,
+    ghsa_id: Option<&'a str>,
+    cve_id: Option<&'a str>,
+    summary: Option<&'a str>,
+    url: &'a str,
+    created_at: &'a str,
+    fixed_at: Option<&'a str>,
+    dismissed_at: Option<&'a str>,
+    dismissed_reason: Option<&'a str>,
+}
+
+#[derive(Serialize)]
+struct JsonPackage<'a> {
+    ecosystem: &'a str,
+    name: &'a str,
+}
+
+impl<'a> From<&'a Dependabot> for JsonAlert<'a> {
+    fn from(alert: &'a Dependabot) -> Self {
+        JsonAlert {
+            number: alert.number,
+            state: &alert.state,
+            severity: alert.severity,
+            package: JsonPackage {
+                ecosystem: &alert.dependency_ecosystem,
+                name: &alert.dependency_name,
+            },
+            manifest_path: alert.manifest_path.as_deref(),
+            scope: alert.scope,
+            vulnerable_version_range: alert.vulnerable_version_range.as_deref(),
+            first_patched_version: alert.first_patched_version.as_deref(),
+            ghsa_id: alert.ghsa_id.as_deref(),
+            cve_id: alert.cve_id.as_deref(),
+            summary: alert.summary.as_deref(),
+            url: &alert.html_url,
+            created_at: &alert.created_at,
+            fixed_at: alert.fixed_at.as_deref(),
+            dismissed_at: alert.dismissed_at.as_deref(),
+            dismissed_reason: alert.dismissed_reason.as_deref(),
+        }
+    }
+}
+
+/// A repository with more open alerts than `check` allows.
+#[derive(Serialize)]
+struct JsonViolation<'a> {
+    repository: &'a str,
+    critical: usize,
+    high: usize,
+}
+
+/// A repository `check` couldn't compare with its thresholds, and why.
+#[derive(Serialize)]
+struct JsonUnchecked<'a> {
+    repository: &'a str,
+    reason: &'a str,
+}
+
+/// The alerts a `watch` fetch turned up that weren't open before it.
+#[derive(Serialize)]
+pub struct JsonWatchChange<'a> {
+    schema_version: u32,
+    fetched_at: DateTime<Utc>,
+    new_alerts: Vec<JsonRepositoryAlert<'a>>,
+}
+
+#[derive(Serialize)]
+struct JsonRepositoryAlert<'a> {
+    repository: &'a str,
+    #[serde(flatten)]
+    alert: JsonAlert<'a>,
+}
+
+impl<'a> JsonDocument<'a> {
+    pub fn new(repositories: &'a [Repository], now: DateTime<Utc>) -> JsonDocument<'a> {
+        let mut open_alerts = JsonAlertCounts::default();
+        let owned: Vec<&Repository> = repositories.iter().filter(|repo| !repo.watched).collect();
+        for repo in owned.iter() {
+            open_alerts.add(&JsonAlertCounts::from(*repo));
+        }
+
+        JsonDocument {
+            schema_version: SCHEMA_VERSION,
+            generated_at: now,
+            totals: JsonTotals {
+                repositories: owned.len(),
+                open_alerts,
+            },
+            repositories: repositories.iter().map(JsonRepository::from).collect(),
+            violations: None,
+            unchecked: None,
+        }
+    }
+
+    /// Add the repositories `check` found over its thresholds.
+    pub fn with_violations(mut self, violations: &[&'a Repository]) -> JsonDocument<'a> {
+        self.violations = Some(
+            violations
+                .iter()
+                .map(|repo| JsonViolation {
+                    repository: &repo.full_name,
+                    critical: repo.critical_alerts,
+                    high: repo.high_alerts,
+                })
+                .collect(),
+        );
+        self
+    }
+
+    /// Add the repositories `check` couldn't read the alerts of, each with the reason.
+    pub fn with_unchecked(mut self, unchecked: &'a [(String, String)]) -> JsonDocument<'a> {
+        self.unchecked = Some(
+            unchecked
+                .iter()
+                .map(|(repository, reason)| JsonUnchecked { repository, reason })
+                .collect(),
+        );
+        self
+    }
+}
+
+impl<'a> JsonWatchChange<'a> {
+    pub fn new(
+        new_alerts: Vec<(&'a Repository, &'a Dependabot)>,
+        now: DateTime<Utc>,
+    ) -> JsonWatchChange<'a> {
+        JsonWatchChange {
+            schema_version: SCHEMA_VERSION,
+            fetched_at: now,
+            new_alerts: new_alerts
+                .into_iter()
+                .map(|(repo, alert)| JsonRepositoryAlert {
+                    repository: &repo.full_name,
+                    alert: JsonAlert::from(alert),
+                })
+                .collect(),
+        }
+    }
+}
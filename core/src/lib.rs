@@ -0,0 +1,91 @@
+//! The GitHub and GitLab alert clients, models, snapshots, and aggregation behind the
+//! `dependabot-tracker` TUI, for other tools (bots, dashboards) to reuse without the terminal
+//! UI.
+//!
+//! - [`provider`] fetches the repositories and their Dependabot, code scanning, and secret
+//!   scanning alerts from GitHub or GitLab, configured by a [`fetch_config::FetchConfig`].
+//! - [`repository`] and [`dependabot`] hold the fetched data, and [`repository_list`] sorts,
+//!   filters, and totals it, including which alerts opened or were resolved between two
+//!   fetches.
+//! - [`storage`] saves each fetch as a snapshot in the data directory and loads them back.
+//! - [`risk`], [`sla`], [`report`], and [`json_output`] summarize the alerts.
+//!
+//! Fetching everything and listing the alerts that opened since the last snapshot:
+//!
+//! ```no_run
+//! use dependabot_tracker_core::fetch_config::FetchConfig;
+//! use dependabot_tracker_core::github_client::GITHUB_API_URL;
+//! use dependabot_tracker_core::provider::{Provider, SecurityProvider};
+//! use dependabot_tracker_core::repository::GitHubProvider;
+//! use dependabot_tracker_core::storage::load_latest_snapshot;
+//! use tokio_util::sync::CancellationToken;
+//!
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! let previous = load_latest_snapshot()?.repositories;
+//! let provider = Provider::GitHub(GitHubProvider {
+//!     token: std::env::var("PAT")?,
+//!     api_url: GITHUB_API_URL.to_string(),
+//! });
+//! let outcome = provider
+//!     .fetch_repos(&previous, &FetchConfig::default(), &|_| {}, &CancellationToken::new())
+//!     .await?;
+//! for (repo, alert) in outcome.repositories.new_open_alerts(&previous) {
+//!     println!("{}: {} ({})", repo.full_name, alert.dependency_name, alert.severity);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+pub mod dependabot;
+pub mod error;
+pub mod event_log;
+pub mod fetch_config;
+pub mod github_client;
+pub mod gitlab;
+pub mod graphql;
+pub mod json_output;
+pub mod migrations;
+pub mod osv;
+pub mod provider;
+pub mod pull_request;
+pub mod report;
+pub mod repository;
+pub mod repository_list;
+pub mod repository_tree;
+pub mod retry;
+pub mod risk;
+pub mod scanning;
+pub mod search;
+pub mod security_alert;
+pub mod sla;
+pub mod storage;
+pub mod timezone;
+
+#[doc(hidden)]
+pub use tracing;
+
+/// Similar to the `std::dbg!` macro, but generates `tracing` events rather
+/// than printing to stdout.
+///
+/// By default, the verbosity level for the generated events is `DEBUG`, but
+/// this can be customized.
+#[macro_export]
+macro_rules! trace_dbg {
+    (target: $target:expr, level: $level:expr, $ex:expr) => {{
+        match $ex {
+            value => {
+                $crate::tracing::event!(target: $target, $level, ?value, stringify!($ex));
+                value
+            }
+        }
+    }};
+    (level: $level:expr, $ex:expr) => {
+        $crate::trace_dbg!(target: module_path!(), level: $level, $ex)
+    };
+    (target: $target:expr, $ex:expr) => {
+        $crate::trace_dbg!(target: $target, level: $crate::tracing::Level::DEBUG, $ex)
+    };
+    ($ex:expr) => {
+        $crate::trace_dbg!(level: $crate::tracing::Level::DEBUG, $ex)
+    };
+}
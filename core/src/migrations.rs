@@ -0,0 +1,227 @@
+use serde_json::{json, Value};
+
+use crate::error::DependabotTrackerError;
+use crate::storage::SavedData;
+
+/// The saved data format this version of the app writes. Bump it, and add a migration from the
+/// previous version to `MIGRATIONS`, whenever a change to the saved models would stop data
+/// saved by an earlier version from deserializing.
+pub const CURRENT_VERSION: u32 = 4;
+
+// the migration from each version to the next, indexed by the version it upgrades from
+const MIGRATIONS: [fn(Value) -> Value; CURRENT_VERSION as usize] = [
+    wrap_in_envelope,
+    forget_alert_etags,
+    forget_alert_etags,
+    flags_to_alerts_status,
+];
+
+/// Upgrade saved data from any earlier version to the current format and deserialize it. Data
+/// saved by a newer version of the app is refused rather than read with parts of it missing.
+pub fn migrate(mut data: Value) -> Result<SavedData, DependabotTrackerError> {
+    let mut version = data_version(&data)?;
+    if version > CURRENT_VERSION {
+        return Err(DependabotTrackerError::Deserialize(format!(
+            "The saved data is version {}, but this version of the app only reads up to version {}",
+            version, CURRENT_VERSION
+        )));
+    }
+
+    while version < CURRENT_VERSION {
+        data = MIGRATIONS[version as usize](data);
+        version += 1;
+        data["version"] = json!(version);
+    }
+
+    Ok(serde_json::from_value(data)?)
+}
+
+fn data_version(data: &Value) -> Result<u32, DependabotTrackerError> {
+    match data {
+        // before it was versioned, the saved data was just the list of repositories
+        Value::Array(_) => Ok(0),
+        Value::Object(fields) => fields
+            .get("version")
+            .and_then(Value::as_u64)
+            .and_then(|version| u32::try_from(version).ok())
+            .ok_or_else(|| {
+                DependabotTrackerError::Deserialize("The saved data has no version".to_string())
+            }),
+        _ => Err(DependabotTrackerError::Deserialize(
+            "The saved data isn't a list of repositories or a versioned envelope".to_string(),
+        )),
+    }
+}
+
+// version 0 didn't record when the repositories were fetched
+fn wrap_in_envelope(repositories: Value) -> Value {
+    json!({
+        "fetched_at": null,
+        "repositories": repositories,
+    })
+}
+
+// for versions that saved new alert fields: version 1 didn't keep who dismissed an alert and
+// why, and version 2 didn't keep the dependency's scope. without the ETags, the next fetch gets
+// every repository's alerts in full instead of being told they haven't changed
+fn forget_alert_etags(mut data: Value) -> Value {
+    if let Some(repositories) = data["repositories"].as_array_mut() {
+        for repository in repositories {
+            repository["alerts_etag"] = Value::Null;
+        }
+    }
+
+    data
+}
+
+// version 3 kept separate flags for repositories the token couldn't see and ones with
+// Dependabot alerts turned off, where version 4 has a single alerts status
+fn flags_to_alerts_status(mut data: Value) -> Value {
+    if let Some(repositories) = data["repositories"].as_array_mut() {
+        for repository in repositories {
+            let Some(fields) = repository.as_object_mut() else {
+                continue;
+            };
+            let inaccessible = fields
+                .remove("inaccessible")
+                .and_then(|flag| flag.as_bool());
+            let alerts_disabled = fields
+                .remove("alerts_disabled")
+                .and_then(|flag| flag.as_bool());
+            let alerts_status = if inaccessible == Some(true) {
+                "no_access"
+            } else if alerts_disabled == Some(true) {
+                "disabled"
+            } else {
+                "enabled"
+            };
+            fields.insert("alerts_status".to_string(), json!(alerts_status));
+        }
+    }
+
+    data
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dependabot::DependencyScope;
+    use crate::repository::AlertsStatus;
+
+    // one fixture per historical version, each holding the same repositories
+    const FIXTURES: [(u32, &str); 5] = [
+        (0, include_str!("../tests/fixtures/saved_data/v0.json")),
+        (1, include_str!("../tests/fixtures/saved_data/v1.json")),
+        (2, include_str!("../tests/fixtures/saved_data/v2.json")),
+        (3, include_str!("../tests/fixtures/saved_data/v3.json")),
+        (4, include_str!("../tests/fixtures/saved_data/v4.json")),
+    ];
+
+    #[test]
+    fn there_is_a_fixture_for_every_version() {
+        let versions: Vec<u32> = FIXTURES.iter().map(|(version, _)| *version).collect();
+        let expected: Vec<u32> = (0..=CURRENT_VERSION).collect();
+
+        assert_eq!(versions, expected);
+    }
+
+    #[test]
+    fn every_version_migrates_to_the_current_one() {
+        for (version, fixture) in FIXTURES {
+            let data: Value = serde_json::from_str(fixture).unwrap();
+            assert_eq!(data_version(&data).unwrap(), version);
+
+            let saved_data = migrate(data)
+                .unwrap_or_else(|e| panic!("version {} failed to migrate: {}", version, e));
+            assert_eq!(saved_data.version, CURRENT_VERSION);
+            assert_eq!(saved_data.repositories.len(), 2);
+            assert_eq!(saved_data.repositories[0].full_name, "user/example-repo1");
+            assert_eq!(saved_data.repositories[0].dependabots.len(), 2);
+            assert_eq!(saved_data.repositories[1].full_name, "user/example-repo2");
+        }
+    }
+
+    #[test]
+    fn unversioned_data_has_no_fetch_time() {
+        let data: Value = serde_json::from_str(FIXTURES[0].1).unwrap();
+
+        assert!(migrate(data).unwrap().fetched_at.is_none());
+    }
+
+    #[test]
+    fn versioned_data_keeps_its_fetch_time() {
+        let data: Value = serde_json::from_str(FIXTURES[1].1).unwrap();
+        let fetched_at = migrate(data).unwrap().fetched_at.unwrap();
+
+        assert_eq!(fetched_at.to_rfc3339(), "2024-05-01T12:00:00+00:00");
+    }
+
+    #[test]
+    fn alert_etags_from_before_new_alert_fields_are_forgotten() {
+        for (_, fixture) in &FIXTURES[1..3] {
+            let data: Value = serde_json::from_str(fixture).unwrap();
+            assert!(data["repositories"][0]["alerts_etag"].is_string());
+
+            let saved_data = migrate(data).unwrap();
+            assert!(saved_data.repositories[0].alerts_etag.is_none());
+        }
+    }
+
+    #[test]
+    fn current_alert_fields_are_kept() {
+        let data: Value = serde_json::from_str(FIXTURES[4].1).unwrap();
+        let saved_data = migrate(data).unwrap();
+        let repository = &saved_data.repositories[0];
+        let dismissed = &repository.dependabots[1];
+
+        assert_eq!(dismissed.dismissed_by.as_deref(), Some("octocat"));
+        assert_eq!(
+            dismissed.dismissed_reason.as_deref(),
+            Some("tolerable_risk")
+        );
+        assert_eq!(dismissed.scope, Some(DependencyScope::Development));
+        assert_eq!(repository.alerts_etag.as_deref(), Some("\"v3\""));
+        assert_eq!(
+            saved_data.repositories[1].alerts_status,
+            AlertsStatus::NoAccess
+        );
+    }
+
+    #[test]
+    fn access_flags_become_an_alerts_status() {
+        let mut data: Value = serde_json::from_str(FIXTURES[3].1).unwrap();
+        data["repositories"][0]["alerts_disabled"] = json!(true);
+        data["repositories"][1]["inaccessible"] = json!(true);
+        let saved_data = migrate(data).unwrap();
+
+        assert_eq!(
+            saved_data.repositories[0].alerts_status,
+            AlertsStatus::Disabled
+        );
+        assert_eq!(
+            saved_data.repositories[1].alerts_status,
+            AlertsStatus::NoAccess
+        );
+    }
+
+    #[test]
+    fn data_from_a_newer_version_is_refused() {
+        let data = json!({
+            "version": CURRENT_VERSION + 1,
+            "fetched_at": null,
+            "repositories": [],
+        });
+
+        assert!(matches!(
+            migrate(data),
+            Err(DependabotTrackerError::Deserialize(_))
+        ));
+    }
+
+    #[test]
+    fn data_without_a_version_is_refused() {
+        let data = json!({ "repositories": [] });
+
+        assert!(migrate(data).is_err());
+    }
+}
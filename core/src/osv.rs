@@ -0,0 +1,193 @@
+use std::fs;
+use std::path::PathBuf;
+
+use reqwest::{Client, StatusCode};
+use serde::Deserialize;
+use tokio::task::JoinHandle;
+
+use crate::dependabot::{Dependabot, DependabotState};
+use crate::error::DependabotTrackerError;
+use crate::storage::data_directory;
+use crate::trace_dbg;
+
+const OSV_VULNS_URL: &str = "https://api.osv.dev/v1/vulns";
+
+/// An advisory's entry in the OSV.dev database, for the affected ranges, references, and aliases
+/// GitHub's alert doesn't carry.
+#[derive(Debug, Clone, Deserialize)]
+pub struct OsvAdvisory {
+    pub id: String,
+    #[serde(default)]
+    pub aliases: Vec<String>,
+    #[serde(default)]
+    pub affected: Vec<OsvAffected>,
+    #[serde(default)]
+    pub references: Vec<OsvReference>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct OsvAffected {
+    pub package: Option<OsvPackage>,
+    #[serde(default)]
+    pub ranges: Vec<OsvRange>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct OsvPackage {
+    pub ecosystem: String,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct OsvRange {
+    #[serde(default)]
+    pub events: Vec<OsvEvent>,
+}
+
+/// A version where a range of affected versions starts or ends. Each event has one of these set.
+#[derive(Debug, Clone, Deserialize)]
+pub struct OsvEvent {
+    pub introduced: Option<String>,
+    pub fixed: Option<String>,
+    pub last_affected: Option<String>,
+    pub limit: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct OsvReference {
+    // e.g. `ADVISORY`, `FIX`, `PACKAGE`, or `WEB`
+    #[serde(rename = "type")]
+    pub reference_type: String,
+    pub url: String,
+}
+
+impl OsvRange {
+    /// The affected versions written like `>= 1.0.0, < 1.2.3`, one entry per introduced version.
+    pub fn describe(&self) -> Vec<String> {
+        let mut intervals = Vec::new();
+        let mut introduced: Option<&str> = None;
+        for event in self.events.iter() {
+            if let Some(version) = &event.introduced {
+                if let Some(start) = introduced.replace(version) {
+                    intervals.push(interval(start, None));
+                }
+                continue;
+            }
+            let end = if let Some(version) = &event.fixed {
+                format!("< {}", version)
+            } else if let Some(version) = &event.last_affected {
+                format!("<= {}", version)
+            } else if let Some(version) = &event.limit {
+                format!("< {}", version)
+            } else {
+                continue;
+            };
+            intervals.push(interval(introduced.take().unwrap_or("0"), Some(end)));
+        }
+        if let Some(start) = introduced {
+            intervals.push(interval(start, None));
+        }
+
+        intervals
+    }
+}
+
+/// An affected interval, leaving out the start when it's every version up to the end.
+fn interval(introduced: &str, end: Option<String>) -> String {
+    match (introduced, end) {
+        ("0", Some(end)) => end,
+        ("0", None) => "every version".to_string(),
+        (introduced, Some(end)) => format!(">= {}, {}", introduced, end),
+        (introduced, None) => format!(">= {}", introduced),
+    }
+}
+
+/// The alert detail screen's OSV.dev entry, as far as it's been looked up.
+pub enum OsvLookup {
+    // the alert has neither a GHSA nor a CVE ID to look up
+    Unavailable,
+    Pending(JoinHandle<Option<OsvAdvisory>>),
+    // the entry, or `None` if OSV.dev doesn't have one or it couldn't be fetched
+    Done(Option<OsvAdvisory>),
+}
+
+/// The ID an alert's advisory is looked up on OSV.dev by: its GHSA ID, or failing that its CVE ID.
+pub fn advisory_id(dependabot: &Dependabot) -> Option<&str> {
+    dependabot
+        .ghsa_id
+        .as_deref()
+        .or(dependabot.cve_id.as_deref())
+}
+
+/// Where an advisory's OSV.dev response is cached, or `None` for an ID that isn't safe to use in
+/// a file name.
+fn cache_path(id: &str) -> Option<PathBuf> {
+    let is_safe = !id.is_empty()
+        && id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    is_safe.then(|| data_directory().join("osv").join(format!("{}.json", id)))
+}
+
+/// The advisory's OSV.dev entry if it was fetched before.
+pub fn cached_advisory(id: &str) -> Option<OsvAdvisory> {
+    let contents = fs::read_to_string(cache_path(id)?).ok()?;
+    serde_json::from_str(&contents).ok()
+}
+
+/// The advisory's OSV.dev entry, from the cache or else fetched and cached. `None` if OSV.dev
+/// has no entry for it.
+pub async fn fetch_advisory(
+    id: &str,
+    client: &Client,
+) -> Result<Option<OsvAdvisory>, DependabotTrackerError> {
+    if let Some(advisory) = cached_advisory(id) {
+        return Ok(Some(advisory));
+    }
+    let Some(path) = cache_path(id) else {
+        return Ok(None);
+    };
+
+    let response = client
+        .get(format!("{}/{}", OSV_VULNS_URL, id))
+        .send()
+        .await?;
+    if response.status() == StatusCode::NOT_FOUND {
+        return Ok(None);
+    }
+    let body = response.error_for_status()?.text().await?;
+    let advisory: OsvAdvisory = serde_json::from_str(&body)?;
+
+    // the response is kept as OSV.dev sent it, so fields read later don't need a new fetch
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(&path, body)?;
+
+    Ok(Some(advisory))
+}
+
+/// Fetch and cache the OSV.dev entries for the advisories that aren't cached yet, so the alert
+/// detail screen has them without waiting. A failed lookup is logged and tried again next time.
+pub async fn enrich_advisories(ids: Vec<String>, client: Client) {
+    for id in ids {
+        if let Err(e) = fetch_advisory(&id, &client).await {
+            let osv_error = format!("Failed to look up {} on OSV.dev: {}", id, e);
+            trace_dbg!(level: tracing::Level::WARN, osv_error);
+        }
+    }
+}
+
+/// The distinct advisory IDs of the open alerts that aren't cached yet.
+pub fn uncached_advisory_ids<'a>(dependabots: impl Iterator<Item = &'a Dependabot>) -> Vec<String> {
+    let mut ids: Vec<String> = dependabots
+        .filter(|dependabot| dependabot.state == DependabotState::Open)
+        .filter_map(advisory_id)
+        .filter(|id| cache_path(id).is_some_and(|path| !path.exists()))
+        .map(str::to_string)
+        .collect();
+    ids.sort();
+    ids.dedup();
+
+    ids
+}
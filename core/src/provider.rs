@@ -0,0 +1,90 @@
+use std::fmt::{self, Display, Formatter};
+use std::future::Future;
+
+use serde::{Deserialize, Serialize};
+use tokio_util::sync::CancellationToken;
+
+use crate::error::DependabotTrackerError;
+use crate::fetch_config::FetchConfig;
+use crate::gitlab::GitLabProvider;
+use crate::repository::{FetchOutcome, FetchProgress, GitHubProvider, Repository};
+
+/// Which service a profile's repositories are hosted on.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProviderKind {
+    #[default]
+    GitHub,
+    GitLab,
+}
+
+impl Display for ProviderKind {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+/// A service that hosts repositories and reports the vulnerable dependencies in them.
+pub trait SecurityProvider {
+    /// Fetch every repository the account can see along with its alerts, saving them as a new
+    /// snapshot. Once `cancellation` is cancelled, the repositories that haven't been fetched yet
+    /// keep their data from `previous_repos`, and the ones that have are still saved.
+    fn fetch_repos(
+        &self,
+        previous_repos: &[Repository],
+        config: &FetchConfig,
+        progress: &(dyn Fn(FetchProgress) + Sync),
+        cancellation: &CancellationToken,
+    ) -> impl Future<Output = Result<FetchOutcome, DependabotTrackerError>> + Send;
+
+    /// Fetch a single repository's alerts again. The repository comes back with its alerts
+    /// status set to `NoAccess` if the account can no longer see it.
+    fn fetch_alerts(
+        &self,
+        repository: &Repository,
+        config: &FetchConfig,
+        progress: &(dyn Fn(FetchProgress) + Sync),
+    ) -> impl Future<Output = Result<Repository, DependabotTrackerError>> + Send;
+}
+
+/// The provider a profile fetches from.
+#[derive(Debug, Clone)]
+pub enum Provider {
+    GitHub(GitHubProvider),
+    GitLab(GitLabProvider),
+}
+
+impl SecurityProvider for Provider {
+    async fn fetch_repos(
+        &self,
+        previous_repos: &[Repository],
+        config: &FetchConfig,
+        progress: &(dyn Fn(FetchProgress) + Sync),
+        cancellation: &CancellationToken,
+    ) -> Result<FetchOutcome, DependabotTrackerError> {
+        match self {
+            Provider::GitHub(github) => {
+                github
+                    .fetch_repos(previous_repos, config, progress, cancellation)
+                    .await
+            }
+            Provider::GitLab(gitlab) => {
+                gitlab
+                    .fetch_repos(previous_repos, config, progress, cancellation)
+                    .await
+            }
+        }
+    }
+
+    async fn fetch_alerts(
+        &self,
+        repository: &Repository,
+        config: &FetchConfig,
+        progress: &(dyn Fn(FetchProgress) + Sync),
+    ) -> Result<Repository, DependabotTrackerError> {
+        match self {
+            Provider::GitHub(github) => github.fetch_alerts(repository, config, progress).await,
+            Provider::GitLab(gitlab) => gitlab.fetch_alerts(repository, config, progress).await,
+        }
+    }
+}
@@ -0,0 +1,171 @@
+use std::fmt::{self, Display, Formatter};
+
+use serde::{Deserialize, Serialize};
+
+use crate::dependabot::{Dependabot, DependabotState};
+use crate::github_client::GithubClient;
+use crate::repository::github_headers;
+use crate::trace_dbg;
+
+const DEPENDABOT_LOGIN: &str = "dependabot[bot]";
+
+/// The combined result of a pull request's check runs.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CiStatus {
+    Passing,
+    Failing,
+    Pending,
+    // the head commit has no check runs, or they couldn't be read
+    Unknown,
+}
+
+impl Display for CiStatus {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            CiStatus::Passing => write!(f, "CI passing"),
+            CiStatus::Failing => write!(f, "CI failing"),
+            CiStatus::Pending => write!(f, "CI pending"),
+            CiStatus::Unknown => write!(f, "no CI status"),
+        }
+    }
+}
+
+/// An open pull request opened by Dependabot.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DependabotPullRequest {
+    pub number: u32,
+    pub title: String,
+    pub html_url: String,
+    // the package being bumped, parsed from a title like `Bump foo from 1.0.0 to 1.0.1`, or
+    // `None` for grouped updates
+    pub dependency_name: Option<String>,
+    pub ci_status: CiStatus,
+}
+
+impl DependabotPullRequest {
+    /// Whether merging the pull request would update the package an open alert is on.
+    pub fn fixes(&self, dependabot: &Dependabot) -> bool {
+        dependabot.state == DependabotState::Open
+            && self.dependency_name.as_deref() == Some(dependabot.dependency_name.as_str())
+    }
+}
+
+#[derive(Deserialize)]
+struct GithubPullRequest {
+    number: u32,
+    title: String,
+    html_url: String,
+    user: PullRequestUser,
+    head: PullRequestHead,
+}
+
+#[derive(Deserialize)]
+struct PullRequestUser {
+    login: String,
+}
+
+#[derive(Deserialize)]
+struct PullRequestHead {
+    sha: String,
+}
+
+#[derive(Deserialize)]
+struct CheckRuns {
+    check_runs: Vec<CheckRun>,
+}
+
+#[derive(Deserialize)]
+struct CheckRun {
+    status: String,
+    conclusion: Option<String>,
+}
+
+/// The package a Dependabot pull request bumps, from titles like `Bump foo from 1.0.0 to 1.0.1`
+/// or `build(deps): bump foo from 1.0.0 to 1.0.1 in /app`.
+fn parse_dependency_name(title: &str) -> Option<String> {
+    let lowercase = title.to_lowercase();
+    let start = lowercase.find("bump ")? + "bump ".len();
+    let end = start + lowercase[start..].find(" from ")?;
+    let name = title.get(start..end)?;
+    // grouped updates are titled `Bump the npm group ...`
+    if name.starts_with("the ") {
+        return None;
+    }
+
+    Some(name.to_string())
+}
+
+/// Fetch the open pull requests Dependabot has opened on a repository, along with their CI
+/// status. Failures are logged and treated as there being none.
+pub async fn fetch_dependabot_pull_requests(
+    token: &str,
+    full_name: &str,
+    client: &dyn GithubClient,
+) -> Vec<DependabotPullRequest> {
+    let Ok(headers) = github_headers(token) else {
+        return Vec::new();
+    };
+    let url = format!("/repos/{}/pulls?state=open&per_page=100", full_name);
+    let pull_requests = match client.get(&url).headers(headers.clone()).send().await {
+        Ok(response) if response.status().is_success() => {
+            response.json::<Vec<GithubPullRequest>>().await.ok()
+        }
+        _ => None,
+    };
+    let Some(pull_requests) = pull_requests else {
+        let pull_requests_unavailable = format!("Pull requests unavailable for {}", full_name);
+        trace_dbg!(level: tracing::Level::WARN, pull_requests_unavailable);
+        return Vec::new();
+    };
+
+    let mut dependabot_pull_requests = Vec::new();
+    for pull_request in pull_requests
+        .into_iter()
+        .filter(|pull_request| pull_request.user.login == DEPENDABOT_LOGIN)
+    {
+        let url = format!(
+            "/repos/{}/commits/{}/check-runs",
+            full_name, pull_request.head.sha
+        );
+        let check_runs = match client.get(&url).headers(headers.clone()).send().await {
+            Ok(response) if response.status().is_success() => {
+                response.json::<CheckRuns>().await.ok()
+            }
+            _ => None,
+        };
+
+        dependabot_pull_requests.push(DependabotPullRequest {
+            number: pull_request.number,
+            dependency_name: parse_dependency_name(&pull_request.title),
+            title: pull_request.title,
+            html_url: pull_request.html_url,
+            ci_status: check_runs.map_or(CiStatus::Unknown, ci_status),
+        });
+    }
+
+    dependabot_pull_requests
+}
+
+fn ci_status(check_runs: CheckRuns) -> CiStatus {
+    if check_runs.check_runs.is_empty() {
+        return CiStatus::Unknown;
+    }
+    if check_runs.check_runs.iter().any(|check_run| {
+        matches!(
+            check_run.conclusion.as_deref(),
+            Some("failure" | "timed_out" | "cancelled" | "action_required")
+        )
+    }) {
+        return CiStatus::Failing;
+    }
+    if check_runs
+        .check_runs
+        .iter()
+        .any(|check_run| check_run.status != "completed")
+    {
+        return CiStatus::Pending;
+    }
+
+    CiStatus::Passing
+}
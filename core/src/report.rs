@@ -0,0 +1,206 @@
+use chrono::{DateTime, Utc};
+
+use crate::dependabot::{format_age, Dependabot, DependabotState};
+use crate::repository::Repository;
+use crate::repository_list::RepositoryList;
+
+// how many rows the top dependencies and oldest alerts sections list
+const REPORT_SECTION_LENGTH: usize = 10;
+
+/// A section of the security report: a heading over a table.
+struct ReportTable {
+    heading: &'static str,
+    headers: Vec<&'static str>,
+    rows: Vec<Vec<String>>,
+}
+
+/// A summary of the repositories' open alerts for a weekly security review: the totals by
+/// severity, each repository's counts, the most widespread vulnerable dependencies, and the
+/// oldest open alerts.
+pub struct SecurityReport {
+    generated_at: DateTime<Utc>,
+    // the open alert totals, most severe first
+    totals: [(&'static str, usize); 4],
+    repository_count: usize,
+    tables: Vec<ReportTable>,
+}
+
+impl SecurityReport {
+    pub fn new(repositories: &RepositoryList, now: DateTime<Utc>) -> SecurityReport {
+        let owned: Vec<&Repository> = repositories.owned().collect();
+        let total = |count: fn(&Repository) -> usize| owned.iter().map(|repo| count(repo)).sum();
+
+        let mut repos_with_alerts: Vec<&Repository> = owned
+            .iter()
+            .copied()
+            .filter(|repo| repo.total_active_alerts > 0)
+            .collect();
+        repos_with_alerts.sort_by_key(|repo| {
+            std::cmp::Reverse((
+                repo.critical_alerts,
+                repo.high_alerts,
+                repo.total_active_alerts,
+            ))
+        });
+        let repository_table = ReportTable {
+            heading: "Open Alerts by Repository",
+            headers: vec!["Repository", "Critical", "High", "Medium", "Low", "Total"],
+            rows: repos_with_alerts
+                .iter()
+                .map(|repo| {
+                    vec![
+                        repo.full_name.clone(),
+                        repo.critical_alerts.to_string(),
+                        repo.high_alerts.to_string(),
+                        repo.medium_alerts.to_string(),
+                        repo.low_alerts.to_string(),
+                        repo.total_active_alerts.to_string(),
+                    ]
+                })
+                .collect(),
+        };
+
+        let dependency_table = ReportTable {
+            heading: "Top Vulnerable Dependencies",
+            headers: vec![
+                "Package",
+                "Ecosystem",
+                "Worst Severity",
+                "Repositories",
+                "Alerts",
+            ],
+            rows: repositories
+                .vulnerable_dependencies()
+                .iter()
+                .take(REPORT_SECTION_LENGTH)
+                .map(|dependency| {
+                    vec![
+                        dependency.dependency_name.clone(),
+                        dependency.dependency_ecosystem.clone(),
+                        dependency.severity.to_string(),
+                        dependency.repositories.len().to_string(),
+                        dependency.alert_count().to_string(),
+                    ]
+                })
+                .collect(),
+        };
+
+        let mut open_alerts: Vec<(&Repository, &Dependabot)> = owned
+            .iter()
+            .flat_map(|repo| repo.dependabots.iter().map(move |alert| (*repo, alert)))
+            .filter(|(_, alert)| alert.state == DependabotState::Open)
+            .collect();
+        open_alerts.sort_by_key(|(_, alert)| std::cmp::Reverse(alert.age(now)));
+        let oldest_table = ReportTable {
+            heading: "Oldest Open Alerts",
+            headers: vec!["Repository", "Alert", "Package", "Severity", "Age"],
+            rows: open_alerts
+                .iter()
+                .take(REPORT_SECTION_LENGTH)
+                .map(|(repo, alert)| {
+                    vec![
+                        repo.full_name.clone(),
+                        format!("#{}", alert.number),
+                        alert.dependency_name.clone(),
+                        alert.severity.to_string(),
+                        format_age(alert.age(now)),
+                    ]
+                })
+                .collect(),
+        };
+
+        SecurityReport {
+            generated_at: now,
+            totals: [
+                ("Critical", total(|repo| repo.critical_alerts)),
+                ("High", total(|repo| repo.high_alerts)),
+                ("Medium", total(|repo| repo.medium_alerts)),
+                ("Low", total(|repo| repo.low_alerts)),
+            ],
+            repository_count: owned.len(),
+            tables: vec![repository_table, dependency_table, oldest_table],
+        }
+    }
+
+    fn total_open_alerts(&self) -> usize {
+        self.totals.iter().map(|(_, count)| count).sum()
+    }
+
+    pub fn to_markdown(&self) -> String {
+        let mut markdown = format!(
+            "# Dependabot Security Report\n\nGenerated {}\n\n{} open alerts across {} repositories\n\n",
+            self.generated_at.format("%Y-%m-%d %H:%M UTC"),
+            self.total_open_alerts(),
+            self.repository_count
+        );
+        for (severity, count) in self.totals.iter() {
+            markdown.push_str(&format!("- **{}**: {}\n", severity, count));
+        }
+
+        for table in self.tables.iter() {
+            markdown.push_str(&format!("\n## {}\n\n", table.heading));
+            if table.rows.is_empty() {
+                markdown.push_str("None\n");
+                continue;
+            }
+            markdown.push_str(&format!("| {} |\n", table.headers.join(" | ")));
+            markdown.push_str(&format!(
+                "|{}\n",
+                table.headers.iter().map(|_| " --- |").collect::<String>()
+            ));
+            for row in table.rows.iter() {
+                let cells: Vec<String> = row.iter().map(|cell| cell.replace('|', "\\|")).collect();
+                markdown.push_str(&format!("| {} |\n", cells.join(" | ")));
+            }
+        }
+
+        markdown
+    }
+
+    pub fn to_html(&self) -> String {
+        let mut html = format!(
+            "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>Dependabot Security Report</title>\n</head>\n<body>\n<h1>Dependabot Security Report</h1>\n<p>Generated {}</p>\n<p>{} open alerts across {} repositories</p>\n<ul>\n",
+            self.generated_at.format("%Y-%m-%d %H:%M UTC"),
+            self.total_open_alerts(),
+            self.repository_count
+        );
+        for (severity, count) in self.totals.iter() {
+            html.push_str(&format!(
+                "<li><strong>{}</strong>: {}</li>\n",
+                severity, count
+            ));
+        }
+        html.push_str("</ul>\n");
+
+        for table in self.tables.iter() {
+            html.push_str(&format!("<h2>{}</h2>\n", table.heading));
+            if table.rows.is_empty() {
+                html.push_str("<p>None</p>\n");
+                continue;
+            }
+            html.push_str("<table>\n<tr>");
+            for header in table.headers.iter() {
+                html.push_str(&format!("<th>{}</th>", header));
+            }
+            html.push_str("</tr>\n");
+            for row in table.rows.iter() {
+                html.push_str("<tr>");
+                for cell in row.iter() {
+                    html.push_str(&format!("<td>{}</td>", escape_html(cell)));
+                }
+                html.push_str("</tr>\n");
+            }
+            html.push_str("</table>\n");
+        }
+        html.push_str("</body>\n</html>\n");
+
+        html
+    }
+}
+
+pub fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
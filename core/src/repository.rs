@@ -0,0 +1,1549 @@
+use std::collections::HashMap;
+use std::fmt::{self, Display, Formatter};
+use std::sync::Mutex;
+
+use chrono::{DateTime, Duration, NaiveDateTime, Utc};
+use futures::future;
+use futures::stream::{self, StreamExt};
+use reqwest::header::{
+    HeaderMap, HeaderValue, ACCEPT, AUTHORIZATION, ETAG, IF_NONE_MATCH, LINK, USER_AGENT,
+};
+use reqwest::Response;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use tokio_util::sync::CancellationToken;
+
+use crate::dependabot::{
+    Dependabot, DependabotSeverity, DependabotState, DismissReason, GithubDependabot,
+};
+use crate::error::{check_rate_limit, check_response, DependabotTrackerError};
+use crate::fetch_config::FetchConfig;
+use crate::github_client::{GithubClient, HttpGithubClient};
+use crate::graphql::list_github_repos_graphql;
+use crate::provider::{ProviderKind, SecurityProvider};
+use crate::pull_request::{fetch_dependabot_pull_requests, DependabotPullRequest};
+use crate::repository_list::RepositoryList;
+use crate::retry::with_retry;
+use crate::scanning::{CodeScanningAlert, GithubCodeScanningAlert, SecretScanningAlert};
+use crate::security_alert::open_alert_count_with_severity;
+use crate::storage::save_snapshot;
+use crate::trace_dbg;
+
+const TOKEN_EXPIRATION_HEADER: &str = "github-authentication-token-expiration";
+// how many repositories to fetch alerts for at once
+pub const MAX_CONCURRENT_REQUESTS: usize = 8;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitHubRepository {
+    pub id: u32,
+    pub name: String,
+    pub full_name: String,
+    pub private: bool,
+    pub html_url: String,
+    pub archived: bool,
+    #[serde(default)]
+    pub fork: bool,
+    // `public`, `private`, or `internal`
+    pub visibility: Option<String>,
+    // when a commit was last pushed to any branch
+    pub pushed_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub topics: Vec<String>,
+    pub owner: RepositoryOwner,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RepositoryOwner {
+    pub login: String,
+}
+
+/// Whether a repository's alerts could be read during the last fetch. Only repositories whose
+/// alerts are `Enabled` can be trusted to have as few alerts as they show.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AlertsStatus {
+    #[default]
+    Enabled,
+    // Dependabot alerts are turned off for the repository
+    Disabled,
+    // the token can't see the repository or its alerts, so it shows its last known data
+    NoAccess,
+    // fetching the alerts failed, so the repository shows its last known data
+    Error,
+}
+
+impl AlertsStatus {
+    /// The badge shown after the repository's name, or `None` if its alerts were read.
+    pub fn badge(self) -> Option<&'static str> {
+        match self {
+            AlertsStatus::Enabled => None,
+            AlertsStatus::Disabled => Some("[alerts disabled]"),
+            AlertsStatus::NoAccess => Some("[no access]"),
+            AlertsStatus::Error => Some("[fetch failed]"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Repository {
+    pub id: u32,
+    pub name: String,
+    pub full_name: String,
+    pub private: bool,
+    pub url: String,
+    pub archived: bool,
+    #[serde(default)]
+    pub fork: bool,
+    #[serde(default)]
+    pub visibility: Option<String>,
+    #[serde(default)]
+    pub pushed_at: Option<DateTime<Utc>>,
+    pub dependabots: Vec<Dependabot>,
+    pub low_alerts: usize,
+    pub medium_alerts: usize,
+    pub high_alerts: usize,
+    pub critical_alerts: usize,
+    pub total_active_alerts: usize,
+    // whether the repository's alerts could be read during the last fetch
+    #[serde(default)]
+    pub alerts_status: AlertsStatus,
+    // whether this is a repository from the watchlist rather than one the user owns
+    #[serde(default)]
+    pub watched: bool,
+    // whether the repository has a SECURITY.md, or `None` if the token couldn't check
+    #[serde(default)]
+    pub has_security_policy: Option<bool>,
+    // when this repository's alerts were last fetched successfully
+    #[serde(default)]
+    pub last_fetched_at: Option<DateTime<Utc>>,
+    // the repository's GitHub topics, e.g. `production` or `deprecated`
+    #[serde(default)]
+    pub topics: Vec<String>,
+    // the ETag of the last alerts response, to ask GitHub for the alerts only if they changed
+    #[serde(default)]
+    pub alerts_etag: Option<String>,
+    // the open pull requests opened by Dependabot, fetched only while there are open alerts
+    #[serde(default)]
+    pub pull_requests: Vec<DependabotPullRequest>,
+    // left empty when the feature isn't enabled or the token can't read it
+    #[serde(default)]
+    pub code_scanning_alerts: Vec<CodeScanningAlert>,
+    #[serde(default)]
+    pub secret_scanning_alerts: Vec<SecretScanningAlert>,
+    // where the repository is hosted
+    #[serde(default)]
+    pub provider: ProviderKind,
+}
+
+impl Repository {
+    fn open_alert_ages(&self) -> Vec<Duration> {
+        let now = Utc::now();
+        self.dependabots
+            .iter()
+            .filter(|dependabot| dependabot.state == DependabotState::Open)
+            .filter_map(|dependabot| dependabot.age(now))
+            .collect()
+    }
+
+    pub fn mean_open_alert_age(&self) -> Option<Duration> {
+        let ages = self.open_alert_ages();
+        if ages.is_empty() {
+            return None;
+        }
+        let total_seconds: i64 = ages.iter().map(|age| age.num_seconds()).sum();
+
+        Some(Duration::seconds(total_seconds / ages.len() as i64))
+    }
+
+    pub fn max_open_alert_age(&self) -> Option<Duration> {
+        self.open_alert_ages().into_iter().max()
+    }
+
+    fn open_alert_count(&self, severity: DependabotSeverity) -> usize {
+        open_alert_count_with_severity(&self.dependabots, severity)
+    }
+
+    /// The open Dependabot pull request that would fix an alert, if there is one.
+    pub fn pull_request_for(&self, dependabot: &Dependabot) -> Option<&DependabotPullRequest> {
+        self.pull_requests
+            .iter()
+            .find(|pull_request| pull_request.fixes(dependabot))
+    }
+
+    /// Recalculate the per-severity open alert counts from `dependabots`.
+    pub fn update_alert_counts(&mut self) {
+        self.low_alerts = self.open_alert_count(DependabotSeverity::Low);
+        self.medium_alerts = self.open_alert_count(DependabotSeverity::Medium);
+        self.high_alerts = self.open_alert_count(DependabotSeverity::High);
+        self.critical_alerts = self.open_alert_count(DependabotSeverity::Critical);
+        self.total_active_alerts =
+            self.low_alerts + self.medium_alerts + self.high_alerts + self.critical_alerts;
+    }
+
+    /// The open alerts, worst severity first and oldest first within a severity.
+    pub fn open_alerts_by_severity(&self) -> Vec<&Dependabot> {
+        let mut open_alerts: Vec<&Dependabot> = self
+            .dependabots
+            .iter()
+            .filter(|dependabot| dependabot.state == DependabotState::Open)
+            .collect();
+        open_alerts.sort_by(|a, b| {
+            b.severity
+                .cmp(&a.severity)
+                .then_with(|| a.created_at.cmp(&b.created_at))
+        });
+
+        open_alerts
+    }
+
+    /// A short Markdown summary of the repository's open alerts, for pasting into chat or tickets.
+    pub fn markdown_summary(&self) -> String {
+        let mut summary = format!(
+            "**[{}]({})**: {} open alerts (critical: {}, high: {}, medium: {}, low: {})\n",
+            self.full_name,
+            self.url,
+            self.total_active_alerts,
+            self.critical_alerts,
+            self.high_alerts,
+            self.medium_alerts,
+            self.low_alerts
+        );
+        for dependabot in self.open_alerts_by_severity().iter().take(3) {
+            summary.push_str(&format!(
+                "- {} `{}` ({}): {}\n",
+                dependabot.severity,
+                dependabot.dependency_name,
+                dependabot.dependency_ecosystem,
+                dependabot.html_url
+            ));
+        }
+
+        summary
+    }
+
+    /// Whether the repository hasn't been fetched within `stale_after`.
+    pub fn is_stale(&self, stale_after: Duration) -> bool {
+        self.last_fetched_at
+            .is_none_or(|last_fetched_at| Utc::now() - last_fetched_at > stale_after)
+    }
+
+    /// The repository's Dependabot page under the GitHub Security tab, or its GitLab
+    /// vulnerability report.
+    pub fn security_tab_url(&self) -> String {
+        match self.provider {
+            ProviderKind::GitHub => {
+                format!("https://github.com/{}/security/dependabot", self.full_name)
+            }
+            ProviderKind::GitLab => format!("{}/-/security/vulnerability_report", self.url),
+        }
+    }
+
+    /// A copy of the repository with only the alerts that have a patched version available.
+    pub fn fixable_only(&self) -> Repository {
+        let mut repository = self.clone();
+        repository
+            .dependabots
+            .retain(|dependabot| dependabot.is_fixable());
+        repository.update_alert_counts();
+
+        repository
+    }
+
+    /// The user or organization that owns the repository.
+    pub fn owner(&self) -> &str {
+        self.full_name
+            .split_once('/')
+            .map_or(self.full_name.as_str(), |(owner, _)| owner)
+    }
+
+    /// Whether the repository is public, private, or internal.
+    pub fn visibility_label(&self) -> &str {
+        match &self.visibility {
+            Some(visibility) => visibility,
+            None if self.private => "private",
+            None => "public",
+        }
+    }
+
+    /// When the most recently updated alert was last updated, as GitHub's timestamp.
+    pub fn last_alert_update(&self) -> Option<&str> {
+        self.dependabots
+            .iter()
+            .map(|dependabot| dependabot.updated_at.as_str())
+            .max()
+    }
+}
+
+pub struct FetchOutcome {
+    pub repositories: RepositoryList,
+    // when the PAT expires, if GitHub reported it
+    pub token_expiration: Option<DateTime<Utc>>,
+    // whether the repositories were cut off at the configured `max_repositories`
+    pub truncated: bool,
+    // whether only some repositories were refreshed, so the account-wide details are unknown
+    pub partial: bool,
+    // whether the fetch was cancelled part way, leaving the repositories it hadn't reached with
+    // their saved data
+    pub cancelled: bool,
+    // problems with the fetch beyond any one repository, like an organization that couldn't
+    // be listed
+    pub warnings: Vec<String>,
+    // which repositories had their alerts fetched and which didn't
+    pub report: FetchReport,
+}
+
+/// How many repositories a fetch got the alerts for, and the ones it couldn't even after
+/// retrying, so they can be shown rather than quietly left with old data.
+#[derive(Debug, Clone, Default)]
+pub struct FetchReport {
+    pub succeeded: usize,
+    // each repository that failed and why
+    pub failed: Vec<(String, String)>,
+}
+
+impl FetchOutcome {
+    /// The outcome of a fetch cancelled before it got to any repository's alerts, which leaves
+    /// every repository as it was.
+    pub fn cancelled(previous_repos: &[Repository]) -> Self {
+        FetchOutcome {
+            repositories: RepositoryList::with_respositories(previous_repos.to_vec()),
+            token_expiration: None,
+            truncated: false,
+            partial: true,
+            cancelled: true,
+            warnings: Vec::new(),
+            report: FetchReport::default(),
+        }
+    }
+}
+
+impl FetchReport {
+    pub fn record<T>(&mut self, name: &str, result: &Result<T, DependabotTrackerError>) {
+        match result {
+            Ok(_) => self.succeeded += 1,
+            Err(e) => self.failed.push((name.to_string(), e.to_string())),
+        }
+    }
+
+    /// e.g. `2 of 40 repositories failed to fetch`, or `None` if none did.
+    pub fn failure_summary(&self) -> Option<String> {
+        if self.failed.is_empty() {
+            return None;
+        }
+
+        Some(format!(
+            "{} of {} repositories failed to fetch",
+            self.failed.len(),
+            self.succeeded + self.failed.len()
+        ))
+    }
+}
+
+/// A step of a running fetch, sent as it happens so the Updating popup can show how far along
+/// it is.
+#[derive(Debug, Clone)]
+pub enum FetchProgress {
+    /// A page of a paginated listing was read.
+    Page(PageProgress),
+    /// This many more repositories are about to have their alerts fetched.
+    RepositoriesQueued(usize),
+    /// A repository's alerts finished fetching, or failed to.
+    RepositoryDone { name: String, failed: bool },
+    /// A repository was fetched, so it can be shown before the rest of the fetch is done.
+    RepositoryFetched(Box<Repository>),
+}
+
+/// How far a paginated listing has gotten.
+#[derive(Debug, Clone)]
+pub struct PageProgress {
+    // what's being fetched, e.g. "Repositories" or "my-repo alerts"
+    pub label: String,
+    pub page: usize,
+    // the last page, if GitHub reported it
+    pub last_page: Option<usize>,
+}
+
+impl Display for PageProgress {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self.last_page {
+            Some(last_page) => write!(f, "{}: page {} of {}", self.label, self.page, last_page),
+            None => write!(f, "{}: page {}", self.label, self.page),
+        }
+    }
+}
+
+/// Fine-grained PATs only see the repositories they were granted access to, as do GitHub App user
+/// tokens from the device flow login.
+pub fn is_fine_grained_token(token: &str) -> bool {
+    token.starts_with("github_pat_") || token.starts_with("ghu_")
+}
+
+/// The error for a token with characters that can't be sent in a header, like a stray newline.
+pub fn invalid_token() -> DependabotTrackerError {
+    DependabotTrackerError::Config(
+        "The token has characters that can't be sent to the server".to_string(),
+    )
+}
+
+pub fn github_headers(token: &str) -> Result<HeaderMap, DependabotTrackerError> {
+    let mut headers = HeaderMap::new();
+    headers.insert(
+        ACCEPT,
+        HeaderValue::from_static("application/vnd.github+json"),
+    );
+    headers.insert(
+        AUTHORIZATION,
+        HeaderValue::from_str(&format!("Bearer {}", token)).map_err(|_| invalid_token())?,
+    );
+    headers.insert(USER_AGENT, HeaderValue::from_static("reqwest"));
+    headers.insert(
+        "X-GitHub-Api-Version",
+        HeaderValue::from_static("2022-11-28"),
+    );
+
+    Ok(headers)
+}
+
+/// The URL for the page with the given `rel` (e.g. `next` or `last`) from the `Link` header,
+/// which looks like `<https://api.github.com/user/repos?page=2>; rel="next", <...>; rel="last"`.
+fn link_url(headers: &HeaderMap, rel: &str) -> Option<String> {
+    let link = headers.get(LINK)?.to_str().ok()?;
+    let rel = format!("rel=\"{}\"", rel);
+
+    link.split(',').find_map(|part| {
+        let (url, params) = part.split_once(';')?;
+        params.contains(&rel).then(|| {
+            url.trim()
+                .trim_start_matches('<')
+                .trim_end_matches('>')
+                .to_string()
+        })
+    })
+}
+
+fn page_number(url: &str) -> Option<usize> {
+    url.split(['?', '&'])
+        .find_map(|param| param.strip_prefix("page=")?.parse().ok())
+}
+
+/// Read a JSON array from `response` and every page after it, following the `Link` header. A
+/// next page anywhere other than the API is refused rather than sent the token.
+async fn read_all_pages<T: DeserializeOwned>(
+    token: &str,
+    client: &dyn GithubClient,
+    mut response: Response,
+    label: &str,
+    progress: &(dyn Fn(FetchProgress) + Sync),
+) -> Result<Vec<T>, DependabotTrackerError> {
+    let mut items = Vec::new();
+    let mut page = 1;
+    let mut last_page = None;
+
+    loop {
+        let next_url = link_url(response.headers(), "next");
+        last_page = link_url(response.headers(), "last")
+            .and_then(|url| page_number(&url))
+            .or(last_page)
+            .or(next_url.is_none().then_some(page));
+        progress(FetchProgress::Page(PageProgress {
+            label: label.to_string(),
+            page,
+            last_page,
+        }));
+
+        let mut page_items: Vec<T> = response.json().await?;
+        items.append(&mut page_items);
+
+        let Some(next_url) = next_url else {
+            break;
+        };
+        if !client.is_api_url(&next_url) {
+            return Err(DependabotTrackerError::Other(format!(
+                "The next page of {} is at {}, outside of {}",
+                label,
+                next_url,
+                client.api_url()
+            )));
+        }
+        response = check_response(
+            client
+                .get(&next_url)
+                .headers(github_headers(token)?)
+                .send()
+                .await?,
+        )?;
+        page += 1;
+    }
+
+    Ok(items)
+}
+
+/// Fetch every page of a JSON array endpoint.
+async fn fetch_all_pages<T: DeserializeOwned>(
+    token: &str,
+    client: &dyn GithubClient,
+    url: String,
+    label: &str,
+    progress: &(dyn Fn(FetchProgress) + Sync),
+) -> Result<Vec<T>, DependabotTrackerError> {
+    let response = check_response(
+        client
+            .get(&url)
+            .headers(github_headers(token)?)
+            .send()
+            .await?,
+    )?;
+
+    read_all_pages(token, client, response, label, progress).await
+}
+
+/// How the repositories and their Dependabot alerts are listed from GitHub.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FetchMethod {
+    /// A request for each repository's alerts through the REST API.
+    #[default]
+    Rest,
+    /// Batched GraphQL queries that list the repositories along with their alerts, falling back
+    /// to the REST API if they fail.
+    Graphql,
+}
+
+/// The repositories to fetch alerts for, along with any alerts that were already listed in bulk.
+pub struct RepositoryListing {
+    pub repositories: Vec<GitHubRepository>,
+    // the alerts already listed for a repository, by its id, so it isn't fetched by itself
+    pub alerts: HashMap<u32, Vec<GithubDependabot>>,
+    // when the PAT expires, if GitHub reported it
+    pub token_expiration: Option<DateTime<Utc>>,
+}
+
+/// Fetches the user's repositories and their Dependabot, code scanning, and secret scanning
+/// alerts from the GitHub API.
+#[derive(Debug, Clone)]
+pub struct GitHubProvider {
+    pub token: String,
+    // the API to fetch from, `https://api.github.com` for GitHub itself
+    pub api_url: String,
+}
+
+impl GitHubProvider {
+    fn client(&self, config: &FetchConfig) -> HttpGithubClient {
+        HttpGithubClient::new(config.retry.client(), &self.api_url)
+    }
+}
+
+impl SecurityProvider for GitHubProvider {
+    async fn fetch_repos(
+        &self,
+        previous_repos: &[Repository],
+        config: &FetchConfig,
+        progress: &(dyn Fn(FetchProgress) + Sync),
+        cancellation: &CancellationToken,
+    ) -> Result<FetchOutcome, DependabotTrackerError> {
+        fetch_github_repos(
+            &self.token,
+            &self.client(config),
+            previous_repos,
+            config,
+            progress,
+            cancellation,
+        )
+        .await
+    }
+
+    async fn fetch_alerts(
+        &self,
+        repository: &Repository,
+        config: &FetchConfig,
+        progress: &(dyn Fn(FetchProgress) + Sync),
+    ) -> Result<Repository, DependabotTrackerError> {
+        let client = self.client(config);
+        let Some(github_repo) =
+            fetch_repository_details(&self.token, &client, &repository.full_name).await?
+        else {
+            return Ok(Repository {
+                alerts_status: AlertsStatus::NoAccess,
+                ..repository.clone()
+            });
+        };
+
+        fetch_repo_depenabot_alerts(
+            &self.token,
+            &github_repo,
+            &client,
+            config,
+            Some(repository),
+            progress,
+        )
+        .await
+    }
+}
+
+async fn fetch_github_repos(
+    token: &str,
+    client: &dyn GithubClient,
+    previous_repos: &[Repository],
+    config: &FetchConfig,
+    progress: &(dyn Fn(FetchProgress) + Sync),
+    cancellation: &CancellationToken,
+) -> Result<FetchOutcome, DependabotTrackerError> {
+    let mut warnings = Vec::new();
+    let mut report = FetchReport::default();
+    let list = async {
+        match config.fetch_method {
+            FetchMethod::Rest => {
+                list_github_repos(token, client, config, progress, &mut warnings).await
+            }
+            FetchMethod::Graphql => {
+                match list_github_repos_graphql(token, client, config, progress, &mut warnings)
+                    .await
+                {
+                    Ok(listing) => Ok(listing),
+                    Err(e) => {
+                        let graphql_error =
+                            format!("GraphQL fetch failed, using the REST API: {}", e);
+                        trace_dbg!(level: tracing::Level::WARN, &graphql_error);
+                        warnings.push(graphql_error);
+                        list_github_repos(token, client, config, progress, &mut warnings).await
+                    }
+                }
+            }
+        }
+    };
+    let listing = tokio::select! {
+        listing = list => listing?,
+        _ = cancellation.cancelled() => return Ok(FetchOutcome::cancelled(previous_repos)),
+    };
+    let RepositoryListing {
+        repositories: mut repos,
+        alerts: listed_alerts,
+        token_expiration,
+    } = listing;
+
+    // excluded and long-untouched repositories are dropped before any of their alerts are
+    // requested
+    repos.retain(|repo| {
+        !config.is_excluded(&repo.full_name, repo.archived, repo.fork)
+            && config.is_recently_pushed(repo.pushed_at)
+    });
+
+    let truncated = config
+        .max_repositories
+        .is_some_and(|max_repositories| repos.len() > max_repositories);
+    if let Some(max_repositories) = config.max_repositories {
+        repos.truncate(max_repositories);
+    }
+
+    let mut updated_repos = fetch_dependabot_alerts(
+        token,
+        client,
+        &repos,
+        config,
+        &listed_alerts,
+        previous_repos,
+        false,
+        progress,
+        cancellation,
+        &mut report,
+    )
+    .await;
+
+    if cancellation.is_cancelled() {
+        // the watchlist hasn't been looked up, so its repositories keep their saved data
+        updated_repos.extend(previous_repos.iter().filter(|repo| repo.watched).cloned());
+    } else {
+        let watched_repos = fetch_watched_repos(token, client, &repos, config, &mut report).await;
+        let updated_watched_repos = fetch_dependabot_alerts(
+            token,
+            client,
+            &watched_repos,
+            config,
+            &HashMap::new(),
+            previous_repos,
+            true,
+            progress,
+            cancellation,
+            &mut report,
+        )
+        .await;
+        updated_repos.extend(updated_watched_repos);
+    }
+
+    if is_fine_grained_token(token) {
+        carry_over_inaccessible_repos(&mut updated_repos, previous_repos);
+    }
+
+    save_snapshot(&updated_repos)?;
+
+    Ok(FetchOutcome {
+        repositories: RepositoryList::with_respositories(updated_repos),
+        token_expiration,
+        truncated,
+        partial: cancellation.is_cancelled(),
+        cancelled: cancellation.is_cancelled(),
+        warnings,
+        report,
+    })
+}
+
+/// List the user's and the configured organizations' repositories through the REST API, along
+/// with the alerts of any organization that can list them all at once.
+async fn list_github_repos(
+    token: &str,
+    client: &dyn GithubClient,
+    config: &FetchConfig,
+    progress: &(dyn Fn(FetchProgress) + Sync),
+    warnings: &mut Vec<String>,
+) -> Result<RepositoryListing, DependabotTrackerError> {
+    let url = format!(
+        "/user/repos?affiliation={}&per_page={}",
+        config.affiliation,
+        config.per_page()
+    );
+
+    let response = check_response(
+        client
+            .get(&url)
+            .headers(github_headers(token)?)
+            .send()
+            .await?,
+    )?;
+
+    let token_expiration = token_expiration(response.headers());
+    let mut repos: Vec<GitHubRepository> =
+        read_all_pages(token, client, response, "Repositories", progress).await?;
+
+    for organization in config.organizations.iter() {
+        let organization_repos =
+            fetch_organization_repos(token, client, organization, config, progress, warnings).await;
+        for repo in organization_repos {
+            if !repos.iter().any(|existing| existing.id == repo.id) {
+                repos.push(repo);
+            }
+        }
+    }
+
+    // a repository in an organization whose alerts were listed takes its alerts from there
+    let organization_alerts = fetch_organization_alerts(token, client, config, progress).await;
+    let alerts = repos
+        .iter()
+        .filter_map(|repo| {
+            let alerts = organization_alerts.get(&repo.owner.login.to_lowercase())?;
+            let repo_alerts = alerts
+                .iter()
+                .filter(|alert| {
+                    alert
+                        .repository
+                        .as_ref()
+                        .is_some_and(|alert_repo| alert_repo.full_name == repo.full_name)
+                })
+                .cloned()
+                .collect();
+            Some((repo.id, repo_alerts))
+        })
+        .collect();
+
+    Ok(RepositoryListing {
+        repositories: repos,
+        alerts,
+        token_expiration,
+    })
+}
+
+/// Refresh only the repositories that haven't been fetched within `stale_after`, keeping the
+/// rest of `previous_repos` as they are. Once `cancellation` is cancelled, the stale
+/// repositories that haven't been refreshed yet are kept as they are too.
+pub async fn fetch_stale_repos<P: SecurityProvider + Sync>(
+    provider: &P,
+    previous_repos: &[Repository],
+    config: &FetchConfig,
+    stale_after: Duration,
+    progress: &(dyn Fn(FetchProgress) + Sync),
+    cancellation: &CancellationToken,
+) -> Result<FetchOutcome, DependabotTrackerError> {
+    let mut updated_repos = previous_repos.to_vec();
+    let report = Mutex::new(FetchReport::default());
+
+    let needs_refresh = |repo: &Repository| {
+        repo.is_stale(stale_after) && !config.is_excluded(&repo.full_name, repo.archived, repo.fork)
+    };
+    let stale_count = updated_repos
+        .iter()
+        .filter(|repo| needs_refresh(repo))
+        .count();
+    progress(FetchProgress::RepositoriesQueued(stale_count));
+    stream::iter(updated_repos.iter_mut().filter(|repo| needs_refresh(repo)))
+        .for_each_concurrent(MAX_CONCURRENT_REQUESTS, |repo| {
+            let report = &report;
+            async move {
+                let fetch = with_retry(&config.retry, &repo.name, || {
+                    provider.fetch_alerts(repo, config, progress)
+                });
+                let result = tokio::select! {
+                    result = fetch => result,
+                    _ = cancellation.cancelled() => return,
+                };
+                report.lock().unwrap().record(&repo.full_name, &result);
+                match result {
+                    Ok(updated_repo) if updated_repo.alerts_status == AlertsStatus::NoAccess => {
+                        repo.alerts_status = AlertsStatus::NoAccess
+                    }
+                    Ok(updated_repo) => {
+                        *repo = Repository {
+                            watched: repo.watched,
+                            ..updated_repo
+                        };
+                        progress(FetchProgress::RepositoryFetched(Box::new(repo.clone())));
+                    }
+                    Err(e) => {
+                        let stale_refresh_error = format!("Failed to refresh {}: {}", repo.name, e);
+                        trace_dbg!(level: tracing::Level::WARN, stale_refresh_error);
+                        repo.alerts_status = AlertsStatus::Error;
+                    }
+                }
+                let failed = repo.alerts_status == AlertsStatus::Error;
+                progress(FetchProgress::RepositoryDone {
+                    name: repo.name.clone(),
+                    failed,
+                });
+            }
+        })
+        .await;
+
+    save_snapshot(&updated_repos)?;
+
+    Ok(FetchOutcome {
+        repositories: RepositoryList::with_respositories(updated_repos),
+        token_expiration: None,
+        truncated: false,
+        partial: true,
+        cancelled: cancellation.is_cancelled(),
+        warnings: Vec::new(),
+        report: report.into_inner().unwrap(),
+    })
+}
+
+/// Keep the last known data for listed repositories whose alerts the token can no longer read,
+/// rather than reporting them as having no alerts. Repositories missing from the listing were
+/// deleted, transferred, or filtered out, so they're left out rather than kept as inaccessible.
+fn carry_over_inaccessible_repos(updated_repos: &mut [Repository], previous_repos: &[Repository]) {
+    for repo in updated_repos
+        .iter_mut()
+        .filter(|repo| repo.alerts_status == AlertsStatus::NoAccess)
+    {
+        if let Some(previous) = previous_repos
+            .iter()
+            .find(|previous| previous.id == repo.id)
+        {
+            *repo = Repository {
+                alerts_status: AlertsStatus::NoAccess,
+                ..previous.clone()
+            };
+        }
+    }
+}
+
+/// When the PAT expires, from the header GitHub adds to its responses for tokens that expire.
+pub fn token_expiration(headers: &HeaderMap) -> Option<DateTime<Utc>> {
+    headers
+        .get(TOKEN_EXPIRATION_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .and_then(parse_token_expiration)
+}
+
+/// Parse the token expiration header, which looks like `2024-05-01 12:00:00 UTC`
+/// or `2024-05-01 12:00:00 -0700`.
+fn parse_token_expiration(value: &str) -> Option<DateTime<Utc>> {
+    if let Ok(expiration) = DateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S %z") {
+        return Some(expiration.with_timezone(&Utc));
+    }
+
+    NaiveDateTime::parse_from_str(value.trim_end_matches(" UTC"), "%Y-%m-%d %H:%M:%S")
+        .ok()
+        .map(|expiration| expiration.and_utc())
+}
+
+/// Fetch the repository details for each `owner/repo` in the watchlist, skipping any the
+/// token can't see and any that are already in the user's own repositories.
+async fn fetch_watched_repos(
+    token: &str,
+    client: &dyn GithubClient,
+    owned_repos: &[GitHubRepository],
+    config: &FetchConfig,
+    report: &mut FetchReport,
+) -> Vec<GitHubRepository> {
+    let requests: Vec<_> = config
+        .watched_repositories
+        .iter()
+        .filter(|full_name| {
+            !owned_repos
+                .iter()
+                .any(|repo| repo.full_name.eq_ignore_ascii_case(full_name))
+        })
+        .map(|full_name| async move {
+            (
+                full_name,
+                fetch_repository_details(token, client, full_name).await,
+            )
+        })
+        .collect();
+    let watched_repos: Vec<_> = stream::iter(requests)
+        .buffered(MAX_CONCURRENT_REQUESTS)
+        .collect()
+        .await;
+
+    watched_repos
+        .into_iter()
+        .filter_map(|(full_name, repo)| {
+            let error = match repo {
+                Ok(Some(repo)) => return Some(repo),
+                Ok(None) => "The token can't see the watched repository".to_string(),
+                Err(e) => e.to_string(),
+            };
+            let watched_repo_error = format!(
+                "Unable to fetch watched repository {}: {}",
+                full_name, error
+            );
+            trace_dbg!(level: tracing::Level::WARN, watched_repo_error);
+            report.failed.push((full_name.clone(), error));
+            None
+        })
+        .collect()
+}
+
+/// Fetch a single repository by its `owner/repo` name, or `None` if the token can't see it.
+async fn fetch_repository_details(
+    token: &str,
+    client: &dyn GithubClient,
+    full_name: &str,
+) -> Result<Option<GitHubRepository>, DependabotTrackerError> {
+    let url = format!("/repos/{}", full_name);
+    let response = check_rate_limit(
+        client
+            .get(&url)
+            .headers(github_headers(token)?)
+            .send()
+            .await?,
+    )?;
+
+    let status = response.status();
+    if status == reqwest::StatusCode::NOT_FOUND || status == reqwest::StatusCode::FORBIDDEN {
+        let repo_not_found = format!("Token cannot see repository {}", full_name);
+        trace_dbg!(level: tracing::Level::WARN, repo_not_found);
+        return Ok(None);
+    }
+
+    Ok(Some(response.error_for_status()?.json().await?))
+}
+
+/// Fetch the repositories owned by an organization, or none if the token can't list them.
+async fn fetch_organization_repos(
+    token: &str,
+    client: &dyn GithubClient,
+    organization: &str,
+    config: &FetchConfig,
+    progress: &(dyn Fn(FetchProgress) + Sync),
+    warnings: &mut Vec<String>,
+) -> Vec<GitHubRepository> {
+    let url = format!(
+        "/orgs/{}/repos?per_page={}",
+        organization,
+        config.per_page()
+    );
+    let label = format!("{} repositories", organization);
+
+    fetch_all_pages(token, client, url, &label, progress)
+        .await
+        .unwrap_or_else(|e| {
+            let organization_error =
+                format!("Unable to fetch repositories for {}: {}", organization, e);
+            trace_dbg!(level: tracing::Level::WARN, &organization_error);
+            warnings.push(organization_error);
+            Vec::new()
+        })
+}
+
+/// Fetch each configured organization's alerts in one request per organization, keyed by the
+/// organization. Organizations whose alerts the token can't list (it needs to belong to an
+/// owner or security manager) are left out, and their repositories are fetched one by one.
+async fn fetch_organization_alerts(
+    token: &str,
+    client: &dyn GithubClient,
+    config: &FetchConfig,
+    progress: &(dyn Fn(FetchProgress) + Sync),
+) -> HashMap<String, Vec<GithubDependabot>> {
+    let requests: Vec<_> = config
+        .organizations
+        .iter()
+        .map(|organization| async move {
+            let url = format!(
+                "/orgs/{}/dependabot/alerts?per_page={}",
+                organization,
+                config.per_page()
+            );
+            let label = format!("{} alerts", organization);
+            let alerts: Option<Vec<GithubDependabot>> =
+                fetch_all_pages(token, client, url, &label, progress)
+                    .await
+                    .ok();
+            if alerts.is_none() {
+                let organization_alerts_error = format!(
+                    "Unable to fetch organization alerts for {}, fetching per repository",
+                    organization
+                );
+                trace_dbg!(level: tracing::Level::WARN, organization_alerts_error);
+            }
+
+            alerts.map(|alerts| (organization.to_lowercase(), alerts))
+        })
+        .collect();
+
+    stream::iter(requests)
+        .buffer_unordered(MAX_CONCURRENT_REQUESTS)
+        .filter_map(future::ready)
+        .collect()
+        .await
+}
+
+/// Fetch the alerts for each repository, up to `MAX_CONCURRENT_REQUESTS` at a time, keeping
+/// the repositories in the order they were listed. Repositories in `listed_alerts` take their
+/// alerts from there instead. Repositories whose alerts couldn't be fetched keep their last
+/// known alerts, or are left out if there are none. Either way they're recorded in `report`.
+/// Each repository is sent to `progress` as soon as it's fetched, marked `watched` if it's from
+/// the watchlist. Once `cancellation` is cancelled, the repositories still waiting or in
+/// flight keep their last known data, and are left out of `report`.
+#[allow(clippy::too_many_arguments)]
+async fn fetch_dependabot_alerts(
+    token: &str,
+    client: &dyn GithubClient,
+    repositories: &[GitHubRepository],
+    config: &FetchConfig,
+    listed_alerts: &HashMap<u32, Vec<GithubDependabot>>,
+    previous_repos: &[Repository],
+    watched: bool,
+    progress: &(dyn Fn(FetchProgress) + Sync),
+    cancellation: &CancellationToken,
+    report: &mut FetchReport,
+) -> Vec<Repository> {
+    progress(FetchProgress::RepositoriesQueued(repositories.len()));
+    // the requests are collected before streaming them, as a stream that maps with a closure
+    // borrowing its items isn't recognized as `Send` when the fetch is spawned
+    let requests: Vec<_> = repositories
+        .iter()
+        .map(|repo| async move {
+            let previous = previous_repos
+                .iter()
+                .find(|previous| previous.id == repo.id);
+            let fetch = async {
+                match listed_alerts.get(&repo.id) {
+                    Some(repo_alerts) => {
+                        let repo_alerts = repo_alerts.clone();
+                        let has_security_policy =
+                            fetch_has_security_policy(token, repo, client).await;
+                        let updated_repo =
+                            build_repository(token, repo, client, repo_alerts, has_security_policy)
+                                .await;
+                        Ok(fetch_scanning_alerts(
+                            token,
+                            repo,
+                            client,
+                            config,
+                            progress,
+                            updated_repo,
+                        )
+                        .await)
+                    }
+                    None => {
+                        with_retry(&config.retry, &repo.name, || {
+                            fetch_repo_depenabot_alerts(
+                                token, repo, client, config, previous, progress,
+                            )
+                        })
+                        .await
+                    }
+                }
+            };
+            // a cancelled fetch stops between one request and the next, leaving the repository
+            // with its saved data
+            let result = tokio::select! {
+                result = fetch => result,
+                _ = cancellation.cancelled() => return (repo, None),
+            };
+            let result = result.map(|updated_repo| Repository {
+                watched,
+                ..updated_repo
+            });
+            if let Ok(updated_repo) = &result {
+                progress(FetchProgress::RepositoryFetched(Box::new(
+                    updated_repo.clone(),
+                )));
+            }
+            progress(FetchProgress::RepositoryDone {
+                name: repo.name.clone(),
+                failed: result.is_err(),
+            });
+            (repo, Some(result))
+        })
+        .collect();
+    let results: Vec<_> = stream::iter(requests)
+        .buffered(MAX_CONCURRENT_REQUESTS)
+        .collect()
+        .await;
+
+    results
+        .into_iter()
+        .filter_map(|(repo, result)| {
+            let previous = previous_repos
+                .iter()
+                .find(|previous| previous.id == repo.id);
+            let Some(result) = result else {
+                return previous.cloned();
+            };
+            report.record(&repo.full_name, &result);
+            match result {
+                Ok(updated_repo) => Some(updated_repo),
+                Err(e) => {
+                    let fetch_error = format!("Failed to fetch alerts for {}: {}", repo.name, e);
+                    trace_dbg!(level: tracing::Level::WARN, fetch_error);
+                    // keep the last known alerts rather than dropping the repository
+                    previous.map(|previous| Repository {
+                        alerts_status: AlertsStatus::Error,
+                        ..previous.clone()
+                    })
+                }
+            }
+        })
+        .collect()
+}
+
+/// Fetch a repository's alerts. If `previous` has an ETag from the last fetch, the alerts are
+/// only downloaded if they've changed, and otherwise the previous alerts are kept.
+async fn fetch_repo_depenabot_alerts(
+    token: &str,
+    repository: &GitHubRepository,
+    client: &dyn GithubClient,
+    config: &FetchConfig,
+    previous: Option<&Repository>,
+    progress: &(dyn Fn(FetchProgress) + Sync),
+) -> Result<Repository, DependabotTrackerError> {
+    let fetch_repo_dependabot_alert_trace =
+        format!("fetching dependabot alerts for {}", repository.name);
+    trace_dbg!(level: tracing::Level::INFO, fetch_repo_dependabot_alert_trace);
+
+    let url = format!(
+        "/repos/{}/dependabot/alerts?per_page={}",
+        repository.full_name,
+        config.per_page()
+    );
+    let mut headers = github_headers(token)?;
+    if let Some(etag) = previous.and_then(|previous| previous.alerts_etag.as_deref()) {
+        if let Ok(etag) = HeaderValue::from_str(etag) {
+            headers.insert(IF_NONE_MATCH, etag);
+        }
+    }
+    // a rate limit fails the fetch rather than being taken for the alerts being turned off
+    let response = check_rate_limit(client.get(&url).headers(headers).send().await?)?;
+
+    let not_modified = response.status() == reqwest::StatusCode::NOT_MODIFIED;
+    if let Some(previous) = previous.filter(|_| not_modified) {
+        // nothing changed, so the previous alerts, licenses, and security policy are reused
+        // without spending any more requests, apart from checking on the pull requests
+        let pull_requests = if previous.total_active_alerts > 0 {
+            fetch_dependabot_pull_requests(token, &repository.full_name, client).await
+        } else {
+            Vec::new()
+        };
+        let updated_repo = Repository {
+            pull_requests,
+            name: repository.name.clone(),
+            full_name: repository.full_name.clone(),
+            private: repository.private,
+            url: repository.html_url.clone(),
+            archived: repository.archived,
+            fork: repository.fork,
+            visibility: repository.visibility.clone(),
+            pushed_at: repository.pushed_at,
+            alerts_status: AlertsStatus::Enabled,
+            watched: false,
+            last_fetched_at: Some(Utc::now()),
+            topics: repository.topics.clone(),
+            ..previous.clone()
+        };
+        return Ok(fetch_scanning_alerts(
+            token,
+            repository,
+            client,
+            config,
+            progress,
+            updated_repo,
+        )
+        .await);
+    }
+
+    let has_security_policy = fetch_has_security_policy(token, repository, client).await;
+
+    if response.status().is_client_error() {
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        // 404s and "not accessible" 403s mean the token can't see the repository's alerts, and
+        // only a 403 saying so means dependabot alerts are turned off. anything else, like a
+        // rejected token, is a failure rather than a repository without alerts
+        let inaccessible = status == reqwest::StatusCode::NOT_FOUND
+            || body.contains("not accessible by personal access token");
+        let disabled = status == reqwest::StatusCode::FORBIDDEN
+            && body
+                .to_lowercase()
+                .contains("dependabot alerts are disabled");
+        if !inaccessible && !disabled {
+            let message = serde_json::from_str::<serde_json::Value>(&body)
+                .ok()
+                .and_then(|body| body["message"].as_str().map(String::from))
+                .unwrap_or(body);
+            return Err(DependabotTrackerError::GitHubApi {
+                status,
+                message: format!("{} ({})", message, status),
+            });
+        }
+
+        if inaccessible {
+            let repo_not_accessible = format!("Token cannot access alerts for {}", repository.name);
+            trace_dbg!(level: tracing::Level::WARN, repo_not_accessible);
+        } else {
+            let repo_dependabot_not_enabled =
+                format!("Dependabot alerts not enable for {}", repository.name);
+            trace_dbg!(level: tracing::Level::WARN, repo_dependabot_not_enabled);
+        }
+
+        let updated_repo = Repository {
+            id: repository.id,
+            name: repository.name.clone(),
+            full_name: repository.full_name.clone(),
+            private: repository.private,
+            url: repository.html_url.clone(),
+            archived: repository.archived,
+            fork: repository.fork,
+            visibility: repository.visibility.clone(),
+            pushed_at: repository.pushed_at,
+            dependabots: Vec::new(),
+            low_alerts: 0,
+            medium_alerts: 0,
+            high_alerts: 0,
+            critical_alerts: 0,
+            total_active_alerts: 0,
+            alerts_status: if inaccessible {
+                AlertsStatus::NoAccess
+            } else {
+                AlertsStatus::Disabled
+            },
+            watched: false,
+            has_security_policy,
+            last_fetched_at: Some(Utc::now()),
+            topics: repository.topics.clone(),
+            alerts_etag: None,
+            pull_requests: Vec::new(),
+            code_scanning_alerts: Vec::new(),
+            secret_scanning_alerts: Vec::new(),
+            provider: ProviderKind::GitHub,
+        };
+        if inaccessible {
+            return Ok(updated_repo);
+        }
+        return Ok(fetch_scanning_alerts(
+            token,
+            repository,
+            client,
+            config,
+            progress,
+            updated_repo,
+        )
+        .await);
+    }
+
+    // a server error is returned as one, so the fetch can be retried
+    let response = check_response(response)?;
+
+    // the ETag only covers the first page, so it can only stand in for every alert when they
+    // all fit on one page
+    let alerts_etag = response
+        .headers()
+        .get(ETAG)
+        .filter(|_| link_url(response.headers(), "next").is_none())
+        .and_then(|etag| etag.to_str().ok())
+        .map(String::from);
+    let label = format!("{} alerts", repository.name);
+    let github_dependabots: Vec<GithubDependabot> =
+        read_all_pages(token, client, response, &label, progress).await?;
+
+    let mut updated_repo = build_repository(
+        token,
+        repository,
+        client,
+        github_dependabots,
+        has_security_policy,
+    )
+    .await;
+    updated_repo.alerts_etag = alerts_etag;
+
+    Ok(fetch_scanning_alerts(token, repository, client, config, progress, updated_repo).await)
+}
+
+/// Add the repository's code scanning and secret scanning alerts. Either is left empty if the
+/// feature isn't enabled or the token can't read its alerts.
+async fn fetch_scanning_alerts(
+    token: &str,
+    repository: &GitHubRepository,
+    client: &dyn GithubClient,
+    config: &FetchConfig,
+    progress: &(dyn Fn(FetchProgress) + Sync),
+    mut updated_repo: Repository,
+) -> Repository {
+    let url = format!(
+        "/repos/{}/code-scanning/alerts?per_page={}",
+        repository.full_name,
+        config.per_page()
+    );
+    let label = format!("{} code scanning alerts", repository.name);
+    updated_repo.code_scanning_alerts = match fetch_all_pages::<GithubCodeScanningAlert>(
+        token, client, url, &label, progress,
+    )
+    .await
+    {
+        Ok(alerts) => alerts.into_iter().map(CodeScanningAlert::from).collect(),
+        Err(_) => {
+            let code_scanning_unavailable =
+                format!("Code scanning alerts unavailable for {}", repository.name);
+            trace_dbg!(level: tracing::Level::WARN, code_scanning_unavailable);
+            Vec::new()
+        }
+    };
+
+    let url = format!(
+        "/repos/{}/secret-scanning/alerts?per_page={}",
+        repository.full_name,
+        config.per_page()
+    );
+    let label = format!("{} secret scanning alerts", repository.name);
+    updated_repo.secret_scanning_alerts =
+        match fetch_all_pages(token, client, url, &label, progress).await {
+            Ok(alerts) => alerts,
+            Err(_) => {
+                let secret_scanning_unavailable =
+                    format!("Secret scanning alerts unavailable for {}", repository.name);
+                trace_dbg!(level: tracing::Level::WARN, secret_scanning_unavailable);
+                Vec::new()
+            }
+        };
+
+    updated_repo
+}
+
+/// Build a repository from its fetched alerts, looking up the vulnerable packages' licenses.
+async fn build_repository(
+    token: &str,
+    repository: &GitHubRepository,
+    client: &dyn GithubClient,
+    github_dependabots: Vec<GithubDependabot>,
+    has_security_policy: Option<bool>,
+) -> Repository {
+    let mut dependabots: Vec<Dependabot> = github_dependabots
+        .into_iter()
+        .map(Dependabot::from)
+        .collect();
+
+    if !dependabots.is_empty() {
+        let licenses = fetch_dependency_licenses(token, repository, client).await;
+        for dependabot in dependabots.iter_mut() {
+            dependabot.license = licenses
+                .iter()
+                .find(|(name, _)| {
+                    name == &dependabot.dependency_name
+                        || name.ends_with(&format!(":{}", dependabot.dependency_name))
+                })
+                .map(|(_, license)| license.clone());
+        }
+    }
+
+    let mut updated_repo = Repository {
+        id: repository.id,
+        name: repository.name.clone(),
+        full_name: repository.full_name.clone(),
+        private: repository.private,
+        url: repository.html_url.clone(),
+        archived: repository.archived,
+        fork: repository.fork,
+        visibility: repository.visibility.clone(),
+        pushed_at: repository.pushed_at,
+        dependabots,
+        low_alerts: 0,
+        medium_alerts: 0,
+        high_alerts: 0,
+        critical_alerts: 0,
+        total_active_alerts: 0,
+        alerts_status: AlertsStatus::Enabled,
+        watched: false,
+        has_security_policy,
+        last_fetched_at: Some(Utc::now()),
+        topics: repository.topics.clone(),
+        alerts_etag: None,
+        pull_requests: Vec::new(),
+        code_scanning_alerts: Vec::new(),
+        secret_scanning_alerts: Vec::new(),
+        provider: ProviderKind::GitHub,
+    };
+    updated_repo.update_alert_counts();
+    if updated_repo.total_active_alerts > 0 {
+        updated_repo.pull_requests =
+            fetch_dependabot_pull_requests(token, &repository.full_name, client).await;
+    }
+
+    updated_repo
+}
+
+#[derive(Debug, Deserialize)]
+struct SbomResponse {
+    sbom: Sbom,
+}
+
+#[derive(Debug, Deserialize)]
+struct Sbom {
+    packages: Vec<SbomPackage>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SbomPackage {
+    name: String,
+    license_concluded: Option<String>,
+    license_declared: Option<String>,
+}
+
+/// The `(package name, license)` pairs from the repository's dependency graph SBOM. Package
+/// names are prefixed with their ecosystem, e.g. `npm:lodash`.
+async fn fetch_dependency_licenses(
+    token: &str,
+    repository: &GitHubRepository,
+    client: &dyn GithubClient,
+) -> Vec<(String, String)> {
+    let url = format!("/repos/{}/dependency-graph/sbom", repository.full_name);
+    let sbom = match github_headers(token) {
+        Ok(headers) => match client.get(&url).headers(headers).send().await {
+            Ok(response) if response.status().is_success() => {
+                response.json::<SbomResponse>().await.ok()
+            }
+            _ => None,
+        },
+        Err(_) => None,
+    };
+
+    let Some(sbom) = sbom else {
+        let sbom_unavailable = format!("Dependency graph unavailable for {}", repository.name);
+        trace_dbg!(level: tracing::Level::WARN, sbom_unavailable);
+        return Vec::new();
+    };
+
+    sbom.sbom
+        .packages
+        .into_iter()
+        .filter_map(|package| {
+            let license = package
+                .license_concluded
+                .or(package.license_declared)
+                .filter(|license| license != "NOASSERTION")?;
+            Some((package.name, license))
+        })
+        .collect()
+}
+
+/// Check the locations GitHub recognizes for a security policy. Returns `None` if the token
+/// isn't allowed to read the repository's contents.
+async fn fetch_has_security_policy(
+    token: &str,
+    repository: &GitHubRepository,
+    client: &dyn GithubClient,
+) -> Option<bool> {
+    let headers = github_headers(token).ok()?;
+    let mut forbidden = false;
+
+    for path in ["SECURITY.md", ".github/SECURITY.md", "docs/SECURITY.md"] {
+        let url = format!("/repos/{}/contents/{}", repository.full_name, path);
+        let response = client
+            .get(&url)
+            .headers(headers.clone())
+            .send()
+            .await
+            .ok()?;
+
+        if response.status().is_success() {
+            return Some(true);
+        }
+        if response.status() == reqwest::StatusCode::FORBIDDEN {
+            forbidden = true;
+        }
+    }
+
+    if forbidden {
+        None
+    } else {
+        Some(false)
+    }
+}
+
+/// Turn on Dependabot alerts for a repository via `PUT /repos/{owner}/{repo}/vulnerability-alerts`,
+/// along with Dependabot security updates if `security_updates` is set.
+pub async fn enable_dependabot_alerts(
+    token: &str,
+    full_name: &str,
+    security_updates: bool,
+    client: &dyn GithubClient,
+) -> Result<(), DependabotTrackerError> {
+    let mut urls = vec![format!("/repos/{}/vulnerability-alerts", full_name)];
+    if security_updates {
+        urls.push(format!("/repos/{}/automated-security-fixes", full_name));
+    }
+
+    for url in urls {
+        client
+            .put(&url)
+            .headers(github_headers(token)?)
+            .send()
+            .await
+            .map_err(DependabotTrackerError::from)
+            .and_then(check_response)?;
+    }
+
+    Ok(())
+}
+
+/// Dismiss an alert via `PATCH /repos/{owner}/{repo}/dependabot/alerts/{number}`, returning
+/// the updated alert.
+pub async fn dismiss_alert(
+    token: &str,
+    full_name: &str,
+    number: u32,
+    reason: DismissReason,
+    comment: &str,
+    client: &dyn GithubClient,
+) -> Result<Dependabot, DependabotTrackerError> {
+    let url = format!("/repos/{}/dependabot/alerts/{}", full_name, number);
+    let mut body = serde_json::json!({
+        "state": "dismissed",
+        "dismissed_reason": reason,
+    });
+    if !comment.trim().is_empty() {
+        body["dismissed_comment"] = serde_json::Value::from(comment.trim());
+    }
+
+    let response = client
+        .patch(&url)
+        .headers(github_headers(token)?)
+        .json(&body)
+        .send()
+        .await
+        .map_err(DependabotTrackerError::from)
+        .and_then(check_response)?;
+
+    let github_dependabot: GithubDependabot = response.json().await?;
+
+    Ok(Dependabot::from(github_dependabot))
+}
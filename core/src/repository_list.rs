@@ -0,0 +1,562 @@
+use std::cmp::Ordering;
+use std::collections::BTreeSet;
+
+use chrono::{Duration, Utc};
+use serde::Deserialize;
+
+use crate::dependabot::{
+    is_copyleft, AffectedRepository, Dependabot, DependabotSeverity, DependabotState,
+    DistinctAlert, VulnerableDependency,
+};
+use crate::fetch_config::FetchConfig;
+use crate::repository::{AlertsStatus, Repository};
+use crate::repository_tree::RepositorySection;
+use crate::risk::RiskConfig;
+use crate::search::is_fuzzy_match;
+
+/// The column the repository list is sorted by, cycled through in column order with `s`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SortMode {
+    #[default]
+    Name,
+    Visibility,
+    CriticalAlerts,
+    HighAlerts,
+    MediumAlerts,
+    LowAlerts,
+    TotalAlerts,
+    LastUpdatedAlert,
+}
+
+impl SortMode {
+    pub fn next(self) -> SortMode {
+        match self {
+            SortMode::Name => SortMode::Visibility,
+            SortMode::Visibility => SortMode::CriticalAlerts,
+            SortMode::CriticalAlerts => SortMode::HighAlerts,
+            SortMode::HighAlerts => SortMode::MediumAlerts,
+            SortMode::MediumAlerts => SortMode::LowAlerts,
+            SortMode::LowAlerts => SortMode::TotalAlerts,
+            SortMode::TotalAlerts => SortMode::LastUpdatedAlert,
+            SortMode::LastUpdatedAlert => SortMode::Name,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            SortMode::Name => "name",
+            SortMode::Visibility => "visibility",
+            SortMode::CriticalAlerts => "critical alerts",
+            SortMode::HighAlerts => "high alerts",
+            SortMode::MediumAlerts => "medium alerts",
+            SortMode::LowAlerts => "low alerts",
+            SortMode::TotalAlerts => "alerts",
+            SortMode::LastUpdatedAlert => "last updated alert",
+        }
+    }
+
+    fn compare(self, a: &Repository, b: &Repository) -> Ordering {
+        // the alert counts put the most alerts first, breaking ties by the total
+        let by_count = |count: fn(&Repository) -> usize| {
+            count(b)
+                .cmp(&count(a))
+                .then_with(|| b.total_active_alerts.cmp(&a.total_active_alerts))
+        };
+        match self {
+            SortMode::Name => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
+            SortMode::Visibility => a
+                .visibility_label()
+                .cmp(b.visibility_label())
+                .then_with(|| a.name.to_lowercase().cmp(&b.name.to_lowercase())),
+            SortMode::CriticalAlerts => by_count(|repo| repo.critical_alerts),
+            SortMode::HighAlerts => by_count(|repo| repo.high_alerts),
+            SortMode::MediumAlerts => by_count(|repo| repo.medium_alerts),
+            SortMode::LowAlerts => by_count(|repo| repo.low_alerts),
+            SortMode::TotalAlerts => b.total_active_alerts.cmp(&a.total_active_alerts),
+            // repositories without any alerts go last
+            SortMode::LastUpdatedAlert => b.last_alert_update().cmp(&a.last_alert_update()),
+        }
+    }
+}
+
+/// The order the list keeps its repositories in, for the given sort mode.
+fn list_order(sort_mode: SortMode, a: &Repository, b: &Repository) -> Ordering {
+    a.watched
+        .cmp(&b.watched)
+        .then_with(|| {
+            if a.watched {
+                Ordering::Equal
+            } else {
+                a.owner().to_lowercase().cmp(&b.owner().to_lowercase())
+            }
+        })
+        .then_with(|| sort_mode.compare(a, b))
+}
+
+#[derive(Clone)]
+pub struct RepositoryList {
+    pub repos: Vec<Repository>,
+    selected: Option<usize>,
+    // how far the list is scrolled, kept between frames
+    offset: usize,
+    sort_mode: SortMode,
+    // a section header highlighted in place of the selected repository, which is kept at the
+    // section's first repository
+    highlighted_section: Option<RepositorySection>,
+}
+
+impl RepositoryList {
+    pub fn with_respositories(repos: Vec<Repository>) -> RepositoryList {
+        let mut repository_list = RepositoryList {
+            selected: (!repos.is_empty()).then_some(0),
+            repos,
+            offset: 0,
+            sort_mode: SortMode::default(),
+            highlighted_section: None,
+        };
+        repository_list.sort();
+
+        repository_list
+    }
+
+    pub fn sort_mode(&self) -> SortMode {
+        self.sort_mode
+    }
+
+    pub fn set_sort_mode(&mut self, sort_mode: SortMode) {
+        self.sort_mode = sort_mode;
+        self.sort();
+    }
+
+    /// Order the repositories by the sort mode, keeping the watched repositories after the
+    /// user's own and the user's own repositories together by owner, so the list's section
+    /// headers stay in one place.
+    fn sort(&mut self) {
+        let sort_mode = self.sort_mode;
+        self.repos.sort_by(|a, b| list_order(sort_mode, a, b));
+    }
+
+    /// Add a freshly fetched repository where it sorts, or replace the one with the same id,
+    /// keeping the same repository selected.
+    pub fn upsert(&mut self, repo: Repository) {
+        let selected_id = self.get_selected_repository().map(|selected| selected.id);
+        if let Some(index) = self.position(repo.id) {
+            self.repos.remove(index);
+        }
+        let index = self
+            .repos
+            .partition_point(|existing| list_order(self.sort_mode, existing, &repo).is_le());
+        self.repos.insert(index, repo);
+
+        let selected_index = selected_id
+            .and_then(|id| self.position(id))
+            .or((!self.repos.is_empty()).then_some(0));
+        self.selected = selected_index;
+    }
+
+    /// The index of the repository with the given id, if it's in the list.
+    pub fn position(&self, id: u32) -> Option<usize> {
+        self.repos.iter().position(|repo| repo.id == id)
+    }
+
+    pub fn select(&mut self, index: usize) {
+        if index < self.repos.len() {
+            self.selected = Some(index);
+            self.highlighted_section = None;
+        }
+    }
+
+    /// Highlight a section's header, selecting the section's first repository, at `index`,
+    /// behind it.
+    pub fn highlight_section(&mut self, index: usize, section: RepositorySection) {
+        if index < self.repos.len() {
+            self.selected = Some(index);
+            self.highlighted_section = Some(section);
+        }
+    }
+
+    pub fn highlighted_section(&self) -> Option<&RepositorySection> {
+        self.highlighted_section.as_ref()
+    }
+
+    /// The index of the repository with the highest risk score, if there are any repositories.
+    pub fn highest_risk_index(&self, risk: &RiskConfig) -> Option<usize> {
+        self.repos
+            .iter()
+            .enumerate()
+            .map(|(index, repo)| (index, risk.score(repo)))
+            .max_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(index, _)| index)
+    }
+
+    pub fn repository_names_with_status(&self, status: AlertsStatus) -> Vec<&str> {
+        self.repos
+            .iter()
+            .filter(|repo| repo.alerts_status == status)
+            .map(|repo| repo.name.as_str())
+            .collect()
+    }
+
+    /// How many of the user's repositories had their alerts read and have none open. Ones whose
+    /// alerts couldn't be read are left out, as having no alerts there doesn't mean they're clean.
+    pub fn without_open_alerts_count(&self) -> usize {
+        self.owned()
+            .filter(|repo| repo.alerts_status == AlertsStatus::Enabled)
+            .filter(|repo| repo.total_active_alerts == 0)
+            .count()
+    }
+
+    /// How many of the user's repositories' alerts couldn't be read during the last fetch.
+    pub fn unchecked_count(&self) -> usize {
+        self.owned()
+            .filter(|repo| repo.alerts_status != AlertsStatus::Enabled)
+            .count()
+    }
+
+    /// The user's own repositories, leaving out any from the watchlist.
+    pub fn owned(&self) -> impl Iterator<Item = &Repository> {
+        self.repos.iter().filter(|repo| !repo.watched)
+    }
+
+    /// How many alerts across the user's repositories GitHub auto-dismissed within `window`.
+    pub fn recently_auto_dismissed_count(&self, window: Duration) -> usize {
+        let now = Utc::now();
+        self.owned()
+            .flat_map(|repo| repo.dependabots.iter())
+            .filter(|dependabot| dependabot.auto_dismissed_within(window, now))
+            .count()
+    }
+
+    /// How many of the user's repositories are known to be missing a security policy.
+    pub fn missing_security_policy_count(&self) -> usize {
+        self.owned()
+            .filter(|repo| repo.has_security_policy == Some(false))
+            .count()
+    }
+
+    /// Collapse the open alerts across the user's repositories into distinct problems, most
+    /// widespread first.
+    pub fn distinct_alerts(&self) -> Vec<DistinctAlert> {
+        let mut distinct_alerts = Vec::<DistinctAlert>::new();
+
+        for repo in self.owned() {
+            let open_alerts = repo
+                .dependabots
+                .iter()
+                .filter(|dependabot| dependabot.state == DependabotState::Open);
+            for dependabot in open_alerts {
+                match distinct_alerts
+                    .iter_mut()
+                    .find(|distinct_alert| distinct_alert.matches(dependabot))
+                {
+                    Some(distinct_alert) => {
+                        distinct_alert.severity = distinct_alert.severity.max(dependabot.severity);
+                        if !distinct_alert.repositories.contains(&repo.name) {
+                            distinct_alert.repositories.push(repo.name.clone());
+                        }
+                    }
+                    None => distinct_alerts.push(DistinctAlert {
+                        dependency_name: dependabot.dependency_name.clone(),
+                        dependency_ecosystem: dependabot.dependency_ecosystem.clone(),
+                        vulnerable_version_range: dependabot.vulnerable_version_range.clone(),
+                        ghsa_id: dependabot.ghsa_id.clone(),
+                        severity: dependabot.severity,
+                        repositories: vec![repo.name.clone()],
+                    }),
+                }
+            }
+        }
+
+        distinct_alerts.sort_by(|a, b| {
+            b.repositories
+                .len()
+                .cmp(&a.repositories.len())
+                .then_with(|| b.severity.cmp(&a.severity))
+        });
+
+        distinct_alerts
+    }
+
+    /// Group the open alerts across the user's repositories by package, so a package can be
+    /// upgraded everywhere at once. The packages affecting the most repositories come first.
+    pub fn vulnerable_dependencies(&self) -> Vec<VulnerableDependency> {
+        let mut dependencies = Vec::<VulnerableDependency>::new();
+
+        for repo in self.owned() {
+            let open_alerts = repo
+                .dependabots
+                .iter()
+                .filter(|dependabot| dependabot.state == DependabotState::Open);
+            for dependabot in open_alerts {
+                let dependency = match dependencies.iter().position(|dependency| {
+                    dependency.dependency_name == dependabot.dependency_name
+                        && dependency.dependency_ecosystem == dependabot.dependency_ecosystem
+                }) {
+                    Some(index) => &mut dependencies[index],
+                    None => {
+                        dependencies.push(VulnerableDependency {
+                            dependency_name: dependabot.dependency_name.clone(),
+                            dependency_ecosystem: dependabot.dependency_ecosystem.clone(),
+                            severity: dependabot.severity,
+                            repositories: Vec::new(),
+                        });
+                        dependencies.last_mut().unwrap()
+                    }
+                };
+                dependency.severity = dependency.severity.max(dependabot.severity);
+
+                match dependency
+                    .repositories
+                    .iter_mut()
+                    .find(|affected| affected.id == repo.id)
+                {
+                    Some(affected) => {
+                        affected.severity = affected.severity.max(dependabot.severity);
+                        affected.alert_count += 1;
+                    }
+                    None => dependency.repositories.push(AffectedRepository {
+                        id: repo.id,
+                        name: repo.name.clone(),
+                        severity: dependabot.severity,
+                        alert_count: 1,
+                    }),
+                }
+            }
+        }
+
+        for dependency in dependencies.iter_mut() {
+            dependency.repositories.sort_by(|a, b| {
+                b.severity
+                    .cmp(&a.severity)
+                    .then_with(|| a.name.cmp(&b.name))
+            });
+        }
+        dependencies.sort_by(|a, b| {
+            b.repositories
+                .len()
+                .cmp(&a.repositories.len())
+                .then_with(|| b.severity.cmp(&a.severity))
+                .then_with(|| a.dependency_name.cmp(&b.dependency_name))
+        });
+
+        dependencies
+    }
+
+    /// The number of open alerts across the user's repositories for each package ecosystem
+    /// (npm, pip, cargo, ...), most alerts first.
+    pub fn open_alerts_by_ecosystem(&self) -> Vec<(String, u64)> {
+        let mut ecosystems = Vec::<(String, u64)>::new();
+        let open_alerts = self.owned().flat_map(|repo| {
+            repo.dependabots
+                .iter()
+                .filter(|dependabot| dependabot.state == DependabotState::Open)
+        });
+        for dependabot in open_alerts {
+            match ecosystems
+                .iter_mut()
+                .find(|(ecosystem, _)| *ecosystem == dependabot.dependency_ecosystem)
+            {
+                Some((_, count)) => *count += 1,
+                None => ecosystems.push((dependabot.dependency_ecosystem.clone(), 1)),
+            }
+        }
+        ecosystems.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+
+        ecosystems
+    }
+
+    /// The open alerts in this list that weren't open in `previous`, along with their
+    /// repositories.
+    pub fn new_open_alerts<'a>(
+        &'a self,
+        previous: &'a [Repository],
+    ) -> Vec<(&'a Repository, &'a Dependabot)> {
+        self.repos
+            .iter()
+            .flat_map(|repo| {
+                let previous_repo = previous.iter().find(|previous| previous.id == repo.id);
+                repo.dependabots
+                    .iter()
+                    .filter(|dependabot| dependabot.state == DependabotState::Open)
+                    .filter(move |dependabot| {
+                        !previous_repo.is_some_and(|previous_repo| {
+                            previous_repo.dependabots.iter().any(|previous_dependabot| {
+                                previous_dependabot.number == dependabot.number
+                                    && previous_dependabot.state == DependabotState::Open
+                            })
+                        })
+                    })
+                    .map(move |dependabot| (repo, dependabot))
+            })
+            .collect()
+    }
+
+    /// The alerts in this list that were open in `previous` but have since been fixed or
+    /// dismissed, along with their repositories.
+    pub fn resolved_alerts<'a>(
+        &'a self,
+        previous: &'a [Repository],
+    ) -> Vec<(&'a Repository, &'a Dependabot)> {
+        self.repos
+            .iter()
+            .filter_map(|repo| {
+                let previous_repo = previous.iter().find(|previous| previous.id == repo.id)?;
+                Some(
+                    repo.dependabots
+                        .iter()
+                        .filter(|dependabot| dependabot.state != DependabotState::Open)
+                        .filter(move |dependabot| {
+                            previous_repo.dependabots.iter().any(|previous_dependabot| {
+                                previous_dependabot.number == dependabot.number
+                                    && previous_dependabot.state == DependabotState::Open
+                            })
+                        })
+                        .map(move |dependabot| (repo, dependabot)),
+                )
+            })
+            .flatten()
+            .collect()
+    }
+
+    /// How many open alerts of `severity` are in this list but weren't open in `previous`.
+    pub fn count_new_open_alerts(
+        &self,
+        previous: &[Repository],
+        severity: DependabotSeverity,
+    ) -> usize {
+        self.new_open_alerts(previous)
+            .iter()
+            .filter(|(_, dependabot)| dependabot.severity == severity)
+            .count()
+    }
+
+    /// The open alerts on copyleft-licensed packages, paired with their repository's name.
+    pub fn copyleft_vulnerable_dependencies(&self) -> Vec<(&str, &Dependabot)> {
+        self.owned()
+            .flat_map(|repo| {
+                repo.dependabots
+                    .iter()
+                    .filter(|dependabot| {
+                        dependabot.state == DependabotState::Open
+                            && dependabot.license.as_deref().is_some_and(is_copyleft)
+                    })
+                    .map(|dependabot| (repo.name.as_str(), dependabot))
+            })
+            .collect()
+    }
+
+    /// The index of the first watched repository, which starts the watched section of the list.
+    pub fn first_watched_index(&self) -> Option<usize> {
+        self.repos.iter().position(|repo| repo.watched)
+    }
+
+    /// A copy of the list with only the repositories in a group, matched by name or
+    /// `owner/repo`, keeping the current selection.
+    pub fn in_group(&self, members: &[String]) -> RepositoryList {
+        RepositoryList {
+            offset: self.offset,
+            repos: self
+                .repos
+                .iter()
+                .filter(|repo| members.contains(&repo.name) || members.contains(&repo.full_name))
+                .cloned()
+                .collect(),
+            selected: self.selected,
+            sort_mode: self.sort_mode,
+            highlighted_section: self.highlighted_section.clone(),
+        }
+    }
+
+    /// A copy of the list with only the repositories whose names fuzzily match `query`, keeping
+    /// the current selection.
+    pub fn matching_name(&self, query: &str) -> RepositoryList {
+        RepositoryList {
+            offset: self.offset,
+            repos: self
+                .repos
+                .iter()
+                .filter(|repo| is_fuzzy_match(&repo.name, query))
+                .cloned()
+                .collect(),
+            selected: self.selected,
+            sort_mode: self.sort_mode,
+            highlighted_section: self.highlighted_section.clone(),
+        }
+    }
+
+    /// A copy of the list with only the repositories tagged with a GitHub topic, keeping the
+    /// current selection.
+    /// A copy of the list without the repositories the config excludes, and without archived
+    /// repositories if `hide_archived` is set, keeping the current selection.
+    pub fn without_excluded(&self, config: &FetchConfig, hide_archived: bool) -> RepositoryList {
+        RepositoryList {
+            offset: self.offset,
+            repos: self
+                .repos
+                .iter()
+                .filter(|repo| {
+                    (!hide_archived || !repo.archived)
+                        && !config.is_excluded(&repo.full_name, repo.archived, repo.fork)
+                })
+                .cloned()
+                .collect(),
+            selected: self.selected,
+            sort_mode: self.sort_mode,
+            highlighted_section: self.highlighted_section.clone(),
+        }
+    }
+
+    pub fn with_topic(&self, topic: &str) -> RepositoryList {
+        RepositoryList {
+            offset: self.offset,
+            repos: self
+                .repos
+                .iter()
+                .filter(|repo| repo.topics.iter().any(|repo_topic| repo_topic == topic))
+                .cloned()
+                .collect(),
+            selected: self.selected,
+            sort_mode: self.sort_mode,
+            highlighted_section: self.highlighted_section.clone(),
+        }
+    }
+
+    /// Every topic used by at least one repository, in alphabetical order.
+    pub fn topics(&self) -> BTreeSet<&str> {
+        self.repos
+            .iter()
+            .flat_map(|repo| repo.topics.iter().map(String::as_str))
+            .collect()
+    }
+
+    /// A copy of the list with only the alerts that have a patched version available, keeping
+    /// the current selection.
+    pub fn fixable_only(&self) -> RepositoryList {
+        RepositoryList {
+            offset: self.offset,
+            repos: self.repos.iter().map(Repository::fixable_only).collect(),
+            selected: self.selected,
+            sort_mode: self.sort_mode,
+            highlighted_section: self.highlighted_section.clone(),
+        }
+    }
+
+    /// The index of the selected repository.
+    pub fn selected(&self) -> Option<usize> {
+        self.selected
+    }
+
+    pub fn get_selected_repository(&self) -> Option<&Repository> {
+        self.selected.and_then(|index| self.repos.get(index))
+    }
+
+    /// How many rows the list was scrolled by when it was last drawn.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    pub fn set_offset(&mut self, offset: usize) {
+        self.offset = offset;
+    }
+}
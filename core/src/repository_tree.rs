@@ -0,0 +1,115 @@
+use std::collections::BTreeSet;
+
+use crate::repository::Repository;
+use crate::repository_list::RepositoryList;
+
+/// A section of the repository list: the user's repositories with one owner, or the watched
+/// repositories.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum RepositorySection {
+    Owner(String),
+    Watched,
+}
+
+impl RepositorySection {
+    pub fn of(repo: &Repository) -> RepositorySection {
+        if repo.watched {
+            RepositorySection::Watched
+        } else {
+            RepositorySection::Owner(repo.owner().to_string())
+        }
+    }
+
+    pub fn title(&self) -> &str {
+        match self {
+            RepositorySection::Owner(owner) => owner,
+            RepositorySection::Watched => "Watched Repositories",
+        }
+    }
+}
+
+/// The header over a section of the repository list, with the section's open alert subtotals.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SectionHeader {
+    pub section: RepositorySection,
+    pub collapsed: bool,
+    pub repository_count: usize,
+    pub critical_alerts: usize,
+    pub high_alerts: usize,
+    pub medium_alerts: usize,
+    pub low_alerts: usize,
+    pub total_active_alerts: usize,
+}
+
+impl SectionHeader {
+    fn new(section: RepositorySection, repositories: &RepositoryList, collapsed: bool) -> Self {
+        let members: Vec<&Repository> = repositories
+            .repos
+            .iter()
+            .filter(|repo| RepositorySection::of(repo) == section)
+            .collect();
+        let total = |count: fn(&Repository) -> usize| members.iter().map(|repo| count(repo)).sum();
+
+        SectionHeader {
+            collapsed,
+            repository_count: members.len(),
+            critical_alerts: total(|repo| repo.critical_alerts),
+            high_alerts: total(|repo| repo.high_alerts),
+            medium_alerts: total(|repo| repo.medium_alerts),
+            low_alerts: total(|repo| repo.low_alerts),
+            total_active_alerts: total(|repo| repo.total_active_alerts),
+            section,
+        }
+    }
+}
+
+/// A row of the repository list as it's drawn.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TreeRow {
+    Section(SectionHeader),
+    // the index of the repository in the list
+    Repository(usize),
+}
+
+/// The rows of the repository list: a header before the watched repositories, and before each
+/// owner's repositories once more than one owner is in the list, followed by the repositories
+/// of the sections that aren't collapsed. A section without a header is never collapsed.
+pub fn tree_rows(
+    repositories: &RepositoryList,
+    collapsed: &BTreeSet<RepositorySection>,
+) -> Vec<TreeRow> {
+    let first_watched_index = repositories.first_watched_index();
+    // only split the user's repositories by owner once organizations are in the mix
+    let group_by_owner = repositories
+        .owned()
+        .any(|repo| repo.owner() != repositories.repos[0].owner());
+
+    let mut rows = Vec::new();
+    let mut section_collapsed = false;
+    for (index, repo) in repositories.repos.iter().enumerate() {
+        let starts_section = first_watched_index == Some(index)
+            || (group_by_owner
+                && !repo.watched
+                && (index == 0 || repositories.repos[index - 1].owner() != repo.owner()));
+        if starts_section {
+            let section = RepositorySection::of(repo);
+            section_collapsed = collapsed.contains(&section);
+            rows.push(TreeRow::Section(SectionHeader::new(
+                section,
+                repositories,
+                section_collapsed,
+            )));
+        }
+        if !section_collapsed {
+            rows.push(TreeRow::Repository(index));
+        }
+    }
+
+    rows
+}
+
+/// The index of the row with the header for `section`, if it has one.
+pub fn section_row(rows: &[TreeRow], section: &RepositorySection) -> Option<usize> {
+    rows.iter()
+        .position(|row| matches!(row, TreeRow::Section(header) if header.section == *section))
+}
@@ -0,0 +1,92 @@
+use std::future::Future;
+use std::time::Duration;
+
+use reqwest::Client;
+use serde::Deserialize;
+
+use crate::error::DependabotTrackerError;
+use crate::trace_dbg;
+
+/// The config's `[retry]` section: how many times a repository's alerts are requested before
+/// it's reported as failed, how long to wait between tries, and how long a request can stall
+/// before it counts as failed.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct RetryConfig {
+    // how many times to try, including the first
+    pub attempts: u32,
+    // how long to wait before the first retry, doubling after each one
+    pub initial_backoff_ms: u64,
+    // the longest to wait between tries
+    pub max_backoff_ms: u64,
+    // how long to wait for a connection to GitHub or GitLab
+    pub connect_timeout_secs: u64,
+    // how long a response can go without sending anything
+    pub read_timeout_secs: u64,
+}
+
+impl Default for RetryConfig {
+    fn default() -> Self {
+        RetryConfig {
+            attempts: 3,
+            initial_backoff_ms: 500,
+            max_backoff_ms: 8000,
+            connect_timeout_secs: 10,
+            read_timeout_secs: 30,
+        }
+    }
+}
+
+impl RetryConfig {
+    /// How long to wait after the `retry`th failed try, starting from 1.
+    fn backoff(&self, retry: u32) -> Duration {
+        let backoff_ms = self
+            .initial_backoff_ms
+            .saturating_mul(2u64.saturating_pow(retry.saturating_sub(1)))
+            .min(self.max_backoff_ms);
+
+        Duration::from_millis(backoff_ms)
+    }
+
+    /// An HTTP client that gives up on stalled requests, so a hung network fails the request
+    /// (and it's tried again) rather than leaving the fetch waiting forever.
+    pub fn client(&self) -> Client {
+        Client::builder()
+            .connect_timeout(Duration::from_secs(self.connect_timeout_secs))
+            .read_timeout(Duration::from_secs(self.read_timeout_secs))
+            .build()
+            .unwrap_or_default()
+    }
+}
+
+/// Run `fetch`, trying it again with exponential backoff for as long as it fails with a
+/// transient error and there are attempts left.
+pub async fn with_retry<T, F, Fut>(
+    config: &RetryConfig,
+    label: &str,
+    mut fetch: F,
+) -> Result<T, DependabotTrackerError>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, DependabotTrackerError>>,
+{
+    let mut attempt = 1;
+    loop {
+        match fetch().await {
+            Err(e) if attempt < config.attempts && e.is_transient() => {
+                let backoff = config.backoff(attempt);
+                let retrying = format!(
+                    "Retrying {} in {}ms after attempt {} failed: {}",
+                    label,
+                    backoff.as_millis(),
+                    attempt,
+                    e
+                );
+                trace_dbg!(level: tracing::Level::WARN, retrying);
+                tokio::time::sleep(backoff).await;
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
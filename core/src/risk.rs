@@ -0,0 +1,86 @@
+use serde::Deserialize;
+
+use crate::dependabot::{DependabotSeverity, DependabotState};
+use crate::repository::Repository;
+
+/// How much each open alert adds to a repository's risk score, set in the config's `[risk]`
+/// section.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct RiskConfig {
+    pub critical: f64,
+    pub high: f64,
+    pub medium: f64,
+    pub low: f64,
+    // weigh each alert by its advisory's CVSS score instead, falling back to its severity's
+    // weight for alerts without one
+    pub use_cvss: bool,
+}
+
+impl Default for RiskConfig {
+    fn default() -> Self {
+        RiskConfig {
+            critical: 10.0,
+            high: 5.0,
+            medium: 2.0,
+            low: 1.0,
+            use_cvss: false,
+        }
+    }
+}
+
+impl RiskConfig {
+    pub fn weight(&self, severity: DependabotSeverity) -> f64 {
+        match severity {
+            DependabotSeverity::Low => self.low,
+            DependabotSeverity::Medium => self.medium,
+            DependabotSeverity::High => self.high,
+            DependabotSeverity::Critical => self.critical,
+        }
+    }
+
+    /// The weighted sum of a repository's open alerts, used to rank how risky it is.
+    pub fn score(&self, repo: &Repository) -> f64 {
+        if !self.use_cvss {
+            return repo.critical_alerts as f64 * self.critical
+                + repo.high_alerts as f64 * self.high
+                + repo.medium_alerts as f64 * self.medium
+                + repo.low_alerts as f64 * self.low;
+        }
+
+        repo.dependabots
+            .iter()
+            .filter(|dependabot| dependabot.state == DependabotState::Open)
+            .map(|dependabot| {
+                dependabot
+                    .cvss_score
+                    .unwrap_or_else(|| self.weight(dependabot.severity))
+            })
+            .sum()
+    }
+
+    /// Up to `count` of the repositories with open alerts, riskiest first, with their scores.
+    pub fn riskiest<'a>(
+        &self,
+        repositories: impl Iterator<Item = &'a Repository>,
+        count: usize,
+    ) -> Vec<(&'a Repository, f64)> {
+        let mut riskiest: Vec<(&Repository, f64)> = repositories
+            .filter(|repo| repo.total_active_alerts > 0)
+            .map(|repo| (repo, self.score(repo)))
+            .collect();
+        riskiest.sort_by(|(_, a), (_, b)| b.total_cmp(a));
+        riskiest.truncate(count);
+
+        riskiest
+    }
+}
+
+/// A risk score without a trailing `.0`, e.g. `42` or `17.3`.
+pub fn format_risk_score(score: f64) -> String {
+    if score.fract() == 0.0 {
+        format!("{:.0}", score)
+    } else {
+        format!("{:.1}", score)
+    }
+}
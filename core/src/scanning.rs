@@ -0,0 +1,134 @@
+use serde::{Deserialize, Serialize};
+
+use crate::dependabot::DependabotSeverity;
+use crate::security_alert::SecurityAlert;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CodeScanningState {
+    Open,
+    Dismissed,
+    Fixed,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SecretScanningState {
+    Open,
+    Resolved,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct GithubCodeScanningAlert {
+    pub number: u32,
+    pub state: CodeScanningState,
+    pub html_url: String,
+    pub created_at: String,
+    pub rule: CodeScanningRule,
+    pub tool: CodeScanningTool,
+    pub most_recent_instance: Option<CodeScanningInstance>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CodeScanningRule {
+    pub id: Option<String>,
+    pub description: String,
+    // only set for rules that find security issues, as opposed to code quality ones
+    pub security_severity_level: Option<DependabotSeverity>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CodeScanningTool {
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CodeScanningInstance {
+    pub location: Option<CodeScanningLocation>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CodeScanningLocation {
+    pub path: Option<String>,
+    pub start_line: Option<u32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CodeScanningAlert {
+    pub number: u32,
+    pub state: CodeScanningState,
+    pub severity: Option<DependabotSeverity>,
+    pub html_url: String,
+    pub created_at: String,
+    pub rule_id: Option<String>,
+    pub description: String,
+    pub tool: String,
+    // where the most recent instance was found, e.g. `src/main.rs:42`
+    pub location: Option<String>,
+}
+
+impl From<GithubCodeScanningAlert> for CodeScanningAlert {
+    fn from(github_alert: GithubCodeScanningAlert) -> Self {
+        CodeScanningAlert {
+            number: github_alert.number,
+            state: github_alert.state,
+            severity: github_alert.rule.security_severity_level,
+            html_url: github_alert.html_url,
+            created_at: github_alert.created_at,
+            rule_id: github_alert.rule.id,
+            description: github_alert.rule.description,
+            tool: github_alert.tool.name,
+            location: github_alert
+                .most_recent_instance
+                .and_then(|instance| instance.location)
+                .and_then(|location| {
+                    let path = location.path?;
+                    Some(match location.start_line {
+                        Some(start_line) => format!("{}:{}", path, start_line),
+                        None => path,
+                    })
+                }),
+        }
+    }
+}
+
+impl SecurityAlert for CodeScanningAlert {
+    fn severity(&self) -> Option<DependabotSeverity> {
+        self.severity
+    }
+
+    fn is_open(&self) -> bool {
+        self.state == CodeScanningState::Open
+    }
+
+    fn html_url(&self) -> &str {
+        &self.html_url
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SecretScanningAlert {
+    pub number: u32,
+    pub state: SecretScanningState,
+    pub html_url: String,
+    pub created_at: String,
+    pub secret_type_display_name: Option<String>,
+    // why the alert was resolved, e.g. `revoked` or `false_positive`
+    pub resolution: Option<String>,
+    #[serde(default)]
+    pub push_protection_bypassed: Option<bool>,
+}
+
+impl SecurityAlert for SecretScanningAlert {
+    fn severity(&self) -> Option<DependabotSeverity> {
+        None
+    }
+
+    fn is_open(&self) -> bool {
+        self.state == SecretScanningState::Open
+    }
+
+    fn html_url(&self) -> &str {
+        &self.html_url
+    }
+}
@@ -0,0 +1,30 @@
+#[derive(Default)]
+pub struct RepositorySearch {
+    pub query: String,
+    // whether keys are being typed into the query
+    pub editing: bool,
+}
+
+impl RepositorySearch {
+    pub fn push(&mut self, c: char) {
+        self.query.push(c);
+    }
+
+    pub fn pop(&mut self) {
+        self.query.pop();
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.editing || !self.query.is_empty()
+    }
+}
+
+/// Whether every character of `query` appears in `name` in order, ignoring case, so `dbt`
+/// matches `dependabot-tracker`.
+pub fn is_fuzzy_match(name: &str, query: &str) -> bool {
+    let mut name_chars = name.chars().flat_map(char::to_lowercase);
+    query
+        .chars()
+        .flat_map(char::to_lowercase)
+        .all(|query_char| name_chars.any(|name_char| name_char == query_char))
+}
@@ -0,0 +1,40 @@
+use crate::dependabot::{Dependabot, DependabotSeverity, DependabotState};
+
+/// What the Project view needs from each kind of alert GitHub reports on a repository:
+/// Dependabot, code scanning, and secret scanning.
+pub trait SecurityAlert {
+    /// The alert's severity, or `None` for alerts GitHub doesn't rate, like leaked secrets.
+    fn severity(&self) -> Option<DependabotSeverity>;
+
+    fn is_open(&self) -> bool;
+
+    fn html_url(&self) -> &str;
+}
+
+impl SecurityAlert for Dependabot {
+    fn severity(&self) -> Option<DependabotSeverity> {
+        Some(self.severity)
+    }
+
+    fn is_open(&self) -> bool {
+        self.state == DependabotState::Open
+    }
+
+    fn html_url(&self) -> &str {
+        &self.html_url
+    }
+}
+
+pub fn open_alert_count<A: SecurityAlert>(alerts: &[A]) -> usize {
+    alerts.iter().filter(|alert| alert.is_open()).count()
+}
+
+pub fn open_alert_count_with_severity<A: SecurityAlert>(
+    alerts: &[A],
+    severity: DependabotSeverity,
+) -> usize {
+    alerts
+        .iter()
+        .filter(|alert| alert.is_open() && alert.severity() == Some(severity))
+        .count()
+}
@@ -0,0 +1,58 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::Deserialize;
+
+use crate::dependabot::{Dependabot, DependabotSeverity, DependabotState};
+use crate::repository_list::RepositoryList;
+
+/// How many days an open alert of each severity may stay open before it's overdue, set in the
+/// config's `[sla]` section. A severity without a window is never overdue.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct SlaConfig {
+    pub critical_days: Option<i64>,
+    pub high_days: Option<i64>,
+    pub medium_days: Option<i64>,
+    pub low_days: Option<i64>,
+}
+
+impl Default for SlaConfig {
+    fn default() -> Self {
+        SlaConfig {
+            critical_days: Some(7),
+            high_days: Some(30),
+            medium_days: None,
+            low_days: None,
+        }
+    }
+}
+
+impl SlaConfig {
+    pub fn window(&self, severity: DependabotSeverity) -> Option<Duration> {
+        match severity {
+            DependabotSeverity::Low => self.low_days,
+            DependabotSeverity::Medium => self.medium_days,
+            DependabotSeverity::High => self.high_days,
+            DependabotSeverity::Critical => self.critical_days,
+        }
+        .map(Duration::days)
+    }
+
+    /// How far past its severity's window an open alert is, or `None` if it's within it.
+    pub fn overdue_by(&self, dependabot: &Dependabot, now: DateTime<Utc>) -> Option<Duration> {
+        if dependabot.state != DependabotState::Open {
+            return None;
+        }
+        let overdue_by = dependabot.age(now)? - self.window(dependabot.severity)?;
+
+        (overdue_by > Duration::zero()).then_some(overdue_by)
+    }
+
+    /// How many open alerts across the user's repositories are past their window.
+    pub fn overdue_count(&self, repositories: &RepositoryList, now: DateTime<Utc>) -> usize {
+        repositories
+            .owned()
+            .flat_map(|repo| repo.dependabots.iter())
+            .filter(|dependabot| self.overdue_by(dependabot, now).is_some())
+            .count()
+    }
+}
@@ -0,0 +1,349 @@
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::sync::RwLock;
+
+use chrono::{DateTime, Duration, Utc};
+use directories::ProjectDirs;
+use lazy_static::lazy_static;
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::error::DependabotTrackerError;
+use crate::migrations::{migrate, CURRENT_VERSION};
+use crate::repository::Repository;
+
+// every fetch is kept as a snapshot, with each repository stored as JSON alongside its
+// open alert counts so trends can be read without deserializing the repositories
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS snapshots (
+    id INTEGER PRIMARY KEY,
+    fetched_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS snapshot_repositories (
+    snapshot_id INTEGER NOT NULL REFERENCES snapshots(id),
+    repository_id INTEGER NOT NULL,
+    low_alerts INTEGER NOT NULL,
+    medium_alerts INTEGER NOT NULL,
+    high_alerts INTEGER NOT NULL,
+    critical_alerts INTEGER NOT NULL,
+    data TEXT NOT NULL,
+    PRIMARY KEY (snapshot_id, repository_id)
+);
+";
+
+// changes to the schema since it was first released, applied in order. the database's
+// `user_version` is how many of them have been applied
+const SCHEMA_MIGRATIONS: [&str; 1] = [
+    // the saved data version each snapshot's repositories were written in
+    "ALTER TABLE snapshots ADD COLUMN version INTEGER NOT NULL DEFAULT 1;",
+];
+
+/// How many of the latest snapshots are always kept, enough for the alert trend.
+pub const KEPT_SNAPSHOT_COUNT: usize = 30;
+
+/// How far back the digest compares the alerts. Every snapshot from within it is kept, along with
+/// the newest one from before it.
+pub const DIGEST_WINDOW_DAYS: i64 = 7;
+
+/// A set of saved repositories and when they were fetched, in the format `migrate` upgrades
+/// older data to.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct SavedData {
+    // the format the data was saved in, see `migrations::CURRENT_VERSION`
+    pub version: u32,
+    // `None` for data saved before the fetch time was recorded
+    pub fetched_at: Option<DateTime<Utc>>,
+    pub repositories: Vec<Repository>,
+}
+
+// the app's name in the platform directories, the same for the TUI and anything else reading
+// its data
+const APP_NAME: &str = "dependabot-tracker";
+
+lazy_static! {
+    // the current profile's data directory, switched along with the profile
+    static ref DATA_DIRECTORY: RwLock<PathBuf> = RwLock::new(get_default_data_dir());
+}
+
+/// Where fetched data is kept by default, e.g. `~/.local/share/dependabot-tracker` on Linux, or
+/// `./data` if the platform has no data directory.
+pub fn get_default_data_dir() -> PathBuf {
+    ProjectDirs::from("", "", APP_NAME)
+        .map(|dirs| dirs.data_dir().to_path_buf())
+        .unwrap_or_else(|| PathBuf::from(".").join("data"))
+}
+
+/// Keep snapshots and the event log in `path` from now on.
+pub fn set_data_directory(path: PathBuf) {
+    if let Ok(mut data_directory) = DATA_DIRECTORY.write() {
+        *data_directory = path;
+    }
+}
+
+pub fn data_directory() -> PathBuf {
+    DATA_DIRECTORY
+        .read()
+        .map(|data_directory| data_directory.clone())
+        .unwrap_or_else(|_| get_default_data_dir())
+}
+
+/// The `./data` directory earlier versions kept everything in, if there's data there and it
+/// isn't the current data directory.
+pub fn legacy_data_directory() -> Option<PathBuf> {
+    let legacy_directory = PathBuf::from(".").join("data");
+    let has_data = ["dependabot-tracker.db", "repositories.json"]
+        .iter()
+        .any(|file| legacy_directory.join(file).exists());
+    let is_current =
+        fs::canonicalize(&legacy_directory).ok() == fs::canonicalize(data_directory()).ok();
+
+    (has_data && !is_current).then_some(legacy_directory)
+}
+
+fn open_database() -> Result<Connection, DependabotTrackerError> {
+    fs::create_dir_all(data_directory())?;
+    let connection = Connection::open(data_directory().join("dependabot-tracker.db"))?;
+    connection.execute_batch(SCHEMA)?;
+    migrate_schema(&connection)?;
+
+    Ok(connection)
+}
+
+fn migrate_schema(connection: &Connection) -> rusqlite::Result<()> {
+    let applied: usize = connection.query_row("PRAGMA user_version", [], |row| row.get(0))?;
+    for (index, migration) in SCHEMA_MIGRATIONS.iter().enumerate().skip(applied) {
+        connection.execute_batch(&format!(
+            "BEGIN; {} PRAGMA user_version = {}; COMMIT;",
+            migration,
+            index + 1
+        ))?;
+    }
+
+    Ok(())
+}
+
+fn insert_repository(
+    connection: &Connection,
+    snapshot_id: i64,
+    repository: &Repository,
+) -> Result<(), DependabotTrackerError> {
+    let data = serde_json::to_string(repository)?;
+    connection
+        .execute(
+            "INSERT OR REPLACE INTO snapshot_repositories
+                (snapshot_id, repository_id, low_alerts, medium_alerts, high_alerts, critical_alerts, data)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                snapshot_id,
+                repository.id,
+                repository.low_alerts,
+                repository.medium_alerts,
+                repository.high_alerts,
+                repository.critical_alerts,
+                data
+            ],
+        )?;
+
+    Ok(())
+}
+
+fn latest_snapshot_id(connection: &Connection) -> rusqlite::Result<Option<i64>> {
+    connection
+        .query_row("SELECT MAX(id) FROM snapshots", [], |row| row.get(0))
+        .optional()
+        .map(Option::flatten)
+}
+
+/// Record the repositories as a new snapshot, dropping the snapshots neither the alert trend nor
+/// the digest needs any more. The snapshot is written in one transaction, so a crash part way
+/// through leaves the previous snapshot as the latest.
+pub fn save_snapshot(repositories: &[Repository]) -> Result<(), DependabotTrackerError> {
+    let mut connection = open_database()?;
+    let transaction = connection.transaction()?;
+    let now = Utc::now();
+    transaction.execute(
+        "INSERT INTO snapshots (fetched_at, version) VALUES (?1, ?2)",
+        params![now.to_rfc3339(), CURRENT_VERSION],
+    )?;
+    let snapshot_id = transaction.last_insert_rowid();
+    for repository in repositories {
+        insert_repository(&transaction, snapshot_id, repository)?;
+    }
+    prune_snapshots(&transaction, now - Duration::days(DIGEST_WINDOW_DAYS))?;
+
+    transaction.commit().map_err(DependabotTrackerError::from)
+}
+
+/// Delete the snapshots fetched before `cutoff`, apart from the newest of them and the latest
+/// `KEPT_SNAPSHOT_COUNT` snapshots.
+fn prune_snapshots(connection: &Connection, cutoff: DateTime<Utc>) -> rusqlite::Result<()> {
+    let prunable = "SELECT id FROM snapshots
+        WHERE fetched_at < ?1
+            AND id < (SELECT MAX(id) FROM snapshots WHERE fetched_at < ?1)
+            AND id NOT IN (SELECT id FROM snapshots ORDER BY id DESC LIMIT ?2)";
+    let cutoff = cutoff.to_rfc3339();
+    connection.execute(
+        &format!(
+            "DELETE FROM snapshot_repositories WHERE snapshot_id IN ({})",
+            prunable
+        ),
+        params![cutoff, KEPT_SNAPSHOT_COUNT],
+    )?;
+    connection.execute(
+        &format!("DELETE FROM snapshots WHERE id IN ({})", prunable),
+        params![cutoff, KEPT_SNAPSHOT_COUNT],
+    )?;
+
+    Ok(())
+}
+
+/// Replace a single repository in the latest snapshot, e.g. after dismissing one of its alerts.
+/// A snapshot saved in an older format is upgraded first, so its repositories all share a
+/// version.
+pub fn update_latest_snapshot(repository: &Repository) -> Result<(), DependabotTrackerError> {
+    let connection = open_database()?;
+    let Some(snapshot_id) = latest_snapshot_id(&connection)? else {
+        // the repositories came from the legacy file, which is saved in full as the first
+        // snapshot so the rest of them aren't lost
+        let mut repositories = load_legacy_repositories()?.repositories;
+        match repositories
+            .iter_mut()
+            .find(|saved_repository| saved_repository.id == repository.id)
+        {
+            Some(saved_repository) => *saved_repository = repository.clone(),
+            None => repositories.push(repository.clone()),
+        }
+        return save_snapshot(&repositories);
+    };
+
+    let version: u32 = connection.query_row(
+        "SELECT version FROM snapshots WHERE id = ?1",
+        params![snapshot_id],
+        |row| row.get(0),
+    )?;
+    if version != CURRENT_VERSION {
+        let saved_data = load_snapshot(&connection, snapshot_id)?;
+        for saved_repository in &saved_data.repositories {
+            insert_repository(&connection, snapshot_id, saved_repository)?;
+        }
+        connection.execute(
+            "UPDATE snapshots SET version = ?1 WHERE id = ?2",
+            params![CURRENT_VERSION, snapshot_id],
+        )?;
+    }
+
+    insert_repository(&connection, snapshot_id, repository)
+}
+
+/// The repositories from the latest snapshot and when they were fetched. Before the first
+/// snapshot is saved, they're read from the `data/repositories.json` file earlier versions used,
+/// if it's there.
+pub fn load_latest_snapshot() -> Result<SavedData, DependabotTrackerError> {
+    let connection = open_database()?;
+    let Some(snapshot_id) = latest_snapshot_id(&connection)? else {
+        return load_legacy_repositories();
+    };
+
+    load_snapshot(&connection, snapshot_id)
+}
+
+/// The repositories from the latest snapshot fetched at or before `cutoff`, or none if every
+/// snapshot is newer.
+pub fn load_snapshot_before(
+    cutoff: DateTime<Utc>,
+) -> Result<Vec<Repository>, DependabotTrackerError> {
+    let connection = open_database()?;
+    let snapshot_id: Option<i64> = connection
+        .query_row(
+            "SELECT MAX(id) FROM snapshots WHERE fetched_at <= ?1",
+            params![cutoff.to_rfc3339()],
+            |row| row.get(0),
+        )
+        .optional()?
+        .flatten();
+    let Some(snapshot_id) = snapshot_id else {
+        return Ok(Vec::new());
+    };
+
+    Ok(load_snapshot(&connection, snapshot_id)?.repositories)
+}
+
+/// A snapshot's repositories, upgraded from the version they were saved in.
+fn load_snapshot(
+    connection: &Connection,
+    snapshot_id: i64,
+) -> Result<SavedData, DependabotTrackerError> {
+    let (fetched_at, version): (DateTime<Utc>, u32) = connection.query_row(
+        "SELECT fetched_at, version FROM snapshots WHERE id = ?1",
+        params![snapshot_id],
+        |row| Ok((row.get(0)?, row.get(1)?)),
+    )?;
+    let mut statement = connection
+        .prepare("SELECT data FROM snapshot_repositories WHERE snapshot_id = ?1 ORDER BY rowid")?;
+    let rows = statement.query_map(params![snapshot_id], |row| row.get::<_, String>(0))?;
+
+    let mut repositories = Vec::new();
+    for data in rows {
+        repositories.push(serde_json::from_str::<Value>(&data?)?);
+    }
+
+    migrate(json!({
+        "version": version,
+        "fetched_at": fetched_at,
+        "repositories": repositories,
+    }))
+}
+
+/// The open alert totals across the user's own repositories as of one snapshot.
+pub struct AlertTrendPoint {
+    pub fetched_at: DateTime<Utc>,
+    pub low_alerts: u64,
+    pub medium_alerts: u64,
+    pub high_alerts: u64,
+    pub critical_alerts: u64,
+}
+
+/// The alert totals for the latest `snapshot_count` snapshots, oldest first.
+pub fn load_alert_trend(
+    snapshot_count: usize,
+) -> Result<Vec<AlertTrendPoint>, DependabotTrackerError> {
+    let connection = open_database()?;
+    let mut statement = connection
+        .prepare(
+            "SELECT snapshots.fetched_at, SUM(low_alerts), SUM(medium_alerts), SUM(high_alerts), SUM(critical_alerts)
+             FROM snapshots
+             JOIN snapshot_repositories ON snapshot_repositories.snapshot_id = snapshots.id
+             WHERE json_extract(snapshot_repositories.data, '$.watched') = 0
+             GROUP BY snapshots.id
+             ORDER BY snapshots.id DESC
+             LIMIT ?1",
+        )?;
+    let rows = statement.query_map(params![snapshot_count], |row| {
+        Ok(AlertTrendPoint {
+            fetched_at: row.get(0)?,
+            low_alerts: row.get(1)?,
+            medium_alerts: row.get(2)?,
+            high_alerts: row.get(3)?,
+            critical_alerts: row.get(4)?,
+        })
+    })?;
+
+    let mut trend = rows.collect::<rusqlite::Result<Vec<_>>>()?;
+    trend.reverse();
+
+    Ok(trend)
+}
+
+fn load_legacy_repositories() -> Result<SavedData, DependabotTrackerError> {
+    let file = match fs::File::open(data_directory().join("repositories.json")) {
+        Ok(file) => file,
+        // nothing has been fetched yet
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(SavedData::default()),
+        Err(e) => return Err(e.into()),
+    };
+    let reader = io::BufReader::new(file);
+
+    migrate(serde_json::from_reader(reader)?)
+}
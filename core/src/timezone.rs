@@ -0,0 +1,52 @@
+use chrono::{DateTime, Local, Utc};
+use chrono_tz::Tz;
+use serde::Deserialize;
+
+const TIMESTAMP_FORMAT: &str = "%Y-%m-%d %H:%M:%S %Z";
+
+/// The timezone timestamps are displayed in, configured as `local`, `utc`, or a named timezone
+/// like `America/New_York`.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(try_from = "String")]
+pub enum DisplayTimezone {
+    #[default]
+    Local,
+    Utc,
+    Named(Tz),
+}
+
+impl TryFrom<String> for DisplayTimezone {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        match value.to_lowercase().as_str() {
+            "local" => Ok(DisplayTimezone::Local),
+            "utc" => Ok(DisplayTimezone::Utc),
+            _ => value
+                .parse::<Tz>()
+                .map(DisplayTimezone::Named)
+                .map_err(|e| format!("unknown timezone {}: {}", value, e)),
+        }
+    }
+}
+
+impl DisplayTimezone {
+    pub fn format(&self, timestamp: DateTime<Utc>, format: &str) -> String {
+        match self {
+            DisplayTimezone::Local => timestamp.with_timezone(&Local).format(format).to_string(),
+            DisplayTimezone::Utc => timestamp.format(format).to_string(),
+            DisplayTimezone::Named(tz) => timestamp.with_timezone(tz).format(format).to_string(),
+        }
+    }
+
+    pub fn format_timestamp(&self, timestamp: DateTime<Utc>) -> String {
+        self.format(timestamp, TIMESTAMP_FORMAT)
+    }
+
+    /// Format an RFC 3339 timestamp from the GitHub API, leaving it as-is if it can't be parsed.
+    pub fn format_rfc3339(&self, timestamp: &str) -> String {
+        DateTime::parse_from_rfc3339(timestamp)
+            .map(|parsed| self.format_timestamp(parsed.with_timezone(&Utc)))
+            .unwrap_or_else(|_| timestamp.to_string())
+    }
+}
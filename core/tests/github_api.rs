@@ -0,0 +1,510 @@
+//! The GitHub fetch run against a mock server standing in for the API.
+
+use dependabot_tracker_core::error::DependabotTrackerError;
+use dependabot_tracker_core::fetch_config::FetchConfig;
+use dependabot_tracker_core::provider::SecurityProvider;
+use dependabot_tracker_core::repository::{
+    AlertsStatus, FetchOutcome, FetchProgress, GitHubProvider,
+};
+use dependabot_tracker_core::storage::set_data_directory;
+use serde_json::{json, Value};
+use tokio_util::sync::CancellationToken;
+use wiremock::matchers::{header, method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const TOKEN: &str = "ghp_test";
+
+fn repository(id: u32, name: &str) -> Value {
+    json!({
+        "id": id,
+        "name": name,
+        "full_name": format!("octocat/{}", name),
+        "private": false,
+        "html_url": format!("https://github.com/octocat/{}", name),
+        "archived": false,
+        "owner": { "login": "octocat" },
+    })
+}
+
+fn alert(number: u32, severity: &str, package: &str) -> Value {
+    json!({
+        "number": number,
+        "state": "open",
+        "security_vulnerability": {
+            "severity": severity,
+            "package": { "ecosystem": "npm", "name": package },
+            "vulnerable_version_range": "< 4.17.21",
+            "first_patched_version": { "identifier": "4.17.21" },
+        },
+        "html_url": format!("https://github.com/octocat/hello/security/dependabot/{}", number),
+        "created_at": "2024-05-01T12:00:00Z",
+        "updated_at": "2024-05-01T12:00:00Z",
+        "dismissed_at": null,
+        "auto_dismissed_at": null,
+        "fixed_at": null,
+        "dismissed_by": null,
+        "dismissed_reason": null,
+        "dismissed_comment": null,
+        "dependency": null,
+        "security_advisory": null,
+    })
+}
+
+/// A mock server, and a provider fetching from it. Snapshots are saved to a directory of
+/// their own rather than the real data directory.
+async fn setup() -> (MockServer, GitHubProvider) {
+    set_data_directory(std::env::temp_dir().join(format!(
+        "dependabot-tracker-core-tests-{}",
+        std::process::id()
+    )));
+    let server = MockServer::start().await;
+    let provider = GitHubProvider {
+        token: TOKEN.to_string(),
+        api_url: server.uri(),
+    };
+
+    (server, provider)
+}
+
+fn config() -> FetchConfig {
+    let mut config = FetchConfig::default();
+    config.retry.initial_backoff_ms = 1;
+    config.retry.max_backoff_ms = 1;
+    config
+}
+
+async fn fetch(provider: &GitHubProvider) -> Result<FetchOutcome, DependabotTrackerError> {
+    provider
+        .fetch_repos(&[], &config(), &|_| {}, &CancellationToken::new())
+        .await
+}
+
+async fn mock_repositories(server: &MockServer, repositories: Value) {
+    Mock::given(method("GET"))
+        .and(path("/user/repos"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(repositories))
+        .mount(server)
+        .await;
+}
+
+#[tokio::test]
+async fn follows_link_headers_through_every_page() {
+    let (server, provider) = setup().await;
+    let next = format!("<{}/user/repos?page=2>; rel=\"next\"", server.uri());
+    Mock::given(method("GET"))
+        .and(path("/user/repos"))
+        .and(query_param("page", "2"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([repository(2, "world")])))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/user/repos"))
+        .and(header(
+            "authorization",
+            format!("Bearer {}", TOKEN).as_str(),
+        ))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("link", next.as_str())
+                .set_body_json(json!([repository(1, "hello")])),
+        )
+        .mount(&server)
+        .await;
+    let next_alerts = format!(
+        "<{}/repos/octocat/hello/dependabot/alerts?page=2>; rel=\"next\"",
+        server.uri()
+    );
+    Mock::given(method("GET"))
+        .and(path("/repos/octocat/hello/dependabot/alerts"))
+        .and(query_param("page", "2"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(json!([alert(2, "critical", "minimist")])),
+        )
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/repos/octocat/hello/dependabot/alerts"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("link", next_alerts.as_str())
+                .set_body_json(json!([alert(1, "high", "lodash")])),
+        )
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/repos/octocat/world/dependabot/alerts"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([])))
+        .mount(&server)
+        .await;
+
+    let outcome = fetch(&provider).await.unwrap();
+    let repos = &outcome.repositories.repos;
+
+    assert_eq!(repos.len(), 2);
+    let hello = repos.iter().find(|repo| repo.name == "hello").unwrap();
+    assert_eq!(hello.alerts_status, AlertsStatus::Enabled);
+    assert_eq!(hello.dependabots.len(), 2);
+    assert_eq!(hello.high_alerts, 1);
+    assert_eq!(hello.critical_alerts, 1);
+    assert!(outcome.report.failed.is_empty());
+}
+
+#[tokio::test]
+async fn rate_limited_listing_fails_with_the_reset_time() {
+    let (server, provider) = setup().await;
+    Mock::given(method("GET"))
+        .and(path("/user/repos"))
+        .respond_with(
+            ResponseTemplate::new(403)
+                .insert_header("x-ratelimit-remaining", "0")
+                .insert_header("x-ratelimit-reset", "1700000000")
+                .set_body_json(json!({ "message": "API rate limit exceeded" })),
+        )
+        .mount(&server)
+        .await;
+
+    let Err(error) = fetch(&provider).await else {
+        panic!("the fetch should have failed");
+    };
+
+    assert!(matches!(
+        error,
+        DependabotTrackerError::RateLimited { reset_at } if reset_at.timestamp() == 1_700_000_000
+    ));
+}
+
+#[tokio::test]
+async fn rate_limited_alerts_are_a_failure_rather_than_disabled() {
+    let (server, provider) = setup().await;
+    mock_repositories(&server, json!([repository(1, "hello")])).await;
+    Mock::given(method("GET"))
+        .and(path("/repos/octocat/hello/dependabot/alerts"))
+        .respond_with(
+            ResponseTemplate::new(403)
+                .insert_header("x-ratelimit-remaining", "0")
+                .insert_header("x-ratelimit-reset", "1700000000")
+                .set_body_json(json!({ "message": "API rate limit exceeded" })),
+        )
+        .mount(&server)
+        .await;
+
+    let outcome = fetch(&provider).await.unwrap();
+
+    // with no previous data to fall back on, the repository is left out
+    assert!(outcome.repositories.repos.is_empty());
+    assert_eq!(outcome.report.failed.len(), 1);
+    assert_eq!(outcome.report.failed[0].0, "octocat/hello");
+}
+
+#[tokio::test]
+async fn forbidden_alerts_mark_the_repository_disabled() {
+    let (server, provider) = setup().await;
+    mock_repositories(&server, json!([repository(1, "hello")])).await;
+    Mock::given(method("GET"))
+        .and(path("/repos/octocat/hello/dependabot/alerts"))
+        .respond_with(ResponseTemplate::new(403).set_body_json(json!({
+            "message": "Dependabot alerts are disabled for this repository.",
+        })))
+        .mount(&server)
+        .await;
+
+    let outcome = fetch(&provider).await.unwrap();
+    let repos = &outcome.repositories.repos;
+
+    assert_eq!(repos.len(), 1);
+    assert_eq!(repos[0].alerts_status, AlertsStatus::Disabled);
+    assert!(repos[0].dependabots.is_empty());
+    assert!(outcome.report.failed.is_empty());
+}
+
+#[tokio::test]
+async fn alerts_hidden_from_the_token_mark_the_repository_inaccessible() {
+    let (server, provider) = setup().await;
+    mock_repositories(&server, json!([repository(1, "hello")])).await;
+    Mock::given(method("GET"))
+        .and(path("/repos/octocat/hello/dependabot/alerts"))
+        .respond_with(ResponseTemplate::new(403).set_body_json(json!({
+            "message": "Resource not accessible by personal access token",
+        })))
+        .mount(&server)
+        .await;
+
+    let outcome = fetch(&provider).await.unwrap();
+
+    assert_eq!(
+        outcome.repositories.repos[0].alerts_status,
+        AlertsStatus::NoAccess
+    );
+}
+
+#[tokio::test]
+async fn malformed_listing_is_a_deserialize_error() {
+    let (server, provider) = setup().await;
+    Mock::given(method("GET"))
+        .and(path("/user/repos"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("[{\"id\": "))
+        .mount(&server)
+        .await;
+
+    let Err(error) = fetch(&provider).await else {
+        panic!("the fetch should have failed");
+    };
+
+    assert!(matches!(error, DependabotTrackerError::Deserialize(_)));
+}
+
+#[tokio::test]
+async fn malformed_alerts_fail_only_their_repository() {
+    let (server, provider) = setup().await;
+    mock_repositories(
+        &server,
+        json!([repository(1, "hello"), repository(2, "world")]),
+    )
+    .await;
+    Mock::given(method("GET"))
+        .and(path("/repos/octocat/hello/dependabot/alerts"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([{ "number": "one" }])))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/repos/octocat/world/dependabot/alerts"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(json!([alert(1, "medium", "lodash")])),
+        )
+        .mount(&server)
+        .await;
+
+    let outcome = fetch(&provider).await.unwrap();
+    let repos = &outcome.repositories.repos;
+
+    assert_eq!(repos.len(), 1);
+    assert_eq!(repos[0].name, "world");
+    assert_eq!(repos[0].medium_alerts, 1);
+    assert_eq!(outcome.report.failed.len(), 1);
+    assert_eq!(outcome.report.failed[0].0, "octocat/hello");
+}
+
+#[tokio::test]
+async fn server_errors_are_retried() {
+    let (server, provider) = setup().await;
+    mock_repositories(&server, json!([repository(1, "hello")])).await;
+    Mock::given(method("GET"))
+        .and(path("/repos/octocat/hello/dependabot/alerts"))
+        .respond_with(ResponseTemplate::new(502))
+        .up_to_n_times(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/repos/octocat/hello/dependabot/alerts"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([alert(1, "low", "lodash")])))
+        .mount(&server)
+        .await;
+
+    let outcome = fetch(&provider).await.unwrap();
+
+    assert_eq!(outcome.repositories.repos[0].low_alerts, 1);
+    assert!(outcome.report.failed.is_empty());
+}
+
+#[tokio::test]
+async fn only_listed_repositories_keep_their_data_when_their_alerts_are_hidden() {
+    let (server, mut provider) = setup().await;
+    provider.token = "github_pat_test".to_string();
+    mock_repositories(
+        &server,
+        json!([repository(1, "hello"), repository(2, "deleted")]),
+    )
+    .await;
+    for name in ["hello", "deleted"] {
+        Mock::given(method("GET"))
+            .and(path(format!("/repos/octocat/{}/dependabot/alerts", name)))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(json!([alert(1, "high", "lodash")])),
+            )
+            .mount(&server)
+            .await;
+    }
+    let previous_repos = fetch(&provider).await.unwrap().repositories.repos;
+
+    // `deleted` is gone from the listing, and `hello`'s alerts are no longer readable
+    server.reset().await;
+    mock_repositories(&server, json!([repository(1, "hello")])).await;
+    Mock::given(method("GET"))
+        .and(path("/repos/octocat/hello/dependabot/alerts"))
+        .respond_with(ResponseTemplate::new(403).set_body_json(json!({
+            "message": "Resource not accessible by personal access token",
+        })))
+        .mount(&server)
+        .await;
+    let outcome = provider
+        .fetch_repos(
+            &previous_repos,
+            &config(),
+            &|_| {},
+            &CancellationToken::new(),
+        )
+        .await
+        .unwrap();
+    let repos = &outcome.repositories.repos;
+
+    assert_eq!(repos.len(), 1);
+    assert_eq!(repos[0].name, "hello");
+    assert_eq!(repos[0].alerts_status, AlertsStatus::NoAccess);
+    assert_eq!(repos[0].high_alerts, 1);
+}
+
+#[tokio::test]
+async fn cancelling_keeps_the_repositories_fetched_so_far() {
+    let (server, provider) = setup().await;
+    mock_repositories(
+        &server,
+        json!([repository(1, "hello"), repository(2, "slow")]),
+    )
+    .await;
+    for name in ["hello", "slow"] {
+        Mock::given(method("GET"))
+            .and(path(format!("/repos/octocat/{}/dependabot/alerts", name)))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(json!([alert(1, "high", "lodash")])),
+            )
+            .mount(&server)
+            .await;
+    }
+    let previous_repos = fetch(&provider).await.unwrap().repositories.repos;
+
+    // `slow` is still being fetched when `hello` comes in and the fetch is cancelled
+    server.reset().await;
+    mock_repositories(
+        &server,
+        json!([repository(1, "hello"), repository(2, "slow")]),
+    )
+    .await;
+    Mock::given(method("GET"))
+        .and(path("/repos/octocat/hello/dependabot/alerts"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+            alert(1, "high", "lodash"),
+            alert(2, "critical", "minimist"),
+        ])))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/repos/octocat/slow/dependabot/alerts"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(json!([]))
+                .set_delay(std::time::Duration::from_secs(30)),
+        )
+        .mount(&server)
+        .await;
+    let cancellation = CancellationToken::new();
+    let progress = |progress: FetchProgress| {
+        if matches!(progress, FetchProgress::RepositoryFetched(_)) {
+            cancellation.cancel();
+        }
+    };
+    let outcome = provider
+        .fetch_repos(&previous_repos, &config(), &progress, &cancellation)
+        .await
+        .unwrap();
+    let repos = &outcome.repositories.repos;
+
+    assert!(outcome.cancelled);
+    assert_eq!(outcome.report.succeeded, 1);
+    assert!(outcome.report.failed.is_empty());
+    assert_eq!(repos.len(), 2);
+    let hello = repos.iter().find(|repo| repo.name == "hello").unwrap();
+    assert_eq!(hello.critical_alerts, 1);
+    let slow = repos.iter().find(|repo| repo.name == "slow").unwrap();
+    assert_eq!(slow.high_alerts, 1);
+}
+
+#[tokio::test]
+async fn rejected_token_fails_the_repository_rather_than_disabling_it() {
+    let (server, provider) = setup().await;
+    mock_repositories(&server, json!([repository(1, "hello")])).await;
+    Mock::given(method("GET"))
+        .and(path("/repos/octocat/hello/dependabot/alerts"))
+        .respond_with(ResponseTemplate::new(401).set_body_json(json!({
+            "message": "Bad credentials",
+        })))
+        .mount(&server)
+        .await;
+
+    let outcome = fetch(&provider).await.unwrap();
+
+    assert!(outcome.repositories.repos.is_empty());
+    assert_eq!(outcome.report.failed.len(), 1);
+    assert_eq!(outcome.report.failed[0].0, "octocat/hello");
+    assert!(outcome.report.failed[0].1.contains("Bad credentials"));
+}
+
+#[tokio::test]
+async fn other_forbidden_alerts_fail_the_repository() {
+    let (server, provider) = setup().await;
+    mock_repositories(&server, json!([repository(1, "hello")])).await;
+    Mock::given(method("GET"))
+        .and(path("/repos/octocat/hello/dependabot/alerts"))
+        .respond_with(ResponseTemplate::new(403).set_body_json(json!({
+            "message": "Must have admin rights to Repository.",
+        })))
+        .mount(&server)
+        .await;
+
+    let outcome = fetch(&provider).await.unwrap();
+
+    assert!(outcome.repositories.repos.is_empty());
+    assert_eq!(outcome.report.failed.len(), 1);
+}
+
+#[tokio::test]
+async fn next_pages_outside_the_api_are_not_followed() {
+    let (server, provider) = setup().await;
+    let elsewhere = MockServer::start().await;
+    let next = format!("<{}/user/repos?page=2>; rel=\"next\"", elsewhere.uri());
+    Mock::given(method("GET"))
+        .and(path("/user/repos"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("link", next.as_str())
+                .set_body_json(json!([repository(1, "hello")])),
+        )
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([])))
+        .expect(0)
+        .mount(&elsewhere)
+        .await;
+
+    let Err(error) = fetch(&provider).await else {
+        panic!("the fetch should have failed");
+    };
+
+    assert!(error.to_string().contains(&elsewhere.uri()));
+}
+
+#[tokio::test]
+async fn malformed_watched_repositories_are_reported_with_their_error() {
+    let (server, provider) = setup().await;
+    mock_repositories(&server, json!([])).await;
+    Mock::given(method("GET"))
+        .and(path("/repos/other/lib"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("{\"id\": "))
+        .mount(&server)
+        .await;
+    let mut config = config();
+    config.watched_repositories = vec!["other/lib".to_string()];
+
+    let outcome = provider
+        .fetch_repos(&[], &config, &|_| {}, &CancellationToken::new())
+        .await
+        .unwrap();
+
+    assert!(outcome.repositories.repos.is_empty());
+    assert_eq!(outcome.report.failed.len(), 1);
+    assert_eq!(outcome.report.failed[0].0, "other/lib");
+    assert_ne!(
+        outcome.report.failed[0].1,
+        "The token can't see the watched repository"
+    );
+}
@@ -0,0 +1,104 @@
+//! Snapshots saved to and loaded from a data directory of their own.
+
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use chrono::{Duration, Utc};
+use dependabot_tracker_core::storage::{
+    load_latest_snapshot, load_snapshot_before, save_snapshot, set_data_directory,
+    update_latest_snapshot, KEPT_SNAPSHOT_COUNT,
+};
+use rusqlite::{params, Connection};
+
+// the data directory is shared by the whole process, so the tests take turns with it
+static DATA_DIRECTORY: Mutex<()> = Mutex::new(());
+
+/// An empty data directory of the test's own, set as the current one.
+fn data_directory(name: &str) -> PathBuf {
+    let data_dir = std::env::temp_dir().join(format!(
+        "dependabot-tracker-core-storage-tests-{}-{}",
+        name,
+        std::process::id()
+    ));
+    let _ = fs::remove_dir_all(&data_dir);
+    fs::create_dir_all(&data_dir).unwrap();
+    set_data_directory(data_dir.clone());
+
+    data_dir
+}
+
+#[test]
+fn updating_a_repository_loaded_from_the_legacy_file_keeps_the_others() {
+    let _lock = DATA_DIRECTORY.lock().unwrap();
+    let data_dir = data_directory("legacy");
+    fs::write(
+        data_dir.join("repositories.json"),
+        include_str!("fixtures/saved_data/v4.json"),
+    )
+    .unwrap();
+
+    let mut repository = load_latest_snapshot().unwrap().repositories.remove(0);
+    repository.dependabots.clear();
+    update_latest_snapshot(&repository).unwrap();
+
+    let repositories = load_latest_snapshot().unwrap().repositories;
+    assert_eq!(repositories.len(), 2);
+    let updated = repositories
+        .iter()
+        .find(|saved_repository| saved_repository.id == repository.id)
+        .unwrap();
+    assert!(updated.dependabots.is_empty());
+}
+
+#[test]
+fn old_snapshots_are_dropped_apart_from_the_digest_baseline() {
+    let _lock = DATA_DIRECTORY.lock().unwrap();
+    let data_dir = data_directory("retention");
+    fs::write(
+        data_dir.join("repositories.json"),
+        include_str!("fixtures/saved_data/v4.json"),
+    )
+    .unwrap();
+    let repositories = load_latest_snapshot().unwrap().repositories;
+    // ten daily snapshots from a couple of weeks ago, then one an hour over the last few days
+    let old_count = 10;
+    let snapshot_count = old_count + KEPT_SNAPSHOT_COUNT;
+    for _ in 0..snapshot_count {
+        save_snapshot(&repositories).unwrap();
+    }
+    let connection = Connection::open(data_dir.join("dependabot-tracker.db")).unwrap();
+    for id in 1..=snapshot_count {
+        let age = if id <= old_count {
+            Duration::days((20 - id) as i64)
+        } else {
+            Duration::hours((snapshot_count + 1 - id) as i64)
+        };
+        connection
+            .execute(
+                "UPDATE snapshots SET fetched_at = ?1 WHERE id = ?2",
+                params![(Utc::now() - age).to_rfc3339(), id as i64],
+            )
+            .unwrap();
+    }
+
+    save_snapshot(&repositories).unwrap();
+
+    // everything from the last week, and the newest snapshot from before it
+    let snapshots: usize = connection
+        .query_row("SELECT COUNT(*) FROM snapshots", [], |row| row.get(0))
+        .unwrap();
+    assert_eq!(snapshots, KEPT_SNAPSHOT_COUNT + 2);
+    let orphaned_repositories: usize = connection
+        .query_row(
+            "SELECT COUNT(*) FROM snapshot_repositories
+             WHERE snapshot_id NOT IN (SELECT id FROM snapshots)",
+            [],
+            |row| row.get(0),
+        )
+        .unwrap();
+    assert_eq!(orphaned_repositories, 0);
+    // the digest still has last week's snapshot to compare against
+    let baseline = load_snapshot_before(Utc::now() - Duration::days(7)).unwrap();
+    assert_eq!(baseline.len(), repositories.len());
+}
@@ -0,0 +1,143 @@
+use std::cmp::Ordering;
+use std::collections::BTreeSet;
+
+use crate::dependabot::{Dependabot, DependabotSeverity, DependabotState, DependencyScope};
+use crate::search::{is_fuzzy_match, RepositorySearch};
+
+/// The order the dependabot details view lists alerts in, cycled with `s`.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum AlertSort {
+    // the order GitHub returned them in
+    #[default]
+    Api,
+    Oldest,
+    Severity,
+    Package,
+}
+
+impl AlertSort {
+    pub fn next(self) -> AlertSort {
+        match self {
+            AlertSort::Api => AlertSort::Oldest,
+            AlertSort::Oldest => AlertSort::Severity,
+            AlertSort::Severity => AlertSort::Package,
+            AlertSort::Package => AlertSort::Api,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            AlertSort::Api => "GitHub order",
+            AlertSort::Oldest => "oldest",
+            AlertSort::Severity => "severity",
+            AlertSort::Package => "package",
+        }
+    }
+
+    fn compare(self, a: &Dependabot, b: &Dependabot) -> Ordering {
+        match self {
+            AlertSort::Api => Ordering::Equal,
+            // GitHub's timestamps are all UTC, so they sort as strings
+            AlertSort::Oldest => a.created_at.cmp(&b.created_at),
+            AlertSort::Severity => b
+                .severity
+                .cmp(&a.severity)
+                .then_with(|| a.created_at.cmp(&b.created_at)),
+            AlertSort::Package => a
+                .dependency_name
+                .to_lowercase()
+                .cmp(&b.dependency_name.to_lowercase()),
+        }
+    }
+}
+
+/// Which alerts the dependabot details view lists and in what order, toggled with `1`-`4`, `a`,
+/// `D`, `s`, and `/`.
+pub struct AlertFilter {
+    // the severities whose alerts are left out
+    hidden_severities: BTreeSet<DependabotSeverity>,
+    // whether dismissed and fixed alerts are listed alongside the open ones
+    pub show_closed: bool,
+    // whether alerts on development-only dependencies are left out
+    pub hide_development: bool,
+    pub sort: AlertSort,
+    // only alerts whose package name fuzzy matches the query are listed
+    pub package_search: RepositorySearch,
+}
+
+impl Default for AlertFilter {
+    fn default() -> Self {
+        AlertFilter {
+            hidden_severities: BTreeSet::new(),
+            show_closed: true,
+            hide_development: false,
+            sort: AlertSort::default(),
+            package_search: RepositorySearch::default(),
+        }
+    }
+}
+
+impl AlertFilter {
+    pub fn toggle_severity(&mut self, severity: DependabotSeverity) {
+        if !self.hidden_severities.remove(&severity) {
+            self.hidden_severities.insert(severity);
+        }
+    }
+
+    pub fn toggle_closed(&mut self) {
+        self.show_closed = !self.show_closed;
+    }
+
+    pub fn toggle_development(&mut self) {
+        self.hide_development = !self.hide_development;
+    }
+
+    pub fn next_sort(&mut self) {
+        self.sort = self.sort.next();
+    }
+
+    pub fn matches(&self, dependabot: &Dependabot) -> bool {
+        !self.hidden_severities.contains(&dependabot.severity)
+            && (self.show_closed || dependabot.state == DependabotState::Open)
+            && !(self.hide_development && dependabot.scope == Some(DependencyScope::Development))
+            && is_fuzzy_match(&dependabot.dependency_name, &self.package_search.query)
+    }
+
+    /// Put the alerts in the chosen order. The sort is stable, so alerts that compare equal
+    /// keep GitHub's order.
+    pub fn sort(&self, alerts: &mut [Dependabot]) {
+        alerts.sort_by(|a, b| self.sort.compare(a, b));
+    }
+
+    /// How the list is sorted and what's being left out, e.g.
+    /// `sorted by severity, hiding low, dismissed/fixed`, or `None` if it's unchanged.
+    pub fn label(&self) -> Option<String> {
+        let mut label = Vec::new();
+        if self.sort != AlertSort::Api {
+            label.push(format!("sorted by {}", self.sort.label()));
+        }
+        if let Some(hidden_label) = self.hidden_label() {
+            label.push(format!("hiding {}", hidden_label));
+        }
+
+        (!label.is_empty()).then(|| label.join(", "))
+    }
+
+    /// What's being left out, e.g. `low, medium, dismissed/fixed, development`, or `None` if
+    /// nothing is.
+    fn hidden_label(&self) -> Option<String> {
+        let mut hidden: Vec<String> = self
+            .hidden_severities
+            .iter()
+            .map(|severity| severity.to_string().to_lowercase())
+            .collect();
+        if !self.show_closed {
+            hidden.push("dismissed/fixed".to_string());
+        }
+        if self.hide_development {
+            hidden.push("development".to_string());
+        }
+
+        (!hidden.is_empty()).then(|| hidden.join(", "))
+    }
+}
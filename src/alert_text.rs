@@ -0,0 +1,286 @@
+use ratatui::{
+    style::Style,
+    text::{Line, Span},
+};
+
+use crate::dependabot::Dependabot;
+use crate::scanning::{CodeScanningAlert, SecretScanningAlert};
+use crate::security_alert::SecurityAlert;
+use crate::theme::Theme;
+use crate::timezone::DisplayTimezone;
+
+/// How each kind of alert is drawn in the Project view, kept out of the alert types themselves
+/// so they don't depend on ratatui.
+pub trait AlertText: SecurityAlert {
+    fn to_text(&self, timezone: &DisplayTimezone, theme: &Theme) -> Vec<Line<'_>>;
+}
+
+impl AlertText for Dependabot {
+    fn to_text(&self, timezone: &DisplayTimezone, theme: &Theme) -> Vec<Line<'_>> {
+        let mut lines = Vec::<Line>::new();
+        lines.push(Line::from(vec![Span::styled(
+            "-".repeat(20),
+            Style::default().fg(theme.accent),
+        )]));
+        lines.push(Line::from(vec![Span::styled(
+            format!("Number: {}", self.number),
+            Style::default().fg(theme.text),
+        )]));
+        lines.push(Line::from(vec![Span::styled(
+            format!("State: {}", self.state),
+            Style::default().fg(theme.text),
+        )]));
+        lines.push(Line::from(vec![Span::styled(
+            format!("Severity: {}", self.severity),
+            Style::default().fg(theme.text),
+        )]));
+        lines.push(Line::from(vec![Span::styled(
+            format!("URL: {}", self.html_url),
+            Style::default().fg(theme.text),
+        )]));
+        lines.push(Line::from(vec![Span::styled(
+            format!("GHSA ID: {}", self.ghsa_id.as_deref().unwrap_or("N/A")),
+            Style::default().fg(theme.text),
+        )]));
+        lines.push(Line::from(vec![Span::styled(
+            format!("CVE ID: {}", self.cve_id.as_deref().unwrap_or("N/A")),
+            Style::default().fg(theme.text),
+        )]));
+        lines.push(Line::from(vec![Span::styled(
+            format!(
+                "CVSS Score: {}",
+                self.cvss_score
+                    .map(|score| format!("{:.1}", score))
+                    .unwrap_or_else(|| "N/A".to_string())
+            ),
+            Style::default().fg(theme.text),
+        )]));
+        lines.push(Line::from(vec![Span::styled(
+            format!("Summary: {}", self.summary.as_deref().unwrap_or("N/A")),
+            Style::default().fg(theme.text),
+        )]));
+        lines.push(Line::from(vec![Span::styled(
+            format!("Created At: {}", timezone.format_rfc3339(&self.created_at)),
+            Style::default().fg(theme.text),
+        )]));
+        lines.push(Line::from(vec![Span::styled(
+            format!("Updated At: {}", timezone.format_rfc3339(&self.updated_at)),
+            Style::default().fg(theme.text),
+        )]));
+        lines.push(Line::from(vec![Span::styled(
+            format!(
+                "Dismissed At: {}",
+                self.dismissed_at
+                    .as_deref()
+                    .map(|dismissed_at| timezone.format_rfc3339(dismissed_at))
+                    .unwrap_or_else(|| "N/A".to_string())
+            ),
+            Style::default().fg(theme.text),
+        )]));
+        lines.push(Line::from(vec![Span::styled(
+            format!(
+                "Auto Dismissed At: {}",
+                self.auto_dismissed_at
+                    .as_deref()
+                    .map(|auto_dismissed_at| timezone.format_rfc3339(auto_dismissed_at))
+                    .unwrap_or_else(|| "N/A".to_string())
+            ),
+            Style::default().fg(theme.text),
+        )]));
+        lines.push(Line::from(vec![Span::styled(
+            format!(
+                "Fixed At: {}",
+                self.fixed_at
+                    .as_deref()
+                    .map(|fixed_at| timezone.format_rfc3339(fixed_at))
+                    .unwrap_or_else(|| "N/A".to_string())
+            ),
+            Style::default().fg(theme.text),
+        )]));
+        if let Some(dismissed_by) = &self.dismissed_by {
+            lines.push(Line::from(vec![Span::styled(
+                format!("Dismissed By: {}", dismissed_by),
+                Style::default().fg(theme.text),
+            )]));
+        }
+        if let Some(dismissed_reason) = self.dismissed_reason_label() {
+            lines.push(Line::from(vec![Span::styled(
+                format!("Dismissed Reason: {}", dismissed_reason),
+                Style::default().fg(theme.text),
+            )]));
+        }
+        if let Some(dismissed_comment) = &self.dismissed_comment {
+            lines.push(Line::from(vec![Span::styled(
+                format!("Dismissed Comment: {}", dismissed_comment),
+                Style::default().fg(theme.text),
+            )]));
+        }
+        lines.push(Line::from(vec![Span::styled(
+            format!("Dependency Ecosystem: {}", self.dependency_ecosystem),
+            Style::default().fg(theme.text),
+        )]));
+        lines.push(Line::from(vec![Span::styled(
+            format!("Dependency Name: {}", self.dependency_name),
+            Style::default().fg(theme.text),
+        )]));
+        lines.push(Line::from(vec![Span::styled(
+            format!(
+                "Manifest: {}",
+                self.manifest_path.as_deref().unwrap_or("N/A")
+            ),
+            Style::default().fg(theme.text),
+        )]));
+        lines.push(Line::from(vec![Span::styled(
+            format!(
+                "Scope: {}",
+                self.scope
+                    .map(|scope| scope.to_string())
+                    .unwrap_or_else(|| "N/A".to_string())
+            ),
+            Style::default().fg(theme.text),
+        )]));
+        lines.push(Line::from(vec![Span::styled(
+            format!(
+                "Vulnerable Versions: {}",
+                self.vulnerable_version_range.as_deref().unwrap_or("N/A")
+            ),
+            Style::default().fg(theme.text),
+        )]));
+        lines.push(Line::from(vec![Span::styled(
+            format!(
+                "Patched Version: {}",
+                self.first_patched_version.as_deref().unwrap_or("N/A")
+            ),
+            Style::default().fg(theme.text),
+        )]));
+        match self.upgrade_hint() {
+            Some(upgrade_hint) => lines.push(Line::from(vec![Span::styled(
+                format!("Fix: {}", upgrade_hint),
+                Style::default().fg(theme.accent),
+            )])),
+            None => lines.push(Line::from(vec![Span::styled(
+                "Fix: no patched version has been released yet",
+                Style::default().fg(theme.warning),
+            )])),
+        }
+        lines.push(Line::from(vec![Span::styled(
+            format!("License: {}", self.license.as_deref().unwrap_or("N/A")),
+            Style::default().fg(theme.text),
+        )]));
+        if let Some(description) = &self.description {
+            lines.push(Line::from(vec![Span::styled(
+                "Description:",
+                Style::default().fg(theme.text),
+            )]));
+            // list items don't wrap, so each line of the description gets its own line
+            lines.extend(
+                description
+                    .lines()
+                    .filter(|line| !line.trim().is_empty())
+                    .map(|line| Line::from(Span::raw(format!("  {}", line.trim_end())))),
+            );
+        }
+
+        lines
+    }
+}
+
+impl AlertText for CodeScanningAlert {
+    fn to_text(&self, timezone: &DisplayTimezone, theme: &Theme) -> Vec<Line<'_>> {
+        vec![
+            Line::from(vec![Span::styled(
+                "-".repeat(20),
+                Style::default().fg(theme.accent),
+            )]),
+            Line::from(vec![Span::styled(
+                format!("Number: {}", self.number),
+                Style::default().fg(theme.text),
+            )]),
+            Line::from(vec![Span::styled(
+                format!("State: {:?}", self.state),
+                Style::default().fg(theme.text),
+            )]),
+            Line::from(vec![Span::styled(
+                format!(
+                    "Severity: {}",
+                    self.severity
+                        .map(|severity| severity.to_string())
+                        .unwrap_or_else(|| "N/A".to_string())
+                ),
+                Style::default().fg(theme.text),
+            )]),
+            Line::from(vec![Span::styled(
+                format!(
+                    "Rule: {} ({})",
+                    self.description,
+                    self.rule_id.as_deref().unwrap_or("N/A")
+                ),
+                Style::default().fg(theme.text),
+            )]),
+            Line::from(vec![Span::styled(
+                format!("Tool: {}", self.tool),
+                Style::default().fg(theme.text),
+            )]),
+            Line::from(vec![Span::styled(
+                format!("Location: {}", self.location.as_deref().unwrap_or("N/A")),
+                Style::default().fg(theme.text),
+            )]),
+            Line::from(vec![Span::styled(
+                format!("URL: {}", self.html_url),
+                Style::default().fg(theme.text),
+            )]),
+            Line::from(vec![Span::styled(
+                format!("Created At: {}", timezone.format_rfc3339(&self.created_at)),
+                Style::default().fg(theme.text),
+            )]),
+        ]
+    }
+}
+
+impl AlertText for SecretScanningAlert {
+    fn to_text(&self, timezone: &DisplayTimezone, theme: &Theme) -> Vec<Line<'_>> {
+        vec![
+            Line::from(vec![Span::styled(
+                "-".repeat(20),
+                Style::default().fg(theme.accent),
+            )]),
+            Line::from(vec![Span::styled(
+                format!("Number: {}", self.number),
+                Style::default().fg(theme.text),
+            )]),
+            Line::from(vec![Span::styled(
+                format!("State: {:?}", self.state),
+                Style::default().fg(theme.text),
+            )]),
+            Line::from(vec![Span::styled(
+                format!(
+                    "Secret Type: {}",
+                    self.secret_type_display_name.as_deref().unwrap_or("N/A")
+                ),
+                Style::default().fg(theme.text),
+            )]),
+            Line::from(vec![Span::styled(
+                format!(
+                    "Resolution: {}",
+                    self.resolution.as_deref().unwrap_or("N/A")
+                ),
+                Style::default().fg(theme.text),
+            )]),
+            Line::from(vec![Span::styled(
+                format!(
+                    "Push Protection Bypassed: {}",
+                    self.push_protection_bypassed.unwrap_or(false)
+                ),
+                Style::default().fg(theme.text),
+            )]),
+            Line::from(vec![Span::styled(
+                format!("URL: {}", self.html_url),
+                Style::default().fg(theme.text),
+            )]),
+            Line::from(vec![Span::styled(
+                format!("Created At: {}", timezone.format_rfc3339(&self.created_at)),
+                Style::default().fg(theme.text),
+            )]),
+        ]
+    }
+}
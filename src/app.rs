@@ -1,82 +1,2278 @@
-use std::error::Error;
+use std::borrow::Cow;
+use std::collections::BTreeSet;
+use std::fs::File;
+use std::future::Future;
+use std::io::{BufWriter, Write};
 use std::path::PathBuf;
-use std::sync::mpsc::Receiver;
+use std::time::Instant;
 
+use arboard::Clipboard;
+use chrono::{DateTime, Duration, Utc};
 use color_eyre::eyre::Result;
-use ratatui::widgets::ScrollbarState;
+use ratatui::widgets::{ListState, ScrollbarState};
+use reqwest::Client;
 use throbber_widgets_tui::ThrobberState;
+use tokio::runtime::Runtime;
+use tokio::sync::mpsc::error::TryRecvError;
+use tokio::sync::mpsc::{self, UnboundedReceiver};
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
 
+use crate::alert_filter::AlertFilter;
+use crate::audit::AuditReport;
+use crate::config::{get_config_path, Config};
+use crate::credentials::{persist_credentials, save_keyring_token, CredentialsInput};
 use crate::current_screen::CurrentScreen;
-use crate::repository::Repository;
+use crate::dependabot::{Dependabot, DependabotSeverity, DependabotState, VulnerableDependency};
+use crate::desktop_notification::notify_new_alerts;
+use crate::device_flow::{fetch_username, poll_for_token, request_device_code, DeviceCode};
+use crate::dismissal::DismissalInput;
+use crate::error::DependabotTrackerError;
+use crate::event_log::{append_alert_events, detect_alert_events, AlertEvent};
+use crate::events::AppEvent;
+use crate::export::export_csv;
+use crate::github_client::{HttpGithubClient, GITHUB_API_URL};
+use crate::help::Help;
+use crate::mouse::HitAreas;
+use crate::notifications::{NotificationLevel, Notifications, StatusMessages};
+use crate::osv::{
+    advisory_id, cached_advisory, enrich_advisories, fetch_advisory, uncached_advisory_ids,
+    OsvLookup,
+};
+use crate::performance::PerformanceStats;
+use crate::profile::Profile;
+use crate::provider::{ProviderKind, SecurityProvider};
+use crate::report::SecurityReport;
+use crate::repository::{
+    dismiss_alert, enable_dependabot_alerts, fetch_stale_repos, AlertsStatus, FetchOutcome,
+    FetchProgress, Repository,
+};
 use crate::repository_list::RepositoryList;
+use crate::repository_tree::{section_row, tree_rows, RepositorySection, SectionHeader, TreeRow};
+use crate::search::RepositorySearch;
+use crate::security_alert::SecurityAlert;
+use crate::setup::{save_setup, SetupWizard};
+use crate::storage::{
+    data_directory, get_default_data_dir, legacy_data_directory, load_alert_trend,
+    load_latest_snapshot, set_data_directory, update_latest_snapshot, AlertTrendPoint, SavedData,
+};
+use crate::templates::{render_template, TemplateContext};
+use crate::theme::{Theme, ThemeName};
 use crate::trace_dbg;
+use crate::update_progress::UpdateProgress;
+use crate::webhook::{post_webhook_summary, WebhookSummary};
 
-pub type DependabotTrackerError = Box<dyn Error + Send + 'static>;
+/// A message from the background device flow login.
+pub enum LoginMessage {
+    Code(DeviceCode),
+    // the username and token once the user has authorized the app
+    Complete(Result<(String, String), DependabotTrackerError>),
+}
+
+/// A device flow login running in the background.
+pub struct DeviceLogin {
+    task: JoinHandle<()>,
+    messages: UnboundedReceiver<LoginMessage>,
+    // the code for the user to enter, once GitHub has issued it
+    pub code: Option<DeviceCode>,
+    // the credentials prompt or setup wizard the login was started from
+    started_from: CurrentScreen,
+}
+
+/// An alert dismissal being sent to GitHub in the background.
+struct PendingDismissal {
+    // the repository the alert is in, which may no longer be the one on screen once it finishes
+    repo_id: u32,
+    task: JoinHandle<Result<Dependabot, DependabotTrackerError>>,
+}
+
+// how many days before the PAT expires to start warning about it
+const TOKEN_EXPIRATION_WARNING_DAYS: i64 = 7;
+// how many of the latest fetches the overview's trend chart covers
+const TREND_SNAPSHOT_COUNT: usize = 30;
+// shown when something that needs the network is tried in offline mode
+const OFFLINE_MESSAGE: &str = "Offline: start without --offline to talk to GitHub";
+// where the export prompt starts out saving the alerts
+const DEFAULT_EXPORT_PATH: &str = "dependabot-alerts.csv";
+// how many marked alerts can be opened in the browser before asking for confirmation
+pub const BATCH_OPEN_CONFIRMATION_THRESHOLD: usize = 10;
+// how many items a half-page scroll moves a list's selection
+pub const HALF_PAGE: usize = 10;
+// how many items a full-page scroll moves a list's selection
+pub const PAGE: usize = HALF_PAGE * 2;
 
 pub struct App {
     // the currently repository being viewed
     pub current_repository: Option<Repository>,
-    // the last time that the dependabots file was updated
-    pub last_updated: String,
+    // when the repositories being shown were fetched, if that's known
+    pub last_updated: Option<DateTime<Utc>>,
     // the list of all repositories
     pub repositories: RepositoryList,
     // the current screen the user is looking at, and will later determine what is rendered
     pub current_screen: CurrentScreen,
-    // the github api token
-    pub token: String,
-    // the github username
-    pub username: String,
+    // where the clickable parts of the last drawn frame were
+    pub hit_areas: HitAreas,
+    // the github account being tracked and where its data is kept
+    pub profile: Profile,
+    // the profiles listed on the profile switcher, read when it's opened
+    pub profiles: Vec<Profile>,
+    // the selection on the profile switcher
+    pub profiles_state: ListState,
     // the state of the spinning widget
     pub spinner_state: ThrobberState,
-    // the channel to receive the result of the fetching thread
-    pub fetching: Option<Receiver<Result<RepositoryList, DependabotTrackerError>>>,
-    // the scrollbar for viewing a repository's dependabots
+    // the channel to receive progress and the result of the background fetch
+    pub fetching: Option<UnboundedReceiver<AppEvent>>,
+    // cancels the running fetch
+    fetch_cancellation: Option<CancellationToken>,
+    // whether the running fetch is a periodic refresh that leaves the UI usable
+    pub background_fetch: bool,
+    // the repositories from before the running fetch, as the list changes while it streams in
+    pre_fetch_repositories: Option<Vec<Repository>>,
+    // the device flow login in progress, if any
+    pub login: Option<DeviceLogin>,
+    // when the last fetch was started (or the app was opened), for the periodic refresh
+    last_fetch_started: Instant,
+    // the runtime that GitHub requests are made on
+    pub runtime: Runtime,
+    // how far the current fetch has gotten
+    pub fetch_progress: UpdateProgress,
+    // the selected alert and scrollbar for viewing a repository's dependabots
     pub scrollbar: DependabotScrollbar,
-    // the height of the current window chunk
-    pub chunk_height: u16,
-    // the last error that occurred
+    // when the github api token expires, as reported by the last fetch
+    pub token_expiration: Option<DateTime<Utc>>,
+    // whether the last fetch stopped at the configured repository cap
+    pub repositories_truncated: bool,
+    // the user's configuration
+    pub config: Config,
+    // the config file the credentials prompt suggests saving the token in
+    pub config_path: Option<PathBuf>,
+    // the input state of the credentials prompt
+    pub credentials_input: CredentialsInput,
+    // the answers given so far on the first-run setup wizard
+    pub setup: SetupWizard,
+    // the selection in the distinct alerts view
+    pub distinct_alerts_state: ListState,
+    // the events that happened during this session
+    pub notifications: Notifications,
+    // the transient messages shown in the footer
+    pub status: StatusMessages,
+    // render timings for the debug overlay
+    pub performance: PerformanceStats,
+    // the keybindings popup
+    pub help: Help,
+    // the built-in theme being shown, and its colors with the config's changes
+    pub theme_name: ThemeName,
+    pub theme: Theme,
+    // the selection in the copyleft license report
+    pub copyleft_report_state: ListState,
+    // the selected package on the Dependencies screen
+    pub dependencies_state: ListState,
+    // the selected affected repository, once a package has been drilled into
+    pub dependency_repositories_state: Option<ListState>,
+    // whether to only show alerts that have a patched version available
+    pub fixable_only: bool,
+    // the alert changes between the last two fetches, shown in What's New and on the Changes screen
+    pub last_changes: Vec<AlertEvent>,
+    // the selection on the Changes screen
+    pub changes_state: ListState,
+    // whether archived repositories are left out of the list, the overview, and the reports
+    pub hide_archived: bool,
+    // the severities and states listed in the dependabot details view
+    pub alert_filter: AlertFilter,
+    // the repository group from the config the app is scoped to, if any
+    pub selected_group: Option<String>,
+    // the GitHub topic the repositories are filtered to, if any
+    pub selected_topic: Option<String>,
+    // the query the repository list is filtered by
+    pub repository_search: RepositorySearch,
+    // the sections of the repository list whose repositories are hidden under their headers
+    pub collapsed_sections: BTreeSet<RepositorySection>,
+    // local scanner reports to compare against GitHub's alerts, from `--audit`
+    pub audit_reports: Vec<AuditReport>,
+    // whether the overview shows each severity's share of the alerts instead of counts
+    pub show_severity_percentages: bool,
+    // whether the overview shows the open alerts over recent fetches instead of the latest counts
+    pub show_alert_trend: bool,
+    // the open alert totals from the latest fetches, oldest first
+    pub alert_trend: Vec<AlertTrendPoint>,
+    // the numbers of the alerts marked for opening in the browser together
+    pub marked_alerts: Vec<u32>,
+    // the system clipboard, kept alive so copied text isn't lost on platforms like X11
+    pub clipboard: Option<Clipboard>,
+    // the selected alert on the code scanning and secret scanning tabs
+    pub security_alerts_state: ListState,
+    // the number of the alert open on the alert detail screen
+    pub alert_detail: Option<u32>,
+    // how many lines the alert detail screen is scrolled down
+    pub alert_detail_scroll: u16,
+    // the OSV.dev entry for the alert open on the alert detail screen
+    pub osv_lookup: OsvLookup,
+    // where the export prompt saves the alerts as CSV
+    pub export_path: String,
+    // the input state of the alert dismissal modal
+    pub dismissal_input: DismissalInput,
+    // dismissals sent to GitHub that haven't come back yet
+    pending_dismissals: Vec<PendingDismissal>,
+    // the last error that occurred, shown in the error popup until it's dismissed
     pub error: Option<String>,
+    // the screen to go back to when the error popup is dismissed
+    pub screen_before_error: CurrentScreen,
+    // the screen to go back to if the credentials prompt is closed, and the one to go on to once
+    // credentials are entered
+    screen_before_credentials: CurrentScreen,
+    screen_after_credentials: CurrentScreen,
+    // the screen the Update prompt was opened from, to go back to if the fetch is cancelled
+    screen_before_update: CurrentScreen,
 }
 
 impl App {
-    pub fn new() -> App {
-        let loaded_repositories = load_repositories_from_file();
-        let repositories = loaded_repositories.unwrap_or_else(|_| {
-            trace_dbg!(level: tracing::Level::ERROR, "Failed to load repositories from file");
-            vec![]
-        });
+    /// An app for the config's default profile.
+    pub fn with_config(config: Config) -> App {
+        let profile = config.default_profile();
+        App::new(config, profile)
+    }
+
+    /// An app showing the repositories saved in the profile's data directory.
+    pub fn new(config: Config, profile: Profile) -> App {
+        let mut notifications = Notifications::default();
+        let saved_data = load_saved_repositories(&mut notifications);
+        if saved_data.repositories.is_empty() {
+            if let Some(legacy_directory) = legacy_data_directory() {
+                notifications.push(
+                    NotificationLevel::Warning,
+                    format!(
+                        "Data is now kept in {}. Move {} there, or start with --data-dir {}, to keep what was fetched before",
+                        data_directory().display(),
+                        legacy_directory.display(),
+                        legacy_directory.display()
+                    ),
+                );
+            }
+        }
+
+        let mut app = App::with_saved_data(config, profile, saved_data);
+        app.notifications = notifications;
+        app.alert_trend = load_trend();
+        app.config_path = get_config_path();
+        app
+    }
+
+    /// An app showing `saved_data`, without reading anything from disk.
+    pub fn with_saved_data(config: Config, profile: Profile, saved_data: SavedData) -> App {
+        let mut repositories = RepositoryList::with_respositories(saved_data.repositories);
+        repositories.set_sort_mode(config.default_sort);
+        let theme_name = config.theme.name;
+        let theme = config.theme.resolve(theme_name);
+        let mut alert_filter = AlertFilter::default();
+        alert_filter.hide_development = config.hide_development_alerts;
         App {
             current_repository: None,
-            last_updated: String::new(),
-            repositories: RepositoryList::with_respositories(repositories),
+            last_updated: saved_data.fetched_at,
+            repositories,
             current_screen: CurrentScreen::default(),
-            token: std::env::var("PAT").expect("PAT not set"),
-            username: std::env::var("GH_USERNAME").expect("GH_USERNAME not set"),
+            token_expiration: None,
+            repositories_truncated: false,
+            config,
+            config_path: None,
+            credentials_input: CredentialsInput::with_username(&profile.username),
+            setup: SetupWizard::default(),
+            distinct_alerts_state: ListState::default(),
+            notifications: Notifications::default(),
+            status: StatusMessages::default(),
+            performance: PerformanceStats::default(),
+            help: Help::default(),
+            theme_name,
+            theme,
+            copyleft_report_state: ListState::default(),
+            dependencies_state: ListState::default(),
+            dependency_repositories_state: None,
+            fixable_only: false,
+            last_changes: Vec::new(),
+            changes_state: ListState::default(),
+            hide_archived: false,
+            alert_filter,
+            marked_alerts: Vec::new(),
+            selected_group: None,
+            selected_topic: None,
+            collapsed_sections: BTreeSet::new(),
+            repository_search: RepositorySearch::default(),
+            show_severity_percentages: false,
+            show_alert_trend: false,
+            alert_trend: Vec::new(),
+            audit_reports: Vec::new(),
+            clipboard: None,
+            security_alerts_state: ListState::default(),
+            alert_detail: None,
+            osv_lookup: OsvLookup::Unavailable,
+            alert_detail_scroll: 0,
+            export_path: DEFAULT_EXPORT_PATH.to_string(),
+            dismissal_input: DismissalInput::default(),
+            pending_dismissals: Vec::new(),
+            profile,
+            profiles: Vec::new(),
+            profiles_state: ListState::default(),
+            hit_areas: HitAreas::default(),
             spinner_state: ThrobberState::default(),
             fetching: None,
+            fetch_cancellation: None,
+            background_fetch: false,
+            pre_fetch_repositories: None,
+            login: None,
+            last_fetch_started: Instant::now(),
+            runtime: Runtime::new().expect("Failed to start the tokio runtime"),
+            fetch_progress: UpdateProgress::default(),
             scrollbar: DependabotScrollbar::default(),
-            chunk_height: 0,
             error: None,
+            screen_before_error: CurrentScreen::default(),
+            screen_before_credentials: CurrentScreen::default(),
+            screen_after_credentials: CurrentScreen::default(),
+            screen_before_update: CurrentScreen::default(),
+        }
+    }
+
+    /// The repositories as they should be displayed, scoped to the selected group and honoring
+    /// the fixable-only filter.
+    pub fn visible_repositories(&self) -> Cow<'_, RepositoryList> {
+        let mut repositories = Cow::Borrowed(&self.repositories);
+        // repositories from an earlier fetch can still be in the list after being excluded
+        if self.hide_archived || self.config.fetch.has_exclusions() {
+            repositories =
+                Cow::Owned(repositories.without_excluded(&self.config.fetch, self.hide_archived));
+        }
+        if let Some(members) = self
+            .selected_group
+            .as_ref()
+            .and_then(|group| self.config.groups.get(group))
+        {
+            repositories = Cow::Owned(repositories.in_group(members));
+        }
+        if let Some(topic) = &self.selected_topic {
+            repositories = Cow::Owned(repositories.with_topic(topic));
+        }
+        if !self.repository_search.query.is_empty() {
+            repositories = Cow::Owned(repositories.matching_name(&self.repository_search.query));
+        }
+        if self.fixable_only {
+            repositories = Cow::Owned(repositories.fixable_only());
+        }
+
+        repositories
+    }
+
+    /// The repository being viewed as it should be displayed, honoring the fixable-only filter.
+    pub fn visible_current_repository(&self) -> Option<Cow<'_, Repository>> {
+        let repo = self.current_repository.as_ref()?;
+        if self.fixable_only {
+            Some(Cow::Owned(repo.fixable_only()))
+        } else {
+            Some(Cow::Borrowed(repo))
+        }
+    }
+
+    /// Switch between showing and hiding archived repositories.
+    pub fn toggle_hide_archived(&mut self) {
+        self.hide_archived = !self.hide_archived;
+        self.repositories.select(0);
+        self.reset_filtered_selections();
+    }
+
+    /// Switch between showing every alert and only the alerts that can be fixed by upgrading.
+    pub fn toggle_fixable_only(&mut self) {
+        self.fixable_only = !self.fixable_only;
+        self.reset_filtered_selections();
+    }
+
+    /// Switch to the next built-in theme, keeping any colors the config changes.
+    pub fn next_theme(&mut self) {
+        self.theme_name = self.theme_name.next();
+        self.theme = self.config.theme.resolve(self.theme_name);
+        self.show_status(
+            NotificationLevel::Info,
+            format!("Switched to the {} theme", self.theme_name),
+        );
+    }
+
+    /// Scope the app to the next repository group from the config, cycling back to all
+    /// repositories after the last group.
+    pub fn next_group(&mut self) {
+        let mut groups = self.config.groups.keys();
+        self.selected_group = match &self.selected_group {
+            None => groups.next(),
+            Some(selected_group) => groups.skip_while(|group| *group != selected_group).nth(1),
+        }
+        .cloned();
+        self.repositories.select(0);
+        self.reset_filtered_selections();
+    }
+
+    /// Filter the app to the next GitHub topic used by the repositories, cycling back to all
+    /// repositories after the last topic.
+    pub fn next_topic(&mut self) {
+        let topics = self.repositories.topics();
+        let mut topics = topics.iter();
+        self.selected_topic = match &self.selected_topic {
+            None => topics.next(),
+            Some(selected_topic) => topics.skip_while(|topic| **topic != selected_topic).nth(1),
+        }
+        .map(|topic| topic.to_string());
+        self.repositories.select(0);
+        self.reset_filtered_selections();
+    }
+
+    /// Sort the repository list by the next sort mode, keeping the selected repository selected.
+    pub fn next_sort_mode(&mut self) {
+        let selected_id = self
+            .visible_repositories()
+            .get_selected_repository()
+            .map(|repo| repo.id);
+        let sort_mode = self.repositories.sort_mode().next();
+        self.repositories.set_sort_mode(sort_mode);
+
+        let selected_index = selected_id.and_then(|id| self.visible_repositories().position(id));
+        if let Some(index) = selected_index {
+            self.repositories.select(index);
+        }
+    }
+
+    pub fn push_repository_search(&mut self, c: char) {
+        self.repository_search.push(c);
+        self.repositories.select(0);
+    }
+
+    pub fn pop_repository_search(&mut self) {
+        self.repository_search.pop();
+        self.repositories.select(0);
+    }
+
+    /// Stop searching and show every repository again.
+    pub fn clear_repository_search(&mut self) {
+        self.repository_search = RepositorySearch::default();
+        self.repositories.select(0);
+    }
+
+    fn reset_filtered_selections(&mut self) {
+        // the filtered lists have different lengths, so start their selections over
+        match self.current_screen {
+            CurrentScreen::DistinctAlerts => self.open_distinct_alerts(),
+            CurrentScreen::CopyleftReport => self.open_copyleft_report(),
+            CurrentScreen::Dependencies => self.open_dependencies(),
+            _ => {}
+        }
+        if self.current_repository.is_some() {
+            self.reset_alert_scrollbar();
+        }
+    }
+
+    /// Open the Project view for the repository currently selected in the repository list.
+    pub fn open_selected_repository(&mut self) {
+        // the selection indexes the visible repositories, but the unfiltered repository is kept
+        // so filters can be changed while viewing it
+        let selected_id = self
+            .visible_repositories()
+            .get_selected_repository()
+            .map(|repo| repo.id);
+        if let Some(repo) = self
+            .repositories
+            .repos
+            .iter()
+            .find(|repo| Some(repo.id) == selected_id)
+        {
+            self.current_repository = Some(repo.clone());
+            self.current_screen = CurrentScreen::Project;
+            self.marked_alerts.clear();
+            self.reset_alert_scrollbar();
+
+            trace_dbg!(level: tracing::Level::INFO, self.scrollbar.get_length());
+        }
+    }
+
+    /// Select the repository with the highest risk score and open its Project view, for
+    /// `--worst-first`. Only the overview is skipped, so the setup wizard is left in place.
+    pub fn open_highest_risk_repository(&mut self) {
+        if !matches!(self.current_screen, CurrentScreen::Overview) {
+            return;
+        }
+        let highest_risk_index = self
+            .visible_repositories()
+            .highest_risk_index(&self.config.risk);
+        if let Some(index) = highest_risk_index {
+            self.repositories.select(index);
+            self.open_selected_repository();
+        }
+    }
+
+    pub fn next_repository(&mut self) {
+        self.move_repository_selection(Movement::Down);
+    }
+
+    pub fn previous_repository(&mut self) {
+        self.move_repository_selection(Movement::Up);
+    }
+
+    /// Select the clicked repository, or open it if it was already selected.
+    pub fn click_repository(&mut self, index: usize) {
+        let already_selected = self.repositories.highlighted_section().is_none()
+            && self.repositories.selected() == Some(index);
+        if already_selected {
+            self.open_selected_repository();
+        } else {
+            self.repositories.select(index);
+        }
+    }
+
+    /// Highlight the clicked section header and collapse or expand its section.
+    pub fn click_section(&mut self, section: RepositorySection) {
+        self.highlight_section(section);
+        self.toggle_repository_section();
+    }
+
+    /// The rows of the repository list, with the collapsed sections' repositories left out.
+    pub fn repository_rows(&self) -> Vec<TreeRow> {
+        tree_rows(&self.visible_repositories(), &self.collapsed_sections)
+    }
+
+    /// The index of the highlighted row in `rows`: the highlighted section header, the header of
+    /// the selected repository's section if it's collapsed, or else the selected repository.
+    pub fn highlighted_row(&self, rows: &[TreeRow]) -> Option<usize> {
+        let repositories = self.visible_repositories();
+        let selected_repo = repositories.get_selected_repository();
+        let section = repositories.highlighted_section().cloned().or_else(|| {
+            selected_repo
+                .map(RepositorySection::of)
+                .filter(|section| self.collapsed_sections.contains(section))
+        });
+
+        section
+            .and_then(|section| section_row(rows, &section))
+            .or_else(|| {
+                let selected = repositories.selected()?;
+                rows.iter()
+                    .position(|row| *row == TreeRow::Repository(selected))
+            })
+    }
+
+    /// Move the repository list's highlight over its rows, section headers included.
+    fn move_repository_selection(&mut self, movement: Movement) {
+        let rows = self.repository_rows();
+        let Some(highlighted) = self.highlighted_row(&rows) else {
+            return;
+        };
+        match &rows[movement.apply(highlighted, rows.len())] {
+            TreeRow::Section(header) => self.highlight_section(header.section.clone()),
+            TreeRow::Repository(index) => self.repositories.select(*index),
+        }
+    }
+
+    fn highlight_section(&mut self, section: RepositorySection) {
+        let first_index = self
+            .visible_repositories()
+            .repos
+            .iter()
+            .position(|repo| RepositorySection::of(repo) == section);
+        if let Some(index) = first_index {
+            self.repositories.highlight_section(index, section);
+        }
+    }
+
+    /// Open the selected repository, or collapse or expand the highlighted section.
+    pub fn open_highlighted_row(&mut self) {
+        let rows = self.repository_rows();
+        match self.highlighted_row(&rows).map(|row| &rows[row]) {
+            Some(TreeRow::Section(_)) => self.toggle_repository_section(),
+            Some(TreeRow::Repository(_)) => self.open_selected_repository(),
+            None => {}
+        }
+    }
+
+    /// Collapse the section of the repository list the highlight is in, or expand it if it's
+    /// collapsed, leaving its header highlighted.
+    pub fn toggle_repository_section(&mut self) {
+        let rows = self.repository_rows();
+        let section = match self.highlighted_row(&rows).map(|row| &rows[row]) {
+            Some(TreeRow::Section(header)) => header.section.clone(),
+            Some(TreeRow::Repository(index)) => {
+                RepositorySection::of(&self.visible_repositories().repos[*index])
+            }
+            None => return,
+        };
+        // only sections with a header can be collapsed, or there'd be nothing to expand them from
+        if section_row(&rows, &section).is_none() {
+            return;
+        }
+
+        if !self.collapsed_sections.remove(&section) {
+            self.collapsed_sections.insert(section.clone());
+        }
+        self.highlight_section(section);
+    }
+
+    /// Collapse every section of the repository list, or expand them all if they're all
+    /// collapsed already.
+    pub fn toggle_all_repository_sections(&mut self) {
+        let rows = self.repository_rows();
+        let headers: Vec<&SectionHeader> = rows
+            .iter()
+            .filter_map(|row| match row {
+                TreeRow::Section(header) => Some(header),
+                TreeRow::Repository(_) => None,
+            })
+            .collect();
+        if headers.iter().all(|header| header.collapsed) {
+            self.collapsed_sections.clear();
+            return;
+        }
+
+        let highlighted_section = match self.highlighted_row(&rows).map(|row| &rows[row]) {
+            Some(TreeRow::Section(header)) => Some(header.section.clone()),
+            Some(TreeRow::Repository(index)) => Some(RepositorySection::of(
+                &self.visible_repositories().repos[*index],
+            )),
+            None => None,
+        };
+        self.collapsed_sections
+            .extend(headers.iter().map(|header| header.section.clone()));
+        // keep the highlight on a row that's still shown
+        if let Some(section) =
+            highlighted_section.filter(|section| self.collapsed_sections.contains(section))
+        {
+            self.highlight_section(section);
+        }
+    }
+
+    /// Whether the current screen is taking text input, so keys shouldn't trigger global actions.
+    pub fn is_editing_text(&self) -> bool {
+        match self.current_screen {
+            CurrentScreen::Credentials
+            | CurrentScreen::Setup
+            | CurrentScreen::Dismiss
+            | CurrentScreen::Export => true,
+            CurrentScreen::ProjectList => self.repository_search.editing,
+            CurrentScreen::DependabotDetails => self.alert_filter.package_search.editing,
+            _ => false,
+        }
+    }
+
+    /// Whether the current screen needs plain letter keys, like text input or a (y/n) question.
+    pub fn is_prompting(&self) -> bool {
+        self.is_editing_text()
+            || matches!(
+                self.current_screen,
+                CurrentScreen::Update | CurrentScreen::ConfirmOpenAlerts
+            )
+    }
+
+    pub fn has_credentials(&self) -> bool {
+        self.profile.has_credentials()
+    }
+
+    /// Ask for credentials, going on to `next` once they're entered. The saved data can be
+    /// browsed without any, so they're only asked for when something needs GitHub.
+    pub fn prompt_for_credentials(&mut self, next: CurrentScreen) {
+        self.screen_before_credentials = self.current_screen;
+        self.screen_after_credentials = next;
+        self.current_screen = CurrentScreen::Credentials;
+    }
+
+    /// Close the credentials prompt without entering any, going back to browsing.
+    pub fn close_credentials_prompt(&mut self) {
+        self.current_screen = self.screen_before_credentials;
+    }
+
+    /// Apply the credentials entered in the prompt, saving the token to the OS keyring if
+    /// requested.
+    pub fn submit_credentials(&mut self) {
+        if !self.credentials_input.is_complete() {
+            return;
+        }
+        self.profile.username = self.credentials_input.username.trim().to_string();
+        self.profile.token = self.credentials_input.token.trim().to_string();
+
+        let persist = self.credentials_input.persist;
+        self.credentials_input = CredentialsInput::default();
+        self.current_screen = self.screen_after_credentials;
+
+        if persist {
+            self.persist_credentials();
+        }
+    }
+
+    fn persist_credentials(&mut self) {
+        // only the default profile's username comes from `.env`
+        let saved = if self.profile.is_default() {
+            persist_credentials(&self.profile.username, &self.profile.token)
+        } else {
+            save_keyring_token(&self.profile.username, &self.profile.token)
+        };
+        if let Err(e) = saved {
+            self.report_error(format!("Failed to save the token to the keyring: {}", e));
+        }
+    }
+
+    /// Start logging in with the device flow of the app configured as `auth.client_id`, showing
+    /// the Login popup until it finishes.
+    pub fn start_login(&mut self) {
+        if self.config.offline {
+            self.show_status(NotificationLevel::Warning, OFFLINE_MESSAGE);
+            return;
+        }
+        let Some(client_id) = self.config.auth.client_id.clone() else {
+            self.report_error(
+                "Set client_id in the [auth] section of the config file to log in with GitHub"
+                    .to_string(),
+            );
+            return;
+        };
+
+        let (tx, rx) = mpsc::unbounded_channel();
+        let task = self.runtime.spawn(async move {
+            let client = Client::new();
+            let result = async {
+                let device_code = request_device_code(&client, &client_id).await?;
+                let _ = tx.send(LoginMessage::Code(device_code.clone()));
+                let token = poll_for_token(&client, &client_id, &device_code).await?;
+                let username = fetch_username(&client, &token).await?;
+                Ok((username, token))
+            }
+            .await;
+            // the receiver is only dropped if the login was cancelled
+            let _ = tx.send(LoginMessage::Complete(result));
+        });
+
+        self.login = Some(DeviceLogin {
+            task,
+            messages: rx,
+            code: None,
+            started_from: self.current_screen,
+        });
+        self.current_screen = CurrentScreen::Login;
+    }
+
+    /// Handle any messages from the running login, opening the verification page once the code
+    /// arrives and saving the token once it's authorized.
+    pub fn poll_login(&mut self) {
+        let Some(login) = &mut self.login else {
+            return;
+        };
+
+        loop {
+            match login.messages.try_recv() {
+                Ok(LoginMessage::Code(code)) => {
+                    if let Err(e) = open::that(&code.verification_uri) {
+                        let browser_error =
+                            format!("Failed to open {}: {}", code.verification_uri, e);
+                        trace_dbg!(level: tracing::Level::WARN, browser_error);
+                    }
+                    login.code = Some(code);
+                }
+                Ok(LoginMessage::Complete(result)) => {
+                    let started_from = login.started_from;
+                    self.login = None;
+                    match result {
+                        Ok((username, token)) if started_from == CurrentScreen::Setup => {
+                            self.setup.username = username;
+                            self.setup.token = token;
+                            self.setup.next_step();
+                            self.current_screen = CurrentScreen::Setup;
+                        }
+                        Ok((username, token)) => {
+                            self.profile.username = username;
+                            self.profile.token = token;
+                            self.credentials_input = CredentialsInput::default();
+                            self.current_screen = self.screen_after_credentials;
+                            self.persist_credentials();
+                        }
+                        Err(e) => {
+                            self.current_screen = started_from;
+                            self.report_error(format!("Failed to log in with GitHub: {}", e));
+                        }
+                    }
+                    return;
+                }
+                Err(TryRecvError::Empty) => return,
+                Err(TryRecvError::Disconnected) => {
+                    self.current_screen = login.started_from;
+                    self.login = None;
+                    self.report_error("The GitHub login stopped unexpectedly".to_string());
+                    return;
+                }
+            }
+        }
+    }
+
+    /// Stop waiting for the login and go back to the credentials prompt or setup wizard.
+    pub fn cancel_login(&mut self) {
+        if let Some(login) = self.login.take() {
+            login.task.abort();
+            self.current_screen = login.started_from;
+        }
+    }
+
+    /// Whether this is the first run: the default profile has no credentials and there's no
+    /// config file yet.
+    pub fn needs_setup(&self) -> bool {
+        self.profile.is_default()
+            && !self.has_credentials()
+            && !self.config.offline
+            && self
+                .config_path
+                .as_ref()
+                .is_some_and(|config_path| !config_path.exists())
+    }
+
+    pub fn open_setup(&mut self) {
+        self.setup = SetupWizard::new(&self.config.data_dir());
+        self.current_screen = CurrentScreen::Setup;
+    }
+
+    /// Move on to the setup wizard's next step once the current one is answered, finishing the
+    /// setup after the last.
+    pub fn next_setup_step(&mut self) {
+        if !self.setup.is_step_complete() {
+            return;
+        }
+        if self.setup.is_last_step() {
+            self.finish_setup();
+        } else {
+            self.setup.next_step();
+        }
+    }
+
+    /// Go back a step on the setup wizard, or skip the setup from the first step to browse
+    /// without credentials.
+    pub fn previous_setup_step(&mut self) {
+        if !self.setup.previous_step() {
+            self.setup = SetupWizard::default();
+            self.current_screen = CurrentScreen::Overview;
+        }
+    }
+
+    /// Save the setup wizard's answers to the config file and the token to the OS keyring, then
+    /// fetch the repositories straight away.
+    fn finish_setup(&mut self) {
+        let username = self.setup.username.trim().to_string();
+        let token = self.setup.token.trim().to_string();
+        let organizations = self.setup.organizations();
+        let data_dir = self.setup.data_dir();
+        let custom_data_dir = (data_dir != get_default_data_dir()).then_some(data_dir.as_path());
+
+        if let Err(e) = std::fs::create_dir_all(&data_dir) {
+            self.report_error(format!("Failed to create {}: {}", data_dir.display(), e));
+            return;
+        }
+        if let Some(config_path) = &self.config_path {
+            if let Err(e) = save_setup(config_path, &username, &organizations, custom_data_dir) {
+                self.report_error(format!("Failed to save the config file: {}", e));
+                return;
+            }
+        }
+        if let Err(e) = save_keyring_token(&username, &token) {
+            self.show_status(
+                NotificationLevel::Warning,
+                format!(
+                    "The token couldn't be saved to the keyring, so it'll be asked for next time: {}",
+                    e
+                ),
+            );
+        }
+
+        self.config.auth.username = Some(username.clone());
+        self.config.fetch.organizations = organizations;
+        self.config.data_dir = Some(data_dir.clone());
+        self.profile.username = username;
+        self.profile.token = token;
+        self.profile.data_dir = data_dir.clone();
+        self.credentials_input = CredentialsInput::with_username(&self.profile.username);
+        self.setup = SetupWizard::default();
+
+        set_data_directory(data_dir);
+        let saved_data = load_saved_repositories(&mut self.notifications);
+        let sort_mode = self.repositories.sort_mode();
+        self.repositories = RepositoryList::with_respositories(saved_data.repositories);
+        self.repositories.set_sort_mode(sort_mode);
+        self.last_updated = saved_data.fetched_at;
+        self.alert_trend = load_trend();
+        self.start_fetch();
+    }
+
+    /// A warning to display if the PAT expires within `TOKEN_EXPIRATION_WARNING_DAYS`.
+    pub fn token_expiration_warning(&self) -> Option<String> {
+        let expiration = self.token_expiration?;
+        let days_left = expiration.signed_duration_since(Utc::now()).num_days();
+
+        if days_left < 0 {
+            Some("PAT has expired".to_string())
+        } else if days_left <= TOKEN_EXPIRATION_WARNING_DAYS {
+            Some(format!("PAT expires in {} day(s)", days_left))
+        } else {
+            None
+        }
+    }
+
+    pub fn open_profiles(&mut self) {
+        self.profiles = self.config.profiles();
+        let current_index = self
+            .profiles
+            .iter()
+            .position(|profile| profile.name == self.profile.name);
+        self.profiles_state = ListState::default();
+        self.profiles_state.select(current_index.or(Some(0)));
+        self.current_screen = CurrentScreen::Profiles;
+    }
+
+    /// Switch to the profile selected on the profile switcher, loading its saved repositories.
+    pub fn switch_to_selected_profile(&mut self) {
+        let Some(profile) = self
+            .profiles_state
+            .selected()
+            .and_then(|index| self.profiles.get(index).cloned())
+        else {
+            return;
+        };
+        if self.fetching.is_some() {
+            // the running fetch saves its snapshot to the current profile's data directory
+            self.show_status(
+                NotificationLevel::Warning,
+                "Wait for the refresh to finish before switching profiles",
+            );
+            return;
+        }
+
+        set_data_directory(profile.data_dir.clone());
+        let sort_mode = self.repositories.sort_mode();
+        let saved_data = load_saved_repositories(&mut self.notifications);
+        self.repositories = RepositoryList::with_respositories(saved_data.repositories);
+        self.repositories.set_sort_mode(sort_mode);
+        self.last_updated = saved_data.fetched_at;
+        self.alert_trend = load_trend();
+        self.current_repository = None;
+        self.marked_alerts.clear();
+        self.last_changes.clear();
+        self.token_expiration = None;
+        self.repositories_truncated = false;
+        self.credentials_input = CredentialsInput::with_username(&profile.username);
+        self.current_screen = CurrentScreen::Overview;
+        self.show_status(
+            NotificationLevel::Info,
+            format!("Switched to profile {}", profile.name),
+        );
+        self.profile = profile;
+    }
+
+    pub fn open_changes(&mut self) {
+        self.changes_state = ListState::default();
+        if !self.last_changes.is_empty() {
+            self.changes_state.select(Some(0));
+        }
+        self.current_screen = CurrentScreen::Changes;
+    }
+
+    pub fn open_distinct_alerts(&mut self) {
+        self.distinct_alerts_state = ListState::default();
+        if !self.visible_repositories().distinct_alerts().is_empty() {
+            self.distinct_alerts_state.select(Some(0));
+        }
+        self.current_screen = CurrentScreen::DistinctAlerts;
+    }
+
+    pub fn open_copyleft_report(&mut self) {
+        self.copyleft_report_state = ListState::default();
+        if !self
+            .visible_repositories()
+            .copyleft_vulnerable_dependencies()
+            .is_empty()
+        {
+            self.copyleft_report_state.select(Some(0));
+        }
+        self.current_screen = CurrentScreen::CopyleftReport;
+    }
+
+    pub fn open_dependencies(&mut self) {
+        self.dependencies_state = ListState::default();
+        self.dependency_repositories_state = None;
+        if !self
+            .visible_repositories()
+            .vulnerable_dependencies()
+            .is_empty()
+        {
+            self.dependencies_state.select(Some(0));
+        }
+        self.current_screen = CurrentScreen::Dependencies;
+    }
+
+    /// The package selected on the Dependencies screen.
+    pub fn selected_dependency(&self) -> Option<VulnerableDependency> {
+        let index = self.dependencies_state.selected()?;
+        self.visible_repositories()
+            .vulnerable_dependencies()
+            .into_iter()
+            .nth(index)
+    }
+
+    /// Move into the selected package's repositories, or open the selected repository if
+    /// already there.
+    pub fn drill_into_dependency(&mut self) {
+        let Some(dependency) = self.selected_dependency() else {
+            return;
+        };
+
+        match &self.dependency_repositories_state {
+            None => {
+                let mut state = ListState::default();
+                state.select(Some(0));
+                self.dependency_repositories_state = Some(state);
+            }
+            Some(state) => {
+                let affected = state
+                    .selected()
+                    .and_then(|index| dependency.repositories.get(index));
+                if let Some(affected) = affected {
+                    self.open_repository(affected.id);
+                }
+            }
+        }
+    }
+
+    /// Go back from a package's repositories to the list of packages.
+    pub fn leave_dependency(&mut self) {
+        self.dependency_repositories_state = None;
+    }
+
+    fn focused_dependency_list_len(&self) -> usize {
+        match self.dependency_repositories_state {
+            Some(_) => self
+                .selected_dependency()
+                .map_or(0, |dependency| dependency.repositories.len()),
+            None => self.visible_repositories().vulnerable_dependencies().len(),
+        }
+    }
+
+    fn focused_dependency_list_state(&mut self) -> &mut ListState {
+        self.dependency_repositories_state
+            .as_mut()
+            .unwrap_or(&mut self.dependencies_state)
+    }
+
+    /// Select the repository with `id` in the repository list and open its Project view.
+    fn open_repository(&mut self, id: u32) {
+        let index = self.visible_repositories().position(id);
+        if let Some(index) = index {
+            self.repositories.select(index);
+            self.open_selected_repository();
+        }
+    }
+
+    /// Log an error, add it to the notification drawer, and show it in the error popup.
+    /// Show a message in the footer for a few seconds, keeping it in the notification log.
+    pub fn show_status(&mut self, level: NotificationLevel, message: impl Into<String>) {
+        let message = message.into();
+        self.status.push(level, message.clone());
+        self.notifications.push(level, message);
+    }
+
+    pub fn report_error(&mut self, message: String) {
+        trace_dbg!(level: tracing::Level::ERROR, &message);
+        self.notifications
+            .push(NotificationLevel::Error, message.clone());
+        self.error = Some(message);
+        if !matches!(self.current_screen, CurrentScreen::Error) {
+            self.screen_before_error = self.current_screen;
+        }
+        self.current_screen = CurrentScreen::Error;
+    }
+
+    /// Close the error popup and go back to the screen it was shown over.
+    pub fn dismiss_error(&mut self) {
+        self.error = None;
+        self.current_screen = self.screen_before_error;
+    }
+
+    /// Ask whether to update the repositories, unless the app is offline, asking for credentials
+    /// first if there aren't any.
+    pub fn open_update_prompt(&mut self) {
+        if self.config.offline {
+            self.show_status(NotificationLevel::Warning, OFFLINE_MESSAGE);
+            return;
+        }
+        if self.fetching.is_some() {
+            self.show_running_fetch();
+            return;
+        }
+        self.screen_before_update = self.current_screen;
+        if !self.has_credentials() {
+            self.prompt_for_credentials(CurrentScreen::Update);
+            return;
+        }
+        self.current_screen = CurrentScreen::Update;
+    }
+
+    /// Bring a fetch running in the background, like the periodic refresh, up in the Updating
+    /// popup rather than starting another alongside it.
+    fn show_running_fetch(&mut self) {
+        self.background_fetch = false;
+        self.current_screen = CurrentScreen::Updating;
+    }
+
+    /// Fetch every repository in the background, showing the Updating popup until it finishes.
+    pub fn start_fetch(&mut self) {
+        let provider = self.profile.security_provider();
+        let previous_repos = self.repositories.repos.clone();
+        let config = self.config.clone();
+
+        self.spawn_fetch(false, move |progress, cancellation| async move {
+            provider
+                .fetch_repos(&previous_repos, &config.fetch, &*progress, &cancellation)
+                .await
+        });
+    }
+
+    /// Fetch every repository without blocking the UI, for the periodic refresh. A spinner in
+    /// the corner stands in for the Updating popup.
+    pub fn start_background_refresh(&mut self) {
+        let provider = self.profile.security_provider();
+        let previous_repos = self.repositories.repos.clone();
+        let config = self.config.clone();
+
+        self.spawn_fetch(true, move |progress, cancellation| async move {
+            provider
+                .fetch_repos(&previous_repos, &config.fetch, &*progress, &cancellation)
+                .await
+        });
+    }
+
+    /// Fetch only the repositories older than the configured `stale_after_hours`.
+    pub fn start_stale_refresh(&mut self) {
+        let provider = self.profile.security_provider();
+        let previous_repos = self.repositories.repos.clone();
+        let config = self.config.clone();
+        let stale_after = Duration::hours(self.config.stale_after_hours);
+
+        self.spawn_fetch(false, move |progress, cancellation| async move {
+            fetch_stale_repos(
+                &provider,
+                &previous_repos,
+                &config.fetch,
+                stale_after,
+                &*progress,
+                &cancellation,
+            )
+            .await
+        });
+    }
+
+    fn spawn_fetch<F, Fut>(&mut self, background: bool, fetch: F)
+    where
+        F: FnOnce(Box<dyn Fn(FetchProgress) + Send + Sync>, CancellationToken) -> Fut,
+        Fut: Future<Output = Result<FetchOutcome, DependabotTrackerError>> + Send + 'static,
+    {
+        if self.config.offline {
+            self.show_status(NotificationLevel::Warning, OFFLINE_MESSAGE);
+            return;
+        }
+        // a second fetch would leave the first one running unseen, using up the rate limit
+        if self.fetching.is_some() {
+            if !background {
+                self.show_running_fetch();
+            }
+            return;
+        }
+        let (tx, rx) = mpsc::unbounded_channel();
+        let progress_tx = tx.clone();
+        let cancellation = CancellationToken::new();
+        let fetch = fetch(
+            Box::new(move |progress| {
+                let _ = progress_tx.send(AppEvent::FetchProgress(progress));
+            }),
+            cancellation.clone(),
+        );
+        self.runtime.spawn(async move {
+            // a cancelled fetch still finishes, with the repositories it already has
+            let result = fetch.await;
+            // the receiver is only dropped if the app has already quit
+            let _ = tx.send(AppEvent::FetchDone(result));
+        });
+
+        if !background {
+            self.current_screen = CurrentScreen::Updating;
+        }
+        self.background_fetch = background;
+        self.pre_fetch_repositories = Some(self.repositories.repos.clone());
+        self.fetch_progress = UpdateProgress::default();
+        self.fetching = Some(rx);
+        self.fetch_cancellation = Some(cancellation);
+        self.last_fetch_started = Instant::now();
+    }
+
+    /// The next message from the running fetch, if it has sent one.
+    pub fn next_fetch_event(&mut self) -> Result<Option<AppEvent>, DependabotTrackerError> {
+        let Some(rx) = &mut self.fetching else {
+            return Ok(None);
+        };
+
+        match rx.try_recv() {
+            Ok(event) => Ok(Some(event)),
+            Err(TryRecvError::Empty) => Ok(None),
+            // The fetch task has panicked or been unexpectedly terminated
+            Err(TryRecvError::Disconnected) => Err(DependabotTrackerError::Other(
+                "Fetch task terminated unexpectedly".to_string(),
+            )),
+        }
+    }
+
+    /// Whether the Updating popup is showing for a fetch started from the Update prompt.
+    pub fn is_updating(&self) -> bool {
+        self.fetching.is_some() && !self.background_fetch
+    }
+
+    pub fn on_fetch_done(&mut self, result: Result<FetchOutcome, DependabotTrackerError>) {
+        self.fetching = None;
+        self.fetch_cancellation = None;
+        self.fetch_progress = UpdateProgress::default();
+        if !self.background_fetch {
+            self.current_screen = CurrentScreen::Overview;
+        }
+        match result {
+            Ok(outcome) => self.on_fetch_complete(outcome),
+            // a failed background refresh shouldn't interrupt whatever is on screen
+            Err(e) if self.background_fetch => {
+                let refresh_error = format!("Automatic refresh failed: {}", e);
+                trace_dbg!(level: tracing::Level::ERROR, &refresh_error);
+                self.show_status(NotificationLevel::Error, refresh_error);
+            }
+            Err(e) => match e.advice() {
+                Some(advice) => {
+                    self.report_error(format!("Failed to update repositories: {}. {}", e, advice))
+                }
+                None => self.report_error(format!("Failed to update repositories: {}", e)),
+            },
+        }
+        // a failed fetch leaves the repositories it streamed in, which are still the newest
+        self.pre_fetch_repositories = None;
+    }
+
+    /// How long until the configured `refresh_interval_minutes` calls for another fetch, if one
+    /// should happen at all. Refreshes wait while a prompt or the error popup is open.
+    pub fn time_until_refresh(&self) -> Option<std::time::Duration> {
+        let interval = self.config.refresh_interval()?;
+        if self.config.offline
+            || !self.has_credentials()
+            || self.fetching.is_some()
+            || self.is_prompting()
+            || matches!(self.current_screen, CurrentScreen::Error)
+        {
+            return None;
+        }
+
+        Some(interval.saturating_sub(self.last_fetch_started.elapsed()))
+    }
+
+    /// Show a repository as soon as the running fetch has it, rather than waiting for the rest.
+    pub fn on_repository_fetched(&mut self, repo: Repository) {
+        let selected_id = self
+            .visible_repositories()
+            .get_selected_repository()
+            .map(|repo| repo.id);
+        self.repositories.upsert(repo);
+        let selected_index = selected_id.and_then(|id| self.visible_repositories().position(id));
+        if let Some(index) = selected_index {
+            self.repositories.select(index);
+        }
+        self.refresh_current_repository();
+    }
+
+    /// Swap the open repository for its copy in the list, which may have been fetched again.
+    fn refresh_current_repository(&mut self) {
+        let Some(current_id) = self.current_repository.as_ref().map(|repo| repo.id) else {
+            return;
+        };
+        let refreshed_repo = self
+            .repositories
+            .repos
+            .iter()
+            .find(|repo| repo.id == current_id)
+            .cloned();
+        if let Some(refreshed_repo) = refreshed_repo {
+            self.current_repository = Some(refreshed_repo);
+            if self.visible_alerts().len() != self.scrollbar.get_length() {
+                self.reset_alert_scrollbar();
+            }
+        }
+    }
+
+    /// Let a fetch started from the Update prompt carry on in the background, so the list can
+    /// be used while the rest of the repositories come in.
+    pub fn send_fetch_to_background(&mut self) {
+        if !self.is_updating() {
+            return;
+        }
+        self.background_fetch = true;
+        self.current_screen = CurrentScreen::ProjectList;
+        self.show_status(
+            NotificationLevel::Info,
+            "Fetching in the background, repositories update as they come in",
+        );
+    }
+
+    /// Stop the fetch started from the Update prompt and go back to the screen it was started
+    /// from. The fetch winds down in the background, saving the repositories it already fetched
+    /// while the rest keep their saved data.
+    pub fn cancel_fetch(&mut self) {
+        if !self.is_updating() {
+            return;
+        }
+        if let Some(cancellation) = self.fetch_cancellation.take() {
+            cancellation.cancel();
+        }
+        self.background_fetch = true;
+        self.current_screen = self.screen_before_update;
+    }
+
+    /// Swap in the freshly fetched repositories and note what changed.
+    pub fn on_fetch_complete(&mut self, outcome: FetchOutcome) {
+        // repositories streamed in during the fetch are already in the list, so the changes are
+        // worked out against the list from before it started
+        let previous_repos = self
+            .pre_fetch_repositories
+            .take()
+            .unwrap_or_else(|| self.repositories.repos.clone());
+        let new_critical_alerts = outcome
+            .repositories
+            .count_new_open_alerts(&previous_repos, DependabotSeverity::Critical);
+        let new_alerts = outcome.repositories.new_open_alerts(&previous_repos).len();
+        // the first fetch has nothing to compare against, so every alert would look new
+        let has_previous_fetch = !previous_repos.is_empty();
+        if has_previous_fetch {
+            notify_new_alerts(
+                &self.config.desktop_notifications,
+                &outcome.repositories.new_open_alerts(&previous_repos),
+            );
+        }
+        let webhook_summary = has_previous_fetch
+            .then(|| {
+                WebhookSummary::new(
+                    &self.config.webhook,
+                    &self.config.templates.webhook_summary,
+                    TemplateContext::from_repositories(
+                        &outcome.repositories,
+                        new_critical_alerts,
+                        &self.config.risk,
+                    ),
+                    &outcome.repositories,
+                    &previous_repos,
+                )
+            })
+            .flatten();
+        if let Some(summary) = webhook_summary {
+            let webhook_config = self.config.webhook.clone();
+            self.runtime.spawn(async move {
+                if let Err(e) =
+                    post_webhook_summary(&webhook_config, &summary, &Client::new()).await
+                {
+                    let webhook_error = format!("Failed to post to webhook: {}", e);
+                    trace_dbg!(level: tracing::Level::WARN, webhook_error);
+                }
+            });
+        }
+        let advisory_ids = uncached_advisory_ids(
+            outcome
+                .repositories
+                .repos
+                .iter()
+                .flat_map(|repo| repo.dependabots.iter()),
+        );
+        if !advisory_ids.is_empty() {
+            self.runtime
+                .spawn(enrich_advisories(advisory_ids, Client::new()));
+        }
+        let changes = record_alert_events(&previous_repos, &outcome.repositories.repos);
+
+        let selected_id = self
+            .visible_repositories()
+            .get_selected_repository()
+            .map(|repo| repo.id);
+        let sort_mode = self.repositories.sort_mode();
+        self.repositories = outcome.repositories;
+        self.repositories.set_sort_mode(sort_mode);
+        self.last_updated = Some(Utc::now());
+        // keep the same repository selected, and the open one current, across the new list
+        let selected_index = selected_id.and_then(|id| self.visible_repositories().position(id));
+        if let Some(index) = selected_index {
+            self.repositories.select(index);
+        }
+        self.refresh_current_repository();
+        self.alert_trend = load_trend();
+        if !outcome.partial {
+            self.token_expiration = outcome.token_expiration;
+            self.repositories_truncated = outcome.truncated;
+        }
+
+        if outcome.cancelled {
+            let message = if outcome.report.succeeded == 0 {
+                "Update cancelled before any repositories were fetched".to_string()
+            } else {
+                format!(
+                    "Update cancelled: {} repositories were fetched, the rest show their saved data",
+                    outcome.report.succeeded
+                )
+            };
+            self.status.push(NotificationLevel::Warning, message);
+        } else if has_previous_fetch {
+            self.status.push(
+                NotificationLevel::Info,
+                format!("Update complete: {} new alerts", new_alerts),
+            );
+        } else {
+            self.status.push(NotificationLevel::Info, "Update complete");
+        }
+        if let Some(failure_summary) = outcome.report.failure_summary() {
+            self.status.push(
+                NotificationLevel::Warning,
+                format!("{} (see notifications)", failure_summary),
+            );
+        }
+
+        let context = TemplateContext::from_repositories(
+            &self.repositories,
+            new_critical_alerts,
+            &self.config.risk,
+        );
+        self.notifications.push(
+            NotificationLevel::Info,
+            render_template(&self.config.templates.refresh_summary, &context),
+        );
+        if new_critical_alerts > 0 {
+            self.notifications.push(
+                NotificationLevel::Warning,
+                format!("{} new critical alert(s) found", new_critical_alerts),
+            );
+        }
+        if self.repositories_truncated {
+            self.notifications.push(
+                NotificationLevel::Warning,
+                "Repositories were limited by max_repositories",
+            );
+        }
+        for warning in outcome.warnings {
+            self.notifications.push(NotificationLevel::Warning, warning);
+        }
+        for (repo_name, error) in outcome.report.failed {
+            self.notifications.push(
+                NotificationLevel::Warning,
+                format!("Failed to fetch {}: {}", repo_name, error),
+            );
+        }
+        let inaccessible_repos = self
+            .repositories
+            .repository_names_with_status(AlertsStatus::NoAccess);
+        if !inaccessible_repos.is_empty() {
+            let inaccessible_warning = format!(
+                "Token cannot access {} repositories",
+                inaccessible_repos.len()
+            );
+            self.notifications
+                .push(NotificationLevel::Warning, inaccessible_warning);
+        }
+        if let Some(warning) = self.token_expiration_warning() {
+            self.notifications.push(NotificationLevel::Warning, warning);
+        }
+
+        if has_previous_fetch {
+            self.last_changes = changes;
+            self.changes_state = ListState::default();
+            // a background refresh shouldn't interrupt whatever is on screen
+            if !self.last_changes.is_empty() && !self.background_fetch {
+                self.current_screen = CurrentScreen::WhatsNew;
+            }
+        }
+    }
+
+    pub fn copy_to_clipboard(&mut self, text: String) -> Result<(), arboard::Error> {
+        if self.clipboard.is_none() {
+            self.clipboard = Some(Clipboard::new()?);
+        }
+        if let Some(clipboard) = self.clipboard.as_mut() {
+            clipboard.set_text(text)?;
+        }
+
+        Ok(())
+    }
+
+    /// Copy a Markdown summary of the current repository to the clipboard.
+    pub fn copy_repository_summary(&mut self) {
+        let Some(summary) = self
+            .current_repository
+            .as_ref()
+            .map(|repo| repo.markdown_summary())
+        else {
+            return;
+        };
+
+        match self.copy_to_clipboard(summary) {
+            Ok(()) => self.show_status(NotificationLevel::Info, "Copied repository summary"),
+            Err(e) => {
+                let clipboard_error = format!("Failed to copy summary: {}", e);
+                self.report_error(clipboard_error);
+            }
+        }
+    }
+
+    /// Copy the URL of the highlighted alert, or of the repository on the Project screen.
+    pub fn copy_url(&mut self) {
+        let url = match self.current_screen {
+            CurrentScreen::Project => self
+                .current_repository
+                .as_ref()
+                .map(|repo| repo.url.clone()),
+            CurrentScreen::DependabotDetails => self.selected_alert().map(|alert| alert.html_url),
+            CurrentScreen::AlertDetail => self.detailed_alert().map(|alert| alert.html_url),
+            CurrentScreen::ResolvedAlerts
+            | CurrentScreen::CodeScanning
+            | CurrentScreen::SecretScanning => self.selected_security_alert_url(),
+            _ => None,
+        };
+        let Some(url) = url else {
+            return;
+        };
+
+        match self.copy_to_clipboard(url) {
+            Ok(()) => self.show_status(NotificationLevel::Info, "Copied URL"),
+            Err(e) => {
+                let clipboard_error = format!("Failed to copy URL: {}", e);
+                self.report_error(clipboard_error);
+            }
+        }
+    }
+
+    /// Write the Markdown security report for the visible repositories to the working directory.
+    pub fn write_security_report(&mut self) {
+        let now = Utc::now();
+        let report = SecurityReport::new(&self.visible_repositories(), now);
+        let path = format!("security-report-{}.md", now.format("%Y-%m-%d"));
+
+        match std::fs::write(&path, report.to_markdown()) {
+            Ok(()) => self.show_status(
+                NotificationLevel::Info,
+                format!("Wrote the security report to {}", path),
+            ),
+            Err(e) => {
+                let report_error = format!("Failed to write {}: {}", path, e);
+                self.report_error(report_error);
+            }
+        }
+    }
+
+    /// Write every alert in the visible repositories to `export_path` as CSV.
+    pub fn submit_export(&mut self) {
+        self.current_screen = CurrentScreen::Overview;
+        let repositories = self.visible_repositories().repos.clone();
+        let result = File::create(&self.export_path).and_then(|file| {
+            let mut writer = BufWriter::new(file);
+            export_csv(&mut writer, &repositories)?;
+            writer.flush()
+        });
+
+        match result {
+            Ok(()) => self.show_status(
+                NotificationLevel::Info,
+                format!("Exported the alerts to {}", self.export_path),
+            ),
+            Err(e) => {
+                let export_error = format!("Failed to export to {}: {}", self.export_path, e);
+                self.report_error(export_error);
+            }
+        }
+    }
+
+    /// The local scanner report for the repository being viewed, if one was passed with `--audit`.
+    pub fn current_audit_report(&self) -> Option<&AuditReport> {
+        let repo = self.current_repository.as_ref()?;
+        self.audit_reports.iter().find(|report| report.is_for(repo))
+    }
+
+    /// Open the comparison between the current repository's alerts and its local scanner report.
+    pub fn open_audit_comparison(&mut self) {
+        if self.current_audit_report().is_some() {
+            self.current_screen = CurrentScreen::AuditComparison;
+        } else if let Some(repo) = &self.current_repository {
+            let missing_report = format!(
+                "No audit report for {}, pass one with --audit {}=<file>",
+                repo.name, repo.name
+            );
+            self.show_status(NotificationLevel::Warning, missing_report);
+        }
+    }
+
+    /// Open the current repository's Dependabot security page in the browser.
+    pub fn open_security_tab(&mut self) {
+        let Some(url) = self
+            .current_repository
+            .as_ref()
+            .map(|repo| repo.security_tab_url())
+        else {
+            return;
+        };
+
+        if let Err(e) = open::that(&url) {
+            let browser_error = format!("Failed to open {}: {}", url, e);
+            self.report_error(browser_error);
+        }
+    }
+
+    /// The current repository's alerts listed in the dependabot details view, honoring its
+    /// severity and state filter.
+    pub fn visible_alerts(&self) -> Vec<Dependabot> {
+        let Some(repo) = self.visible_current_repository() else {
+            return Vec::new();
+        };
+        let mut alerts: Vec<Dependabot> = repo
+            .dependabots
+            .iter()
+            .filter(|dependabot| self.alert_filter.matches(dependabot))
+            .cloned()
+            .collect();
+        self.alert_filter.sort(&mut alerts);
+
+        alerts
+    }
+
+    /// The alert selected in the dependabot details view.
+    pub fn selected_alert(&self) -> Option<Dependabot> {
+        self.visible_alerts().get(self.scrollbar.position).cloned()
+    }
+
+    /// Show or hide the alerts of a severity in the dependabot details view.
+    pub fn toggle_alert_severity(&mut self, severity: DependabotSeverity) {
+        self.alert_filter.toggle_severity(severity);
+        self.reset_alert_scrollbar();
+    }
+
+    /// Cycle the order of the dependabot details view.
+    pub fn next_alert_sort(&mut self) {
+        self.alert_filter.next_sort();
+        self.reset_alert_scrollbar();
+    }
+
+    pub fn push_package_search(&mut self, c: char) {
+        self.alert_filter.package_search.push(c);
+        self.reset_alert_scrollbar();
+    }
+
+    pub fn pop_package_search(&mut self) {
+        self.alert_filter.package_search.pop();
+        self.reset_alert_scrollbar();
+    }
+
+    /// Stop filtering the dependabot details view by package name.
+    pub fn clear_package_search(&mut self) {
+        self.alert_filter.package_search = RepositorySearch::default();
+        self.reset_alert_scrollbar();
+    }
+
+    /// Show or hide dismissed and fixed alerts in the dependabot details view.
+    pub fn toggle_closed_alerts(&mut self) {
+        self.alert_filter.toggle_closed();
+        self.reset_alert_scrollbar();
+    }
+
+    /// Show or hide alerts on development-only dependencies in the dependabot details view.
+    pub fn toggle_development_alerts(&mut self) {
+        self.alert_filter.toggle_development();
+        self.reset_alert_scrollbar();
+    }
+
+    fn reset_alert_scrollbar(&mut self) {
+        self.scrollbar = DependabotScrollbar::new(self.visible_alerts().len());
+    }
+
+    /// Mark or unmark the alert selected in the dependabot details view for batch opening.
+    pub fn toggle_marked_alert(&mut self) {
+        let Some(dependabot) = self.selected_alert() else {
+            return;
+        };
+
+        match self
+            .marked_alerts
+            .iter()
+            .position(|number| *number == dependabot.number)
+        {
+            Some(index) => {
+                self.marked_alerts.remove(index);
+            }
+            None => self.marked_alerts.push(dependabot.number),
+        }
+    }
+
+    /// Open the marked alerts in the browser, asking for confirmation first when there are many.
+    pub fn start_opening_marked_alerts(&mut self) {
+        if self.marked_alerts.len() > BATCH_OPEN_CONFIRMATION_THRESHOLD {
+            self.current_screen = CurrentScreen::ConfirmOpenAlerts;
+        } else {
+            self.open_marked_alerts();
+        }
+    }
+
+    /// Open every marked alert in the browser and clear the marks.
+    pub fn open_marked_alerts(&mut self) {
+        self.current_screen = CurrentScreen::DependabotDetails;
+        let Some(repo) = &self.current_repository else {
+            return;
+        };
+        let urls: Vec<String> = repo
+            .dependabots
+            .iter()
+            .filter(|dependabot| self.marked_alerts.contains(&dependabot.number))
+            .map(|dependabot| dependabot.html_url.clone())
+            .collect();
+        if urls.is_empty() {
+            return;
+        }
+
+        let mut opened_count = 0;
+        for url in urls {
+            match open::that(&url) {
+                Ok(()) => opened_count += 1,
+                Err(e) => {
+                    let browser_error = format!("Failed to open {}: {}", url, e);
+                    self.report_error(browser_error);
+                }
+            }
+        }
+        self.show_status(
+            NotificationLevel::Info,
+            format!("Opened {} alerts in the browser", opened_count),
+        );
+        self.marked_alerts.clear();
+    }
+
+    /// Open the dismissal modal for the alert at the top of the dependabot details view.
+    pub fn start_dismissal(&mut self) {
+        let dependabot = match self.alert_detail {
+            Some(_) => self.detailed_alert(),
+            None => self.selected_alert(),
+        };
+        if self.profile.provider != ProviderKind::GitHub {
+            let dismissal_unsupported = format!(
+                "Alerts can't be dismissed from {} profiles",
+                self.profile.provider
+            );
+            self.report_error(dismissal_unsupported);
+            return;
+        }
+        if self.config.offline {
+            self.show_status(NotificationLevel::Warning, OFFLINE_MESSAGE);
+            return;
+        }
+        if let Some(dependabot) = dependabot {
+            self.dismissal_input = DismissalInput::new(dependabot.number);
+            if self.has_credentials() {
+                self.current_screen = CurrentScreen::Dismiss;
+            } else {
+                self.prompt_for_credentials(CurrentScreen::Dismiss);
+            }
+        }
+    }
+
+    /// Close the dismissal modal, going back to the screen it was opened from.
+    pub fn cancel_dismissal(&mut self) {
+        self.current_screen = self.alert_screen();
+    }
+
+    // the alert detail screen while it's open, otherwise the dependabot details view
+    fn alert_screen(&self) -> CurrentScreen {
+        match self.alert_detail {
+            Some(_) => CurrentScreen::AlertDetail,
+            None => CurrentScreen::DependabotDetails,
+        }
+    }
+
+    /// Switch to the repository's next tab: Project, Dependabot Details, Resolved Alerts, Code
+    /// Scanning, then Secret Scanning.
+    pub fn next_tab(&mut self) {
+        let next_tab = match self.current_screen {
+            CurrentScreen::Project => CurrentScreen::DependabotDetails,
+            CurrentScreen::DependabotDetails => CurrentScreen::ResolvedAlerts,
+            CurrentScreen::ResolvedAlerts => CurrentScreen::CodeScanning,
+            CurrentScreen::CodeScanning => CurrentScreen::SecretScanning,
+            _ => CurrentScreen::Project,
+        };
+        self.open_tab(next_tab);
+    }
+
+    /// Switch to one of the repository's tabs.
+    pub fn open_tab(&mut self, tab: CurrentScreen) {
+        self.current_screen = tab;
+        self.security_alerts_state = ListState::default();
+        if self.security_alert_count() > 0 {
+            self.security_alerts_state.select(Some(0));
+        }
+    }
+
+    /// The repository's fixed and dismissed Dependabot alerts, most recently resolved first.
+    pub fn resolved_alerts(&self) -> Vec<Dependabot> {
+        let Some(repo) = &self.current_repository else {
+            return Vec::new();
+        };
+        let mut alerts: Vec<Dependabot> = repo
+            .dependabots
+            .iter()
+            .filter(|dependabot| dependabot.state != DependabotState::Open)
+            .cloned()
+            .collect();
+        // GitHub's timestamps are all UTC, so they sort as strings
+        alerts.sort_by(|a, b| b.resolved_at().cmp(&a.resolved_at()));
+
+        alerts
+    }
+
+    // how many alerts the resolved alerts, code scanning, or secret scanning tab lists
+    fn security_alert_count(&self) -> usize {
+        let Some(repo) = &self.current_repository else {
+            return 0;
+        };
+        match self.current_screen {
+            CurrentScreen::ResolvedAlerts => self.resolved_alerts().len(),
+            CurrentScreen::CodeScanning => repo.code_scanning_alerts.len(),
+            CurrentScreen::SecretScanning => repo.secret_scanning_alerts.len(),
+            _ => 0,
+        }
+    }
+
+    /// Open the alert selected on the resolved alerts, code scanning, or secret scanning tab in
+    /// the browser.
+    pub fn open_selected_security_alert(&mut self) {
+        let Some(url) = self.selected_security_alert_url() else {
+            return;
+        };
+
+        if let Err(e) = open::that(&url) {
+            let browser_error = format!("Failed to open {}: {}", url, e);
+            self.report_error(browser_error);
+        }
+    }
+
+    /// The URL of the alert selected on the resolved alerts, code scanning, or secret scanning
+    /// tab.
+    fn selected_security_alert_url(&self) -> Option<String> {
+        let repo = self.current_repository.as_ref()?;
+        let index = self.security_alerts_state.selected()?;
+        match self.current_screen {
+            CurrentScreen::ResolvedAlerts => self
+                .resolved_alerts()
+                .get(index)
+                .map(|alert| alert.html_url.clone()),
+            CurrentScreen::CodeScanning => repo
+                .code_scanning_alerts
+                .get(index)
+                .map(|alert| alert.html_url().to_string()),
+            CurrentScreen::SecretScanning => repo
+                .secret_scanning_alerts
+                .get(index)
+                .map(|alert| alert.html_url().to_string()),
+            _ => None,
+        }
+    }
+
+    /// Show the selected alert on its own screen.
+    pub fn open_alert_detail(&mut self) {
+        if let Some(dependabot) = self.selected_alert() {
+            self.alert_detail = Some(dependabot.number);
+            self.alert_detail_scroll = 0;
+            self.current_screen = CurrentScreen::AlertDetail;
+            self.look_up_osv_advisory(&dependabot);
+        }
+    }
+
+    /// Look up the alert's advisory on OSV.dev in the background, unless it's cached already.
+    fn look_up_osv_advisory(&mut self, dependabot: &Dependabot) {
+        let Some(id) = advisory_id(dependabot).map(str::to_string) else {
+            self.osv_lookup = OsvLookup::Unavailable;
+            return;
+        };
+        let cached = cached_advisory(&id);
+        if cached.is_some() || self.config.offline {
+            self.osv_lookup = OsvLookup::Done(cached);
+            return;
+        }
+        let task = self.runtime.spawn(async move {
+            fetch_advisory(&id, &Client::new())
+                .await
+                .unwrap_or_else(|e| {
+                    let osv_error = format!("Failed to look up {} on OSV.dev: {}", id, e);
+                    trace_dbg!(level: tracing::Level::WARN, osv_error);
+                    None
+                })
+        });
+        self.osv_lookup = OsvLookup::Pending(task);
+    }
+
+    /// Pick up the alert detail screen's OSV.dev entry once its lookup finishes.
+    fn poll_osv_lookup(&mut self) {
+        let OsvLookup::Pending(task) = &self.osv_lookup else {
+            return;
+        };
+        if !task.is_finished() {
+            return;
+        }
+        if let OsvLookup::Pending(task) =
+            std::mem::replace(&mut self.osv_lookup, OsvLookup::Unavailable)
+        {
+            self.osv_lookup = OsvLookup::Done(self.runtime.block_on(task).ok().flatten());
+        }
+    }
+
+    pub fn close_alert_detail(&mut self) {
+        self.alert_detail = None;
+        self.current_screen = CurrentScreen::DependabotDetails;
+    }
+
+    /// The alert open on the alert detail screen, looked up by number since dismissing it can
+    /// drop it out of the filtered list.
+    pub fn detailed_alert(&self) -> Option<Dependabot> {
+        let number = self.alert_detail?;
+        self.current_repository
+            .as_ref()?
+            .dependabots
+            .iter()
+            .find(|dependabot| dependabot.number == number)
+            .cloned()
+    }
+
+    /// Move the selection of the list on the current screen, or scroll the alert detail text.
+    pub fn move_selection(&mut self, movement: Movement) {
+        match self.current_screen {
+            CurrentScreen::ProjectList => self.move_repository_selection(movement),
+            CurrentScreen::DependabotDetails => self.scrollbar.move_selection(movement),
+            CurrentScreen::ResolvedAlerts
+            | CurrentScreen::CodeScanning
+            | CurrentScreen::SecretScanning => {
+                let count = self.security_alert_count();
+                move_list_selection(&mut self.security_alerts_state, count, movement);
+            }
+            CurrentScreen::AlertDetail => {
+                self.alert_detail_scroll = match movement {
+                    Movement::Up => self.alert_detail_scroll.saturating_sub(1),
+                    Movement::Down => self.alert_detail_scroll.saturating_add(1),
+                    Movement::HalfPageUp => {
+                        self.alert_detail_scroll.saturating_sub(HALF_PAGE as u16)
+                    }
+                    Movement::HalfPageDown => {
+                        self.alert_detail_scroll.saturating_add(HALF_PAGE as u16)
+                    }
+                    Movement::PageUp => self.alert_detail_scroll.saturating_sub(PAGE as u16),
+                    Movement::PageDown => self.alert_detail_scroll.saturating_add(PAGE as u16),
+                    // the text's length isn't known until it's wrapped, so there's no bottom
+                    Movement::Top | Movement::Bottom => 0,
+                };
+            }
+            CurrentScreen::DistinctAlerts => {
+                let count = self.visible_repositories().distinct_alerts().len();
+                move_list_selection(&mut self.distinct_alerts_state, count, movement);
+            }
+            CurrentScreen::CopyleftReport => {
+                let count = self
+                    .visible_repositories()
+                    .copyleft_vulnerable_dependencies()
+                    .len();
+                move_list_selection(&mut self.copyleft_report_state, count, movement);
+            }
+            CurrentScreen::Dependencies => {
+                let count = self.focused_dependency_list_len();
+                move_list_selection(self.focused_dependency_list_state(), count, movement);
+            }
+            CurrentScreen::Profiles => {
+                let count = self.profiles.len();
+                move_list_selection(&mut self.profiles_state, count, movement);
+            }
+            CurrentScreen::Changes => {
+                move_list_selection(&mut self.changes_state, self.last_changes.len(), movement);
+            }
+            _ => {}
+        }
+    }
+
+    /// Open the alert on the alert detail screen in the browser.
+    pub fn open_detailed_alert(&mut self) {
+        let Some(dependabot) = self.detailed_alert() else {
+            return;
+        };
+
+        if let Err(e) = open::that(&dependabot.html_url) {
+            let browser_error = format!("Failed to open {}: {}", dependabot.html_url, e);
+            self.report_error(browser_error);
+        }
+    }
+
+    /// The client alerts are dismissed and enabled through.
+    fn github_client(&self) -> HttpGithubClient {
+        HttpGithubClient::new(self.config.fetch.retry.client(), GITHUB_API_URL)
+    }
+
+    /// Dismiss the alert on GitHub with the reason and comment chosen in the modal. The request
+    /// runs in the background, and the alert is updated once it comes back.
+    pub fn submit_dismissal(&mut self) {
+        self.current_screen = self.alert_screen();
+        let Some(repo) = &self.current_repository else {
+            return;
+        };
+
+        let repo_id = repo.id;
+        let token = self.profile.token.clone();
+        let full_name = repo.full_name.clone();
+        let number = self.dismissal_input.alert_number;
+        let reason = self.dismissal_input.reason();
+        let comment = self.dismissal_input.comment.clone();
+        let client = self.github_client();
+        let task = self.runtime.spawn(async move {
+            dismiss_alert(&token, &full_name, number, reason, &comment, &client).await
+        });
+        self.pending_dismissals
+            .push(PendingDismissal { repo_id, task });
+        self.show_status(
+            NotificationLevel::Info,
+            format!("Dismissing alert #{}...", number),
+        );
+    }
+
+    /// Apply the dismissals GitHub has answered since the last tick.
+    fn poll_dismissals(&mut self) {
+        let (finished, pending) = std::mem::take(&mut self.pending_dismissals)
+            .into_iter()
+            .partition(|dismissal| dismissal.task.is_finished());
+        self.pending_dismissals = pending;
+
+        for dismissal in finished {
+            let result = self
+                .runtime
+                .block_on(dismissal.task)
+                .unwrap_or_else(|e| Err(DependabotTrackerError::Other(e.to_string())));
+            match result {
+                Ok(dependabot) => {
+                    self.show_status(
+                        NotificationLevel::Info,
+                        format!("Dismissed alert #{}", dependabot.number),
+                    );
+                    self.replace_alert(dismissal.repo_id, dependabot);
+                }
+                Err(e) => {
+                    let dismissal_error = format!("Failed to dismiss alert: {}", e);
+                    self.report_error(dismissal_error);
+                }
+            }
+        }
+    }
+
+    /// Turn on Dependabot alerts for the current repository, if they're disabled. Its alerts
+    /// show up on the next refresh.
+    pub fn enable_alerts(&mut self) {
+        if self.config.offline {
+            self.show_status(NotificationLevel::Warning, OFFLINE_MESSAGE);
+            return;
+        }
+        let Some(repo) = &self.current_repository else {
+            return;
+        };
+        if self.profile.provider != ProviderKind::GitHub {
+            let enable_unsupported = format!(
+                "Dependabot alerts can't be enabled from {} profiles",
+                self.profile.provider
+            );
+            self.report_error(enable_unsupported);
+            return;
+        }
+        if repo.alerts_status != AlertsStatus::Disabled {
+            self.show_status(
+                NotificationLevel::Info,
+                format!("Dependabot alerts are already enabled for {}", repo.name),
+            );
+            return;
+        }
+        if !self.has_credentials() {
+            // back on the Project tab to press the key again once they're entered
+            self.prompt_for_credentials(self.current_screen);
+            return;
+        }
+
+        let repo_id = repo.id;
+        let repo_name = repo.name.clone();
+        match self.runtime.block_on(enable_dependabot_alerts(
+            &self.profile.token,
+            &repo.full_name,
+            self.config.enable_security_updates,
+            &self.github_client(),
+        )) {
+            Ok(()) => {
+                let repos = self
+                    .repositories
+                    .repos
+                    .iter_mut()
+                    .filter(|repo| repo.id == repo_id)
+                    .chain(self.current_repository.as_mut());
+                for repo in repos {
+                    repo.alerts_status = AlertsStatus::Enabled;
+                }
+                if let Some(repo) = &self.current_repository {
+                    if let Err(e) = update_latest_snapshot(repo) {
+                        let save_error = format!("Failed to save {}: {}", repo.name, e);
+                        trace_dbg!(level: tracing::Level::ERROR, save_error);
+                    }
+                }
+                self.show_status(
+                    NotificationLevel::Info,
+                    format!(
+                        "Enabled Dependabot alerts for {}, refresh to fetch them",
+                        repo_name
+                    ),
+                );
+            }
+            Err(e) => {
+                let enable_error = format!("Failed to enable Dependabot alerts: {}", e);
+                self.report_error(enable_error);
+            }
+        }
+    }
+
+    /// Swap an updated alert into the current repository and the repository list, then save.
+    fn replace_alert(&mut self, repo_id: u32, dependabot: Dependabot) {
+        let on_screen = self
+            .current_repository
+            .as_ref()
+            .is_some_and(|repo| repo.id == repo_id);
+        let Some(previous_repo) = self
+            .repositories
+            .repos
+            .iter()
+            .chain(self.current_repository.as_ref())
+            .find(|repo| repo.id == repo_id)
+            .cloned()
+        else {
+            return;
+        };
+        let repos = self
+            .repositories
+            .repos
+            .iter_mut()
+            .chain(self.current_repository.as_mut())
+            .filter(|repo| repo.id == repo_id);
+
+        for repo in repos {
+            if let Some(existing) = repo
+                .dependabots
+                .iter_mut()
+                .find(|existing| existing.number == dependabot.number)
+            {
+                *existing = dependabot.clone();
+            }
+            repo.update_alert_counts();
+        }
+        // a dismissed alert drops out of the list when closed alerts are hidden
+        if on_screen && self.visible_alerts().len() != self.scrollbar.get_length() {
+            self.reset_alert_scrollbar();
+        }
+
+        let updated_repo = self
+            .repositories
+            .repos
+            .iter()
+            .chain(self.current_repository.as_ref())
+            .find(|repo| repo.id == repo_id);
+        if let Some(updated_repo) = updated_repo {
+            record_alert_events(&[previous_repo], std::slice::from_ref(updated_repo));
+            if let Err(e) = update_latest_snapshot(updated_repo) {
+                trace_dbg!(level: tracing::Level::ERROR, e.to_string());
+            }
         }
     }
 
     pub fn on_tick(&mut self) {
         self.spinner_state.calc_next();
+        self.poll_login();
+        self.poll_dismissals();
+        self.poll_osv_lookup();
+        self.status.tick();
+        if self
+            .time_until_refresh()
+            .is_some_and(|time_left| time_left.is_zero())
+        {
+            self.start_background_refresh();
+        }
     }
 }
 
-pub fn load_repositories_from_file() -> Result<Vec<Repository>, Box<dyn Error>> {
-    let file_location = PathBuf::from(".").join("data").join("repositories.json");
-    let file = std::fs::File::open(file_location)?;
-    let reader = std::io::BufReader::new(file);
-    let repositories = serde_json::from_reader(reader)?;
+/// The latest saved repositories, or none if they couldn't be read. That's noted in the
+/// notification log, since the next fetch would otherwise look like the first.
+fn load_saved_repositories(notifications: &mut Notifications) -> SavedData {
+    load_latest_snapshot().unwrap_or_else(|e| {
+        let load_error = format!("Failed to load the saved repositories: {}", e);
+        trace_dbg!(level: tracing::Level::ERROR, &load_error);
+        notifications.push(NotificationLevel::Error, load_error);
+        SavedData::default()
+    })
+}
 
-    Ok(repositories)
+fn load_trend() -> Vec<AlertTrendPoint> {
+    load_alert_trend(TREND_SNAPSHOT_COUNT).unwrap_or_else(|e| {
+        let trend_error = format!("Failed to load the alert trend: {}", e);
+        trace_dbg!(level: tracing::Level::ERROR, trend_error);
+        Vec::new()
+    })
 }
 
+/// Append the alert lifecycle changes between two snapshots to the event log, returning them.
+pub fn record_alert_events(previous: &[Repository], updated: &[Repository]) -> Vec<AlertEvent> {
+    let events = detect_alert_events(previous, updated);
+    if let Err(e) = append_alert_events(&events) {
+        trace_dbg!(level: tracing::Level::ERROR, e.to_string());
+    }
+
+    events
+}
+
+/// How far to move a list's selection. Moving up or down one item wraps around at the ends.
+#[derive(Debug, Clone, Copy)]
+pub enum Movement {
+    Up,
+    Down,
+    Top,
+    Bottom,
+    HalfPageDown,
+    HalfPageUp,
+    PageDown,
+    PageUp,
+}
+
+impl Movement {
+    /// Where the selection at `index` in a list of `count` items moves to.
+    fn apply(self, index: usize, count: usize) -> usize {
+        let last = count.saturating_sub(1);
+        match self {
+            Movement::Up if index == 0 => last,
+            Movement::Up => index - 1,
+            Movement::Down if index + 1 >= count => 0,
+            Movement::Down => index + 1,
+            Movement::Top => 0,
+            Movement::Bottom => last,
+            Movement::HalfPageDown => (index + HALF_PAGE).min(last),
+            Movement::HalfPageUp => index.saturating_sub(HALF_PAGE),
+            Movement::PageDown => (index + PAGE).min(last),
+            Movement::PageUp => index.saturating_sub(PAGE),
+        }
+    }
+}
+
+fn move_list_selection(state: &mut ListState, count: usize, movement: Movement) {
+    if let Some(index) = state.selected() {
+        state.select(Some(movement.apply(index, count)));
+    }
+}
+
+/// The selected alert in the dependabot details list, along with its scrollbar.
 pub struct DependabotScrollbar {
     state: ScrollbarState,
+    list_state: ListState,
+    // the number of alerts in the list
     length: usize,
+    // how many alerts fit in the list as it was last drawn
+    viewport: usize,
+    // the index of the selected alert
     pub position: usize,
 }
 
@@ -84,55 +2280,98 @@ impl DependabotScrollbar {
     pub fn default() -> Self {
         DependabotScrollbar {
             state: ScrollbarState::default(),
+            list_state: ListState::default(),
             length: 0,
+            viewport: 1,
             position: 0,
         }
     }
 
     pub fn new(length: usize) -> Self {
-        DependabotScrollbar {
+        let mut scrollbar = DependabotScrollbar {
             state: ScrollbarState::default()
                 .content_length(length)
-                .viewport_content_length(1)
-                .position(0),
+                .viewport_content_length(1),
+            list_state: ListState::default(),
             length,
+            viewport: 1,
             position: 0,
-        }
+        };
+        scrollbar.select(0);
+
+        scrollbar
+    }
+
+    fn select(&mut self, position: usize) {
+        self.position = position;
+        self.state = self.state.position(position);
+        self.list_state
+            .select((self.length > 0).then_some(position));
     }
 
     pub fn scroll_down(&mut self) {
-        if self.position < self.length {
-            self.position += 1;
+        if self.position + 1 < self.length {
+            self.select(self.position + 1);
         } else {
-            self.position = 0;
+            self.select(0);
         }
-
-        self.state = self.state.position(self.position);
     }
 
     pub fn scroll_up(&mut self) {
         if self.position > 0 {
-            self.position -= 1;
+            self.select(self.position - 1);
         } else {
-            self.position = self.length;
+            self.select(self.length.saturating_sub(1));
         }
+    }
+
+    pub fn move_selection(&mut self, movement: Movement) {
+        match movement {
+            Movement::PageDown => self.page_down(),
+            Movement::PageUp => self.page_up(),
+            Movement::HalfPageDown => self.half_page_down(),
+            Movement::HalfPageUp => self.half_page_up(),
+            movement if self.length > 0 => self.select(movement.apply(self.position, self.length)),
+            _ => {}
+        }
+    }
 
-        self.state = self.state.position(self.position);
+    /// Move the selection down by as many alerts as fit in the list.
+    pub fn page_down(&mut self) {
+        self.scroll_down_by(self.viewport);
+    }
+
+    pub fn page_up(&mut self) {
+        self.select(self.position.saturating_sub(self.viewport));
+    }
+
+    pub fn half_page_down(&mut self) {
+        self.scroll_down_by((self.viewport / 2).max(1));
+    }
+
+    pub fn half_page_up(&mut self) {
+        self.select(self.position.saturating_sub((self.viewport / 2).max(1)));
+    }
+
+    fn scroll_down_by(&mut self, count: usize) {
+        if self.length > 0 {
+            self.select((self.position + count).min(self.length - 1));
+        }
     }
 
-    pub fn top(&mut self) {
-        self.position = 0;
-        self.state = self.state.position(self.position);
+    /// Note how many alerts fit in the list, so pages are the right size and the scrollbar's
+    /// thumb is in proportion to the list.
+    pub fn set_viewport(&mut self, viewport: usize) {
+        self.viewport = viewport.max(1);
+        self.state = self.state.viewport_content_length(self.viewport);
     }
 
     pub fn get_mut_state(&mut self) -> &mut ScrollbarState {
         &mut self.state
     }
 
-    pub fn resize(&mut self, length: usize) {
-        self.length = length;
-        self.position = 0;
-        self.state = self.state.content_length(length).position(0);
+    pub fn get_mut_list_state(&mut self) -> &mut ListState {
+        &mut self.list_state
     }
 
     pub fn get_length(&self) -> usize {
@@ -0,0 +1,182 @@
+use std::path::{Path, PathBuf};
+use std::{fs, io};
+
+use serde_json::Value;
+
+use crate::dependabot::{Dependabot, DependabotState};
+use crate::repository::Repository;
+
+/// A vulnerable package reported by a local scanner.
+#[derive(Debug, Clone)]
+pub struct AuditFinding {
+    pub package: String,
+    pub ecosystem: String,
+    // advisory identifiers, e.g. `RUSTSEC-2021-0001` or `GHSA-xxxx-xxxx-xxxx`
+    pub advisory_ids: Vec<String>,
+}
+
+impl AuditFinding {
+    fn matches(&self, dependabot: &Dependabot) -> bool {
+        match &dependabot.ghsa_id {
+            Some(ghsa_id) if self.advisory_ids.iter().any(|id| id == ghsa_id) => true,
+            _ => {
+                self.ecosystem == dependabot.dependency_ecosystem
+                    && self
+                        .package
+                        .eq_ignore_ascii_case(&dependabot.dependency_name)
+            }
+        }
+    }
+}
+
+/// A local scanner report to compare against a repository's GitHub alerts, passed on the
+/// command line as `<repository>=<file>`.
+#[derive(Debug, Clone)]
+pub struct AuditReport {
+    pub repository: String,
+    pub findings: Vec<AuditFinding>,
+}
+
+impl AuditReport {
+    pub fn load(repository: &str, path: &Path) -> io::Result<AuditReport> {
+        let contents = fs::read_to_string(path)?;
+        let report: Value = serde_json::from_str(&contents)?;
+        let findings = parse_cargo_audit(&report)
+            .or_else(|| parse_npm_audit(&report))
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!(
+                        "{} isn't `cargo audit --json` or `npm audit --json` output",
+                        path.display()
+                    ),
+                )
+            })?;
+
+        Ok(AuditReport {
+            repository: repository.to_string(),
+            findings,
+        })
+    }
+
+    pub fn is_for(&self, repository: &Repository) -> bool {
+        self.repository == repository.name || self.repository == repository.full_name
+    }
+}
+
+/// Split a `<repository>=<file>` command line argument.
+pub fn parse_audit_argument(value: &str) -> Result<(String, PathBuf), String> {
+    match value.split_once('=') {
+        Some((repository, path)) if !repository.is_empty() && !path.is_empty() => {
+            Ok((repository.to_string(), PathBuf::from(path)))
+        }
+        _ => Err(format!("expected <repository>=<file>, got {}", value)),
+    }
+}
+
+/// `cargo audit --json` lists findings under `vulnerabilities.list`.
+fn parse_cargo_audit(report: &Value) -> Option<Vec<AuditFinding>> {
+    let list = report.get("vulnerabilities")?.get("list")?.as_array()?;
+
+    Some(
+        list.iter()
+            .filter_map(|vulnerability| {
+                let advisory = vulnerability.get("advisory")?;
+                let mut advisory_ids = vec![advisory.get("id")?.as_str()?.to_string()];
+                advisory_ids.extend(string_array(advisory.get("aliases")));
+
+                Some(AuditFinding {
+                    package: vulnerability
+                        .get("package")?
+                        .get("name")?
+                        .as_str()?
+                        .to_string(),
+                    ecosystem: "rust".to_string(),
+                    advisory_ids,
+                })
+            })
+            .collect(),
+    )
+}
+
+/// `npm audit --json` keys findings by package name under `vulnerabilities`, with the advisories
+/// that affect each package under `via`.
+fn parse_npm_audit(report: &Value) -> Option<Vec<AuditFinding>> {
+    let vulnerabilities = report.get("vulnerabilities")?.as_object()?;
+
+    Some(
+        vulnerabilities
+            .iter()
+            .map(|(package, vulnerability)| {
+                let advisory_ids = vulnerability
+                    .get("via")
+                    .and_then(Value::as_array)
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|via| via.get("url")?.as_str())
+                    .filter_map(|url| url.rsplit('/').next())
+                    .map(str::to_string)
+                    .collect();
+
+                AuditFinding {
+                    package: package.clone(),
+                    ecosystem: "npm".to_string(),
+                    advisory_ids,
+                }
+            })
+            .collect(),
+    )
+}
+
+fn string_array(value: Option<&Value>) -> impl Iterator<Item = String> + '_ {
+    value
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_str)
+        .map(str::to_string)
+}
+
+/// How a local scanner's findings line up with a repository's open GitHub alerts.
+pub struct AuditComparison<'a> {
+    pub in_both: Vec<(&'a AuditFinding, &'a Dependabot)>,
+    pub local_only: Vec<&'a AuditFinding>,
+    pub github_only: Vec<&'a Dependabot>,
+}
+
+impl<'a> AuditComparison<'a> {
+    pub fn new(report: &'a AuditReport, repository: &'a Repository) -> AuditComparison<'a> {
+        let open_alerts: Vec<&Dependabot> = repository
+            .dependabots
+            .iter()
+            .filter(|dependabot| dependabot.state == DependabotState::Open)
+            .collect();
+
+        let mut in_both = Vec::new();
+        let mut local_only = Vec::new();
+        for finding in report.findings.iter() {
+            match open_alerts
+                .iter()
+                .find(|dependabot| finding.matches(dependabot))
+            {
+                Some(dependabot) => in_both.push((finding, *dependabot)),
+                None => local_only.push(finding),
+            }
+        }
+        let github_only = open_alerts
+            .into_iter()
+            .filter(|dependabot| {
+                !report
+                    .findings
+                    .iter()
+                    .any(|finding| finding.matches(dependabot))
+            })
+            .collect();
+
+        AuditComparison {
+            in_both,
+            local_only,
+            github_only,
+        }
+    }
+}
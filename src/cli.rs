@@ -0,0 +1,205 @@
+use std::path::PathBuf;
+use std::time::Duration;
+
+use clap::{Parser, Subcommand, ValueEnum};
+
+use crate::audit::parse_audit_argument;
+use crate::config::ConfigOverrides;
+use crate::timezone::DisplayTimezone;
+
+#[derive(Debug, Parser)]
+#[command(
+    version,
+    about = "A TUI for tracking a GitHub user's active dependabot alerts"
+)]
+pub struct Cli {
+    /// Run a command without the TUI instead of starting it
+    #[command(subcommand)]
+    pub command: Option<Command>,
+    /// Skip the overview and open the repository with the highest risk score
+    #[arg(long)]
+    pub worst_first: bool,
+    /// Feed the key commands in this file to the app before reading from the keyboard
+    #[arg(long)]
+    pub script: Option<PathBuf>,
+    /// Read settings from this config file, layered over the default config file
+    #[arg(long)]
+    pub config: Option<PathBuf>,
+    /// Start as this profile from the config's [profiles] section instead of the default one
+    #[arg(long)]
+    pub profile: Option<String>,
+    /// Override the number of results per page requested from the GitHub API
+    #[arg(long)]
+    pub per_page: Option<u32>,
+    /// Override the maximum number of repositories to fetch alerts for
+    #[arg(long)]
+    pub max_repositories: Option<usize>,
+    /// Override how many hours old a repository's data gets before a stale refresh fetches it
+    #[arg(long)]
+    pub stale_after_hours: Option<i64>,
+    /// Override the timezone timestamps are shown in: local, utc, or a name like America/New_York
+    #[arg(long, value_parser = parse_timezone)]
+    pub timezone: Option<DisplayTimezone>,
+    /// Keep fetched data in this directory instead of the platform's data directory
+    #[arg(long)]
+    pub data_dir: Option<PathBuf>,
+    /// Never touch the network: show the saved data with updates turned off
+    #[arg(long)]
+    pub offline: bool,
+    /// Compare `cargo audit --json` or `npm audit --json` output from a local checkout with a
+    /// repository's GitHub alerts, e.g. `--audit my-repo=audit.json`. Can be repeated
+    #[arg(long, value_name = "REPOSITORY=FILE", value_parser = parse_audit_argument)]
+    pub audit: Vec<(String, PathBuf)>,
+    /// Print a subcommand's results as text or as versioned JSON, e.g. `fetch --output json`
+    #[arg(long, global = true, value_enum, default_value_t = OutputMode::Text)]
+    pub output: OutputMode,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Fetch every repository's alerts and save them
+    Fetch,
+    /// Print a summary of the saved repositories' open alerts
+    Report {
+        #[arg(long, value_enum, default_value_t = ReportFormat::Table)]
+        format: ReportFormat,
+        /// Write to this file instead of stdout
+        #[arg(long)]
+        out_file: Option<PathBuf>,
+    },
+    /// Fetch every repository's alerts and exit with a failure if any repository has more open
+    /// alerts than allowed
+    Check {
+        /// The most open critical alerts a repository can have
+        #[arg(long)]
+        max_critical: Option<usize>,
+        /// The most open high alerts a repository can have
+        #[arg(long)]
+        max_high: Option<usize>,
+    },
+    /// Fetch every repository's alerts and email a digest of the open alerts and how they changed
+    /// over the last week to the config's digest recipients
+    Digest {
+        /// Print the digest instead of sending it
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Write the saved alerts to stdout or a file
+    Export {
+        #[arg(long, value_enum, default_value_t = ExportFormat::Json)]
+        format: ExportFormat,
+        /// Write to this file instead of stdout
+        #[arg(long)]
+        out_file: Option<PathBuf>,
+    },
+    /// Keep fetching every repository's alerts, and print the new open alerts as JSON or pipe
+    /// them to a command whenever a fetch turns some up
+    Watch {
+        /// How long to wait between fetches, e.g. 30s, 15m, or 1h
+        #[arg(long, default_value = "15m", value_parser = parse_interval)]
+        interval: Duration,
+        /// Run this command with `sh -c` when new alerts appear, with the JSON on its stdin
+        #[arg(long)]
+        on_change: Option<String>,
+        /// Stop watching after the first fetch that turns up new alerts
+        #[arg(long)]
+        exit_on_change: bool,
+    },
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum ReportFormat {
+    /// A table of each repository's open alert counts
+    Table,
+    /// A security review summary with the top vulnerable dependencies and oldest alerts
+    Markdown,
+    /// The Markdown summary as a standalone HTML page
+    Html,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub enum OutputMode {
+    Text,
+    /// The documented JSON schema, which stays the same within a `schema_version`
+    Json,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum ExportFormat {
+    Json,
+    Csv,
+    /// A SARIF 2.1.0 log, for security dashboards and IDEs
+    Sarif,
+}
+
+impl Cli {
+    pub fn config_overrides(&self) -> ConfigOverrides {
+        ConfigOverrides {
+            per_page: self.per_page,
+            max_repositories: self.max_repositories,
+            stale_after_hours: self.stale_after_hours,
+            timezone: self.timezone.clone(),
+            data_dir: self.data_dir.clone(),
+            // the flag can only turn offline mode on, so leave the config's setting alone without it
+            offline: self.offline.then_some(true),
+        }
+    }
+}
+
+/// Parse an interval like `90s`, `15m`, `2h`, or `1d`.
+fn parse_interval(value: &str) -> Result<Duration, String> {
+    let invalid = || format!("expected an interval like 30s, 15m, or 1h, got '{}'", value);
+    let value = value.trim();
+    let unit_start = value
+        .find(|c: char| !c.is_ascii_digit())
+        .ok_or_else(invalid)?;
+    let (amount, unit) = value.split_at(unit_start);
+    let amount: u64 = amount.parse().map_err(|_| invalid())?;
+    let seconds = match unit {
+        "s" => amount,
+        "m" => amount * 60,
+        "h" => amount * 60 * 60,
+        "d" => amount * 60 * 60 * 24,
+        _ => return Err(invalid()),
+    };
+    if seconds == 0 {
+        return Err(invalid());
+    }
+
+    Ok(Duration::from_secs(seconds))
+}
+
+fn parse_timezone(value: &str) -> Result<DisplayTimezone, String> {
+    DisplayTimezone::try_from(value.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn output_mode_can_follow_the_subcommand() {
+        let cli =
+            Cli::try_parse_from(["dependabot-tracker", "report", "--output", "json"]).unwrap();
+
+        assert_eq!(cli.output, OutputMode::Json);
+        match cli.command {
+            Some(Command::Report { out_file, .. }) => assert_eq!(out_file, None),
+            command => panic!("expected the report command, got {:?}", command),
+        }
+    }
+
+    #[test]
+    fn report_writes_to_the_out_file() {
+        let cli =
+            Cli::try_parse_from(["dependabot-tracker", "report", "--out-file", "json"]).unwrap();
+
+        assert_eq!(cli.output, OutputMode::Text);
+        match cli.command {
+            Some(Command::Report { out_file, .. }) => {
+                assert_eq!(out_file, Some(PathBuf::from("json")))
+            }
+            command => panic!("expected the report command, got {:?}", command),
+        }
+    }
+}
@@ -0,0 +1,412 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use directories::ProjectDirs;
+use serde::Deserialize;
+use toml::Table;
+
+use crate::credentials::load_keyring_token;
+use crate::desktop_notification::DesktopNotificationConfig;
+use crate::digest::DigestConfig;
+use crate::error::DependabotTrackerError;
+use crate::fetch_config::FetchConfig;
+use crate::keymap::Keymap;
+use crate::profile::{Profile, ProfileConfig, DEFAULT_PROFILE};
+use crate::provider::ProviderKind;
+use crate::repository_list::SortMode;
+use crate::risk::RiskConfig;
+use crate::sla::SlaConfig;
+use crate::storage::get_default_data_dir;
+use crate::templates::Templates;
+use crate::theme::ThemeConfig;
+use crate::timezone::DisplayTimezone;
+use crate::trace_dbg;
+use crate::webhook::WebhookConfig;
+
+const ENV_PREFIX: &str = "DEPENDABOT_TRACKER_";
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    // which repositories to fetch and how, set at the top level of the config file
+    #[serde(flatten)]
+    pub fetch: FetchConfig,
+    // how old a repository's data can get before a stale refresh fetches it again
+    pub stale_after_hours: i64,
+    // where fetched data is kept, the platform's data directory by default
+    pub data_dir: Option<PathBuf>,
+    // whether the alerts tab starts out hiding alerts on development-only dependencies
+    pub hide_development_alerts: bool,
+    // whether enabling Dependabot alerts from the app turns on Dependabot security updates too
+    pub enable_security_updates: bool,
+    // message templates for notifications
+    pub templates: Templates,
+    // named groups of repositories, by name or `owner/repo`, that the app can be scoped to
+    pub groups: BTreeMap<String, Vec<String>>,
+    // the timezone to display timestamps in
+    pub timezone: DisplayTimezone,
+    // credentials to use when `PAT`/`GH_USERNAME` aren't set in the environment
+    pub auth: AuthConfig,
+    // the order the repository list starts out in
+    pub default_sort: SortMode,
+    // how often to fetch the repositories again while the app is open
+    pub refresh_interval_minutes: Option<u64>,
+    // never touch the network, only showing the saved data
+    pub offline: bool,
+    // whether to start on the repository with the highest risk score instead of the overview
+    pub worst_first: bool,
+    // the theme to start with and any colors changed in it
+    pub theme: ThemeConfig,
+    // when to show a desktop notification for alerts that opened since the last fetch
+    pub desktop_notifications: DesktopNotificationConfig,
+    // where to post a summary of the alerts that opened or were resolved in each fetch
+    pub webhook: WebhookConfig,
+    // the SMTP server and recipients for `dependabot-tracker digest`
+    pub digest: DigestConfig,
+    // how long open alerts of each severity may stay open before they're overdue
+    pub sla: SlaConfig,
+    // how much each severity of open alert adds to a repository's risk score
+    pub risk: RiskConfig,
+    // other GitHub or GitLab accounts to switch between, each with its own data directory
+    pub profiles: BTreeMap<String, ProfileConfig>,
+    // keys for any actions being rebound from their defaults
+    pub keymap: Keymap,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct AuthConfig {
+    pub token: Option<String>,
+    pub username: Option<String>,
+    // the client ID of the OAuth or GitHub App to log in through with the device flow
+    pub client_id: Option<String>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            fetch: FetchConfig::default(),
+            stale_after_hours: 24,
+            data_dir: None,
+            hide_development_alerts: false,
+            enable_security_updates: false,
+            templates: Templates::default(),
+            groups: BTreeMap::new(),
+            timezone: DisplayTimezone::default(),
+            auth: AuthConfig::default(),
+            default_sort: SortMode::default(),
+            refresh_interval_minutes: None,
+            offline: false,
+            worst_first: false,
+            theme: ThemeConfig::default(),
+            desktop_notifications: DesktopNotificationConfig::default(),
+            webhook: WebhookConfig::default(),
+            digest: DigestConfig::default(),
+            sla: SlaConfig::default(),
+            risk: RiskConfig::default(),
+            profiles: BTreeMap::new(),
+            keymap: Keymap::default(),
+        }
+    }
+}
+
+/// Settings that can be overridden from the environment or the command line.
+#[derive(Debug, Clone, Default)]
+pub struct ConfigOverrides {
+    pub per_page: Option<u32>,
+    pub max_repositories: Option<usize>,
+    pub stale_after_hours: Option<i64>,
+    pub timezone: Option<DisplayTimezone>,
+    pub data_dir: Option<PathBuf>,
+    pub offline: Option<bool>,
+}
+
+impl ConfigOverrides {
+    /// Read overrides from `DEPENDABOT_TRACKER_*` environment variables, ignoring (and logging)
+    /// any that can't be parsed.
+    pub fn from_env() -> ConfigOverrides {
+        ConfigOverrides {
+            per_page: env_override("PER_PAGE", |value| value.parse().ok()),
+            max_repositories: env_override("MAX_REPOSITORIES", |value| value.parse().ok()),
+            stale_after_hours: env_override("STALE_AFTER_HOURS", |value| value.parse().ok()),
+            timezone: env_override("TIMEZONE", |value| {
+                DisplayTimezone::try_from(value.to_string()).ok()
+            }),
+            data_dir: env_override("DATA_DIR", |value| Some(PathBuf::from(value))),
+            offline: env_override("OFFLINE", |value| match value {
+                "1" | "true" => Some(true),
+                "0" | "false" => Some(false),
+                _ => None,
+            }),
+        }
+    }
+
+    fn apply(self, config: &mut Config) {
+        if let Some(per_page) = self.per_page {
+            config.fetch.per_page = per_page;
+        }
+        if let Some(max_repositories) = self.max_repositories {
+            config.fetch.max_repositories = Some(max_repositories);
+        }
+        if let Some(stale_after_hours) = self.stale_after_hours {
+            config.stale_after_hours = stale_after_hours;
+        }
+        if let Some(timezone) = self.timezone {
+            config.timezone = timezone;
+        }
+        if let Some(data_dir) = self.data_dir {
+            config.data_dir = Some(data_dir);
+        }
+        if let Some(offline) = self.offline {
+            config.offline = offline;
+        }
+    }
+}
+
+fn env_override<T>(name: &str, parse: impl Fn(&str) -> Option<T>) -> Option<T> {
+    let variable = format!("{}{}", ENV_PREFIX, name);
+    let value = std::env::var(&variable).ok()?;
+    let parsed = parse(&value);
+    if parsed.is_none() {
+        let env_error = format!("Ignoring invalid value for {}: {}", variable, value);
+        trace_dbg!(level: tracing::Level::WARN, env_error);
+    }
+
+    parsed
+}
+
+impl Config {
+    /// Resolve the config from its layers, lowest precedence first: the default config file, the
+    /// file passed with `--config`, `DEPENDABOT_TRACKER_*` environment variables, and command line
+    /// flags. A default file that can't be read or parsed is logged and skipped, but a file passed
+    /// explicitly has to exist and parse, and an invalid setting in either file is an error.
+    pub fn resolve(
+        config_path: Option<&Path>,
+        cli_overrides: ConfigOverrides,
+    ) -> Result<Config, DependabotTrackerError> {
+        let mut layered = Table::new();
+
+        if let Some(default_path) = get_config_path() {
+            if let Ok(contents) = std::fs::read_to_string(&default_path) {
+                match contents.parse::<Table>() {
+                    Ok(table) => merge_tables(&mut layered, table),
+                    Err(e) => {
+                        let config_error =
+                            format!("Failed to parse {}: {}", default_path.display(), e);
+                        trace_dbg!(level: tracing::Level::ERROR, config_error);
+                    }
+                }
+            }
+        }
+        if let Some(config_path) = config_path {
+            let table = std::fs::read_to_string(config_path)
+                .map_err(|e| e.to_string())
+                .and_then(|contents| contents.parse::<Table>().map_err(|e| e.to_string()))
+                .map_err(|e| {
+                    DependabotTrackerError::Config(format!(
+                        "Failed to load {}: {}",
+                        config_path.display(),
+                        e
+                    ))
+                })?;
+            merge_tables(&mut layered, table);
+        }
+
+        let mut config: Config = layered.clone().try_into().map_err(|e| {
+            // nested settings are already named in toml's error
+            let message = match invalid_key(&layered) {
+                Some(key) if !e.to_string().contains(&format!("`{}`", key)) => {
+                    format!("Invalid configuration for {}: {}", key, e)
+                }
+                _ => format!("Invalid configuration: {}", e),
+            };
+            DependabotTrackerError::Config(message.trim_end().to_string())
+        })?;
+        ConfigOverrides::from_env().apply(&mut config);
+        cli_overrides.apply(&mut config);
+
+        Ok(config)
+    }
+
+    /// The GitHub token, from `PAT` if it's set, then the OS keyring, and lastly the `[auth]`
+    /// section.
+    pub fn token(&self) -> String {
+        // the keyring is only asked once `PAT` turns out not to be set, as reading it can prompt
+        // to unlock the keyring
+        if let Some(token) = env_var("PAT") {
+            return token;
+        }
+
+        load_keyring_token(&self.username())
+            .or_else(|| self.auth.token.clone())
+            .unwrap_or_default()
+    }
+
+    /// The password for the digest's SMTP server, from `SMTP_PASSWORD` if it's set or else from
+    /// the `[digest]` section.
+    pub fn smtp_password(&self) -> String {
+        env_or_configured("SMTP_PASSWORD", &self.digest.smtp_password)
+    }
+
+    /// The GitHub username, from `GH_USERNAME` if it's set or else from the `[auth]` section.
+    pub fn username(&self) -> String {
+        env_or_configured("GH_USERNAME", &self.auth.username)
+    }
+
+    /// Where fetched data is kept: `data_dir` if it's set, otherwise the platform's data
+    /// directory.
+    pub fn data_dir(&self) -> PathBuf {
+        self.data_dir.clone().unwrap_or_else(get_default_data_dir)
+    }
+
+    /// The profile made from `PAT`/`GH_USERNAME` and the `[auth]` section, which keeps its data
+    /// in `data_dir()`.
+    pub fn default_profile(&self) -> Profile {
+        Profile {
+            name: DEFAULT_PROFILE.to_string(),
+            username: self.username(),
+            token: self.token(),
+            data_dir: self.data_dir(),
+            provider: ProviderKind::GitHub,
+            gitlab_url: None,
+        }
+    }
+
+    /// The profile with the given name, either the default one or one from `[profiles]`.
+    pub fn profile(&self, name: &str) -> Option<Profile> {
+        if name == DEFAULT_PROFILE {
+            return Some(self.default_profile());
+        }
+
+        let profile = self.profiles.get(name)?;
+        let username = profile.username.clone().unwrap_or_default();
+        Some(Profile {
+            name: name.to_string(),
+            token: profile
+                .token
+                .clone()
+                .or_else(|| load_keyring_token(&username))
+                .unwrap_or_default(),
+            username,
+            data_dir: profile
+                .data_dir
+                .clone()
+                .unwrap_or_else(|| self.data_dir().join(name)),
+            provider: profile.provider,
+            gitlab_url: profile.gitlab_url.clone(),
+        })
+    }
+
+    /// The default profile followed by the ones from `[profiles]`, by name.
+    pub fn profiles(&self) -> Vec<Profile> {
+        std::iter::once(DEFAULT_PROFILE)
+            .chain(
+                self.profiles
+                    .keys()
+                    .map(String::as_str)
+                    .filter(|name| *name != DEFAULT_PROFILE),
+            )
+            .filter_map(|name| self.profile(name))
+            .collect()
+    }
+
+    pub fn refresh_interval(&self) -> Option<std::time::Duration> {
+        self.refresh_interval_minutes
+            .filter(|minutes| *minutes > 0)
+            .map(|minutes| std::time::Duration::from_secs(minutes * 60))
+    }
+}
+
+fn env_var(variable: &str) -> Option<String> {
+    std::env::var(variable)
+        .ok()
+        .filter(|value| !value.is_empty())
+}
+
+fn env_or_configured(variable: &str, configured: &Option<String>) -> String {
+    env_var(variable)
+        .or_else(|| configured.clone())
+        .unwrap_or_default()
+}
+
+/// Merge `overlay` into `base`, recursing into tables so a later layer only replaces the keys it
+/// sets.
+fn merge_tables(base: &mut Table, overlay: Table) {
+    for (key, value) in overlay {
+        match (base.get_mut(&key), value) {
+            (Some(toml::Value::Table(base_table)), toml::Value::Table(overlay_table)) => {
+                merge_tables(base_table, overlay_table);
+            }
+            (_, value) => {
+                base.insert(key, value);
+            }
+        }
+    }
+}
+
+/// The setting that makes `table` fail to deserialize, like `per_page` or `auth.username`.
+/// toml's error doesn't name it, so each setting is tried on its own, with the rest left at
+/// their defaults.
+fn invalid_key(table: &Table) -> Option<String> {
+    let is_valid = |table: Table| table.try_into::<Config>().is_ok();
+
+    table.iter().find_map(|(key, value)| {
+        if is_valid(Table::from_iter([(key.clone(), value.clone())])) {
+            return None;
+        }
+        let nested_key = match value {
+            toml::Value::Table(nested) => nested.iter().find_map(|(nested_key, nested_value)| {
+                let nested = Table::from_iter([(nested_key.clone(), nested_value.clone())]);
+                let single = Table::from_iter([(key.clone(), toml::Value::Table(nested))]);
+                (!is_valid(single)).then(|| format!("{}.{}", key, nested_key))
+            }),
+            _ => None,
+        };
+        Some(nested_key.unwrap_or_else(|| key.clone()))
+    })
+}
+
+/// The location of the config file, e.g. `~/.config/dependabot-tracker/config.toml` on Linux.
+pub fn get_config_path() -> Option<PathBuf> {
+    ProjectDirs::from("", "", env!("CARGO_PKG_NAME"))
+        .map(|dirs| dirs.config_dir().join("config.toml"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn invalid_settings_are_named() {
+        let top_level: Table = "per_page = \"lots\"\nstale_after_hours = 12"
+            .parse()
+            .unwrap();
+        let nested: Table = "[auth]\ntoken = \"ghp_test\"\nusername = 5"
+            .parse()
+            .unwrap();
+
+        assert_eq!(invalid_key(&top_level).as_deref(), Some("per_page"));
+        assert_eq!(invalid_key(&nested).as_deref(), Some("auth.username"));
+    }
+
+    #[test]
+    fn an_invalid_setting_in_an_explicit_file_is_an_error() {
+        let path = std::env::temp_dir().join(format!(
+            "dependabot-tracker-config-test-{}.toml",
+            std::process::id()
+        ));
+        std::fs::write(
+            &path,
+            "per_page = \"lots\"\n[auth]\nusername = \"octocat\"\n",
+        )
+        .unwrap();
+
+        let result = Config::resolve(Some(&path), ConfigOverrides::default());
+        let _ = std::fs::remove_file(&path);
+
+        let Err(DependabotTrackerError::Config(message)) = result else {
+            panic!("the config should have been rejected");
+        };
+        assert!(message.contains("per_page"));
+    }
+}
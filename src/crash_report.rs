@@ -0,0 +1,78 @@
+use std::backtrace::Backtrace;
+use std::fs;
+use std::io;
+use std::panic::PanicHookInfo;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use chrono::Local;
+use lazy_static::lazy_static;
+
+use crate::logging::{get_state_dir, LOG_FILE};
+
+// how many lines from the end of the log file to include in a crash report
+const RECENT_LOG_LINES: usize = 50;
+
+/// The parts of the app state worth including in a crash report, refreshed every frame so
+/// the panic hook (which can't see the `App`) has something to dump.
+#[derive(Default)]
+pub struct CrashContext {
+    pub current_screen: String,
+    pub selected_repository: Option<String>,
+}
+
+lazy_static! {
+    static ref CRASH_CONTEXT: Mutex<CrashContext> = Mutex::new(CrashContext::default());
+}
+
+pub fn update_crash_context(current_screen: String, selected_repository: Option<String>) {
+    if let Ok(mut context) = CRASH_CONTEXT.lock() {
+        context.current_screen = current_screen;
+        context.selected_repository = selected_repository;
+    }
+}
+
+/// Write a crash report to the state directory and return its path.
+pub fn write_crash_report(panic_info: &PanicHookInfo) -> io::Result<PathBuf> {
+    let directory = get_state_dir();
+    fs::create_dir_all(&directory)?;
+    let report_path = directory.join(format!(
+        "crash-{}.txt",
+        Local::now().format("%Y%m%d-%H%M%S")
+    ));
+
+    let (current_screen, selected_repository) = match CRASH_CONTEXT.lock() {
+        Ok(context) => (
+            context.current_screen.clone(),
+            context
+                .selected_repository
+                .clone()
+                .unwrap_or_else(|| "N/A".to_string()),
+        ),
+        Err(_) => ("unknown".to_string(), "unknown".to_string()),
+    };
+
+    let log = fs::read_to_string(directory.join(LOG_FILE.clone())).unwrap_or_default();
+    let log_lines: Vec<&str> = log.lines().collect();
+    let recent_log = log_lines[log_lines.len().saturating_sub(RECENT_LOG_LINES)..].join("\n");
+
+    let report = format!(
+        "{} {} crash report ({})\n\n\
+         Panic: {}\n\
+         Current screen: {}\n\
+         Selected repository: {}\n\n\
+         Backtrace:\n{}\n\n\
+         Recent log lines:\n{}\n",
+        env!("CARGO_PKG_NAME"),
+        env!("CARGO_PKG_VERSION"),
+        Local::now().to_rfc3339(),
+        panic_info,
+        current_screen,
+        selected_repository,
+        Backtrace::force_capture(),
+        recent_log
+    );
+    fs::write(&report_path, report)?;
+
+    Ok(report_path)
+}
@@ -0,0 +1,129 @@
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+use keyring::Entry;
+
+use crate::error::DependabotTrackerError;
+use crate::trace_dbg;
+
+// the service name the token is stored under in the OS keyring
+const KEYRING_SERVICE: &str = env!("CARGO_PKG_NAME");
+
+#[derive(Clone, Copy, Default, PartialEq)]
+pub enum CredentialField {
+    #[default]
+    Username,
+    Token,
+    Persist,
+}
+
+#[derive(Default)]
+pub struct CredentialsInput {
+    pub username: String,
+    pub token: String,
+    pub persist: bool,
+    pub focus: CredentialField,
+}
+
+impl CredentialsInput {
+    pub fn with_username(username: &str) -> Self {
+        CredentialsInput {
+            username: username.to_string(),
+            focus: if username.is_empty() {
+                CredentialField::Username
+            } else {
+                CredentialField::Token
+            },
+            persist: true,
+            ..CredentialsInput::default()
+        }
+    }
+
+    pub fn next_field(&mut self) {
+        self.focus = match self.focus {
+            CredentialField::Username => CredentialField::Token,
+            CredentialField::Token => CredentialField::Persist,
+            CredentialField::Persist => CredentialField::Username,
+        };
+    }
+
+    pub fn push(&mut self, c: char) {
+        match self.focus {
+            CredentialField::Username => self.username.push(c),
+            CredentialField::Token => self.token.push(c),
+            CredentialField::Persist => {
+                if c == ' ' {
+                    self.persist = !self.persist;
+                }
+            }
+        }
+    }
+
+    pub fn pop(&mut self) {
+        match self.focus {
+            CredentialField::Username => {
+                self.username.pop();
+            }
+            CredentialField::Token => {
+                self.token.pop();
+            }
+            CredentialField::Persist => {}
+        }
+    }
+
+    pub fn is_complete(&self) -> bool {
+        !self.username.trim().is_empty() && !self.token.trim().is_empty()
+    }
+
+    pub fn masked_token(&self) -> String {
+        "*".repeat(self.token.chars().count())
+    }
+}
+
+/// The token saved in the OS keyring for `username`, if there is one.
+pub fn load_keyring_token(username: &str) -> Option<String> {
+    if username.is_empty() {
+        return None;
+    }
+
+    match Entry::new(KEYRING_SERVICE, username).and_then(|entry| entry.get_password()) {
+        Ok(token) => Some(token),
+        Err(keyring::Error::NoEntry) => None,
+        Err(e) => {
+            let keyring_error = format!("Failed to read the token from the keyring: {}", e);
+            trace_dbg!(level: tracing::Level::WARN, keyring_error);
+            None
+        }
+    }
+}
+
+/// Save the token in the OS keyring and the username in `.env`, removing any plaintext `PAT`
+/// entry left there by earlier versions.
+pub fn persist_credentials(username: &str, token: &str) -> Result<(), DependabotTrackerError> {
+    save_keyring_token(username, token)?;
+
+    persist_username(username).map_err(DependabotTrackerError::from)
+}
+
+/// Save the token in the OS keyring under the username.
+pub fn save_keyring_token(username: &str, token: &str) -> Result<(), DependabotTrackerError> {
+    Entry::new(KEYRING_SERVICE, username)
+        .and_then(|entry| entry.set_password(token))
+        .map_err(|e| DependabotTrackerError::Keyring(e.to_string()))
+}
+
+/// Write the username into `.env`, replacing any existing `GH_USERNAME`/`PAT` entries.
+fn persist_username(username: &str) -> io::Result<()> {
+    let env_file = PathBuf::from(".").join(".env");
+    let existing = fs::read_to_string(&env_file).unwrap_or_default();
+
+    let mut lines: Vec<String> = existing
+        .lines()
+        .filter(|line| !line.starts_with("GH_USERNAME=") && !line.starts_with("PAT="))
+        .map(String::from)
+        .collect();
+    lines.push(format!("GH_USERNAME={}", username));
+
+    fs::write(env_file, lines.join("\n") + "\n")
+}
@@ -1,27 +1,90 @@
-use std::cmp::Ordering;
-
+use chrono::{DateTime, Duration, Utc};
 use ratatui::{
     layout::{Constraint, Direction, Layout, Margin, Rect},
-    style::{Color, Style, Stylize},
+    style::{Style, Stylize},
+    symbols::Marker,
     text::{Line, Span, Text},
     widgets::{
-        Bar, BarChart, BarGroup, Block, Borders, Clear, List, ListItem, Padding, Paragraph,
-        Scrollbar, ScrollbarOrientation, Wrap,
+        Axis, Bar, BarChart, BarGroup, Block, Borders, Cell, Chart, Clear, Dataset, Gauge,
+        GraphType, List, ListItem, ListState, Padding, Paragraph, Row, Scrollbar,
+        ScrollbarOrientation, Table, TableState, Wrap,
     },
     Frame,
 };
 
+use crate::alert_text::AlertText;
 use crate::app::App;
+use crate::audit::AuditComparison;
+use crate::credentials::CredentialField;
+use crate::dependabot::format_age;
+use crate::dependabot::Dependabot;
+use crate::dependabot::DependabotSeverity;
+use crate::dependabot::DependabotState;
+use crate::dependabot::DismissReason;
+use crate::dismissal::DismissalField;
+use crate::event_log::{count_events, AlertEvent, AlertEventKind};
+use crate::help::help_sections;
+use crate::keymap::Action;
+use crate::mouse::RepositoryListArea;
+use crate::osv::{OsvAdvisory, OsvLookup};
+use crate::pull_request::{CiStatus, DependabotPullRequest};
+use crate::repository::{AlertsStatus, Repository};
+use crate::repository_list::{RepositoryList, SortMode};
+use crate::repository_tree::{SectionHeader, TreeRow};
+use crate::risk::{format_risk_score, RiskConfig};
+use crate::search::RepositorySearch;
+use crate::security_alert::open_alert_count;
+use crate::setup::SetupStep;
+use crate::storage::AlertTrendPoint;
+use crate::theme::Theme;
+use crate::timezone::DisplayTimezone;
+
+// the window for the "recently auto-dismissed" overview stat
+const RECENT_AUTO_DISMISSAL_DAYS: i64 = 30;
+// how many of the worst open alerts the repository list preview shows
+const PREVIEW_ALERT_COUNT: usize = 5;
+// how many repositories the overview's riskiest repositories table lists
+const RISKIEST_REPOSITORY_COUNT: usize = 10;
+// the repository table's column headers and the sort each one stands for, if any
+const REPOSITORY_COLUMNS: [(&str, Option<SortMode>); 9] = [
+    ("Repository", Some(SortMode::Name)),
+    ("Visibility", Some(SortMode::Visibility)),
+    ("Critical", Some(SortMode::CriticalAlerts)),
+    ("High", Some(SortMode::HighAlerts)),
+    ("Medium", Some(SortMode::MediumAlerts)),
+    ("Low", Some(SortMode::LowAlerts)),
+    ("Total", Some(SortMode::TotalAlerts)),
+    ("Risk", None),
+    ("Last Alert", Some(SortMode::LastUpdatedAlert)),
+];
 
-#[derive(Clone, Copy, Default)]
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
 pub enum CurrentScreen {
     #[default]
     Overview,
     ProjectList,
     Project,
     DependabotDetails,
+    AlertDetail,
+    ResolvedAlerts,
+    CodeScanning,
+    SecretScanning,
     Update,
     Updating,
+    Credentials,
+    Dismiss,
+    DistinctAlerts,
+    CopyleftReport,
+    ConfirmOpenAlerts,
+    AuditComparison,
+    Error,
+    Login,
+    Dependencies,
+    Export,
+    WhatsNew,
+    Changes,
+    Profiles,
+    Setup,
 }
 
 pub fn render_screen(app: &mut App, frame: &mut Frame, chunks: &[Rect]) {
@@ -30,140 +93,868 @@ pub fn render_screen(app: &mut App, frame: &mut Frame, chunks: &[Rect]) {
         CurrentScreen::ProjectList => render_project_list(app, frame, chunks),
         CurrentScreen::Project => render_project(app, frame, chunks),
         CurrentScreen::DependabotDetails => render_dependabot_details(app, frame, chunks),
+        CurrentScreen::AlertDetail => render_alert_detail(app, frame, chunks),
+        CurrentScreen::ResolvedAlerts => render_resolved_alerts(app, frame, chunks),
+        CurrentScreen::CodeScanning | CurrentScreen::SecretScanning => {
+            render_scanning_alerts(app, frame, chunks)
+        }
+        CurrentScreen::DistinctAlerts => render_distinct_alerts(app, frame, chunks),
+        CurrentScreen::CopyleftReport => render_copyleft_report(app, frame, chunks),
+        CurrentScreen::Dependencies => render_dependencies(app, frame, chunks),
+        CurrentScreen::AuditComparison => render_audit_comparison(app, frame, chunks),
+        CurrentScreen::Changes => render_changes(app, frame, chunks),
+        CurrentScreen::Profiles => render_profiles(app, frame, chunks),
         _ => {}
     }
 }
 
 pub fn render_popup(app: &mut App, frame: &mut Frame) {
     match app.current_screen {
-        CurrentScreen::Update => render_update_popup(frame),
+        CurrentScreen::Update => render_update_popup(frame, &app.theme),
         CurrentScreen::Updating => render_updating_popup(app, frame),
+        CurrentScreen::Credentials => render_credentials_popup(app, frame),
+        CurrentScreen::Setup => render_setup_popup(app, frame),
+        CurrentScreen::Dismiss => render_dismiss_popup(app, frame),
+        CurrentScreen::ConfirmOpenAlerts => render_confirm_open_alerts_popup(app, frame),
+        CurrentScreen::Error => render_error_popup(app, frame),
+        CurrentScreen::Login => render_login_popup(app, frame),
+        CurrentScreen::Export => render_export_popup(app, frame),
+        CurrentScreen::WhatsNew => render_whats_new_popup(app, frame),
         _ => {}
     }
 }
 
-pub fn get_key_hint_text(app: &App) -> Span {
+pub fn get_key_hint_text(app: &App) -> Span<'static> {
+    let theme = app.theme;
+    let keys = |action| app.config.keymap.describe(action);
+    if app.help.visible {
+        return Span::styled(
+            format!(
+                "({}/{}) to scroll / ({}/{}) to close",
+                keys(Action::Up),
+                keys(Action::Down),
+                keys(Action::Back),
+                keys(Action::Help)
+            ),
+            Style::default().fg(theme.hint),
+        );
+    }
     match app.current_screen {
-        CurrentScreen::Overview => Span::styled(
-            "(r) to view repositories / (u) to update repositories / (q) to quit",
-            Style::default().fg(Color::Red),
-        ),
-        CurrentScreen::ProjectList => Span::styled(
-            "(↑/↓) to navigate / (enter) to view repository / (q) to quit / (o) to view overview / (u) to update repositories",
-            Style::default().fg(Color::Red),
+        CurrentScreen::ProjectList if app.repository_search.editing => Span::styled(
+            "(type) to search / (↑/↓) to navigate / (enter) to finish searching / (esc) to clear search",
+            Style::default().fg(theme.hint),
         ),
         CurrentScreen::Update => Span::styled(
-            "(y/n) to confirm update",
-            Style::default().fg(Color::Red),
+            "(y/n) to confirm update / (s) to refresh stale repositories",
+            Style::default().fg(theme.hint),
         ),
         CurrentScreen::Updating => Span::styled(
-            "(y/n) to confirm update",
-            Style::default().fg(Color::Red),
+            format!(
+                "({}) to cancel / ({}) to keep working while the rest of the repositories come in",
+                keys(Action::Back),
+                keys(Action::SendToBackground)
+            ),
+            Style::default().fg(theme.hint),
+        ),
+        CurrentScreen::DependabotDetails if app.alert_filter.package_search.editing => Span::styled(
+            "(type) to filter by package / (↑/↓) to select alert / (enter) to finish filtering / (esc) to clear filter",
+            Style::default().fg(theme.hint),
+        ),
+        CurrentScreen::ConfirmOpenAlerts => Span::styled(
+            "(y/n) to confirm opening alerts",
+            Style::default().fg(theme.hint),
+        ),
+        CurrentScreen::Error => Span::styled(
+            "(enter/esc) to dismiss",
+            Style::default().fg(theme.hint),
         ),
-        CurrentScreen::Project => Span::styled(
-            "(q) to quit / (o) to view overview / (r) to view repositories / (tab) to switch tabs",
-            Style::default().fg(Color::Red),
+        CurrentScreen::Dismiss => Span::styled(
+            "(↑/↓) to choose reason / (tab) to edit comment / (enter) to dismiss / (esc) to cancel",
+            Style::default().fg(theme.hint),
+        ),
+        CurrentScreen::Credentials if app.config.auth.client_id.is_some() => Span::styled(
+            "(tab) to switch fields / (space) to toggle saving / (enter) to confirm / (ctrl+l) to log in with GitHub / (esc) to keep browsing",
+            Style::default().fg(theme.hint),
+        ),
+        CurrentScreen::Credentials => Span::styled(
+            "(tab) to switch fields / (space) to toggle saving / (enter) to confirm / (esc) to keep browsing",
+            Style::default().fg(theme.hint),
+        ),
+        CurrentScreen::Setup => {
+            let login = if app.setup.step == SetupStep::Token && app.config.auth.client_id.is_some()
+            {
+                " / (ctrl+l) to log in with GitHub"
+            } else {
+                ""
+            };
+            let back = if app.setup.step == SetupStep::Username {
+                "(esc) to skip setup and browse saved data"
+            } else {
+                "(esc) to go back"
+            };
+            let next = if app.setup.is_last_step() {
+                "(enter) to save and fetch"
+            } else {
+                "(enter) to continue"
+            };
+            Span::styled(
+                format!("(type) to answer / {}{} / {}", next, login, back),
+                Style::default().fg(theme.hint),
+            )
+        }
+        CurrentScreen::Login => Span::styled("(esc) to cancel", Style::default().fg(theme.hint)),
+        CurrentScreen::Export => Span::styled(
+            "(type) to edit the path / (enter) to export / (esc) to cancel",
+            Style::default().fg(theme.hint),
         ),
-        CurrentScreen::DependabotDetails => Span::styled(
-            "(↑/↓) to navigate / (q) to quit / (r) to view repositories / (tab) to switch tabs",
-            Style::default().fg(Color::Red),
+        CurrentScreen::WhatsNew => Span::styled(
+            "(c) to view all changes / (enter/esc) to dismiss",
+            Style::default().fg(theme.hint),
+        ),
+        // the rest are listed in the help popup, rather than overflowing the footer
+        _ => Span::styled(
+            format!(
+                "({}) to show keybindings / ({}) to quit",
+                keys(Action::Help),
+                keys(Action::Quit)
+            ),
+            Style::default().fg(theme.hint),
         ),
     }
 }
 
-pub fn get_navigation_text(app: &App) -> Span {
+pub fn get_navigation_text(app: &App) -> Span<'_> {
+    let theme = app.theme;
     match app.current_screen {
-        CurrentScreen::Overview => Span::styled("Overview", Style::default().fg(Color::Green)),
+        CurrentScreen::Overview => Span::styled("Overview", Style::default().fg(theme.accent)),
         CurrentScreen::ProjectList => {
-            Span::styled("Repository List", Style::default().fg(Color::Yellow))
+            Span::styled("Repository List", Style::default().fg(theme.heading))
         }
         CurrentScreen::Project => {
-            if let Some(current_repo) = app.repositories.get_selected_repository() {
+            if let Some(current_repo) = &app.current_repository {
                 Span::styled(
                     current_repo.name.clone(),
-                    Style::default().fg(Color::Yellow),
+                    Style::default().fg(theme.heading),
                 )
             } else {
-                Span::styled("Repository", Style::default().fg(Color::Yellow))
+                Span::styled("Repository", Style::default().fg(theme.heading))
             }
         }
-        CurrentScreen::DependabotDetails => Span::styled(
-            app.repositories
-                .get_selected_repository()
-                .unwrap()
-                .name
-                .clone(),
-            Style::default().fg(Color::Yellow),
+        CurrentScreen::DependabotDetails
+        | CurrentScreen::ResolvedAlerts
+        | CurrentScreen::CodeScanning
+        | CurrentScreen::SecretScanning => Span::styled(
+            app.current_repository.as_ref().unwrap().name.clone(),
+            Style::default().fg(theme.heading),
+        ),
+        CurrentScreen::AlertDetail => Span::styled(
+            format!(
+                "{} #{}",
+                app.current_repository.as_ref().unwrap().name,
+                app.alert_detail.unwrap_or_default()
+            ),
+            Style::default().fg(theme.heading),
         ),
-        CurrentScreen::Update => Span::styled("Updating", Style::default().fg(Color::LightRed)),
-        CurrentScreen::Updating => Span::styled("Updating", Style::default().fg(Color::LightRed)),
+        CurrentScreen::Update => Span::styled("Updating", Style::default().fg(theme.popup)),
+        CurrentScreen::Updating => Span::styled("Updating", Style::default().fg(theme.popup)),
+        CurrentScreen::Credentials => Span::styled("Credentials", Style::default().fg(theme.popup)),
+        CurrentScreen::Setup => Span::styled("Setup", Style::default().fg(theme.popup)),
+        CurrentScreen::Dismiss => Span::styled("Dismiss Alert", Style::default().fg(theme.popup)),
+        CurrentScreen::ConfirmOpenAlerts => {
+            Span::styled("Open Alerts", Style::default().fg(theme.popup))
+        }
+        CurrentScreen::Error => Span::styled("Error", Style::default().fg(theme.popup)),
+        CurrentScreen::Login => Span::styled("Login", Style::default().fg(theme.popup)),
+        CurrentScreen::Export => Span::styled("Export", Style::default().fg(theme.popup)),
+        CurrentScreen::WhatsNew => Span::styled("What's New", Style::default().fg(theme.popup)),
+        CurrentScreen::Changes => Span::styled("Changes", Style::default().fg(theme.heading)),
+        CurrentScreen::Profiles => Span::styled("Profiles", Style::default().fg(theme.heading)),
+        CurrentScreen::AuditComparison => {
+            Span::styled("Audit Comparison", Style::default().fg(theme.heading))
+        }
+        CurrentScreen::DistinctAlerts => {
+            Span::styled("Distinct Alerts", Style::default().fg(theme.heading))
+        }
+        CurrentScreen::CopyleftReport => {
+            Span::styled("Copyleft Report", Style::default().fg(theme.heading))
+        }
+        CurrentScreen::Dependencies => {
+            Span::styled("Dependencies", Style::default().fg(theme.heading))
+        }
     }
     .to_owned()
 }
 
 fn render_overview(app: &mut App, frame: &mut Frame, chunks: &[Rect]) {
-    let repository_count = app.repositories.repos.len();
+    let theme = app.theme;
+    let repositories = app.visible_repositories();
+    let repository_count = repositories.owned().count();
     let mut low_alerts_count = 0;
     let mut medium_alerts_count = 0;
     let mut high_alerts_count = 0;
     let mut critical_alerts_count = 0;
     if repository_count > 0 {
-        low_alerts_count = app
-            .repositories
-            .repos
-            .iter()
-            .map(|r| r.low_alerts as u64)
-            .sum();
-        medium_alerts_count = app
-            .repositories
-            .repos
-            .iter()
-            .map(|r| r.medium_alerts as u64)
-            .sum();
-        high_alerts_count = app
-            .repositories
-            .repos
-            .iter()
-            .map(|r| r.high_alerts as u64)
-            .sum();
-        critical_alerts_count = app
-            .repositories
-            .repos
-            .iter()
-            .map(|r| r.critical_alerts as u64)
-            .sum();
+        low_alerts_count = repositories.owned().map(|r| r.low_alerts as u64).sum();
+        medium_alerts_count = repositories.owned().map(|r| r.medium_alerts as u64).sum();
+        high_alerts_count = repositories.owned().map(|r| r.high_alerts as u64).sum();
+        critical_alerts_count = repositories.owned().map(|r| r.critical_alerts as u64).sum();
+    }
+    let mut title = format!("Alert Levels for {} Repositories", repository_count);
+    if let Some(group) = &app.selected_group {
+        title.push_str(&format!(" in {}", group));
+    }
+    if let Some(topic) = &app.selected_topic {
+        title.push_str(&format!(" tagged {}", topic));
+    }
+    if app.hide_archived {
+        title.push_str(" (hiding archived)");
+    }
+    if app.repositories_truncated {
+        title.push_str(" (limited by max_repositories)");
     }
-    let title = format!("Alert Levels for {} Repositories", repository_count);
 
-    let barchart = get_dependabot_bar_chart(
-        &title,
-        low_alerts_count,
-        medium_alerts_count,
-        high_alerts_count,
-        critical_alerts_count,
+    let mut stats = Vec::<Line>::new();
+    let overdue_count = app.config.sla.overdue_count(&repositories, Utc::now());
+    stats.push(Line::from(Span::styled(
+        format!("Open alerts past their SLA: {}", overdue_count),
+        Style::default().fg(if overdue_count > 0 {
+            theme.error
+        } else {
+            theme.text
+        }),
+    )));
+    stats.push(Line::from(Span::styled(
+        format!(
+            "Auto-dismissed by GitHub in the last {} days: {}",
+            RECENT_AUTO_DISMISSAL_DAYS,
+            repositories.recently_auto_dismissed_count(Duration::days(RECENT_AUTO_DISMISSAL_DAYS))
+        ),
+        Style::default().fg(theme.text),
+    )));
+
+    stats.push(Line::from(Span::styled(
+        format!(
+            "Open code scanning alerts: {} / Open secret scanning alerts: {}",
+            repositories
+                .owned()
+                .map(|repo| open_alert_count(&repo.code_scanning_alerts))
+                .sum::<usize>(),
+            repositories
+                .owned()
+                .map(|repo| open_alert_count(&repo.secret_scanning_alerts))
+                .sum::<usize>()
+        ),
+        Style::default().fg(theme.text),
+    )));
+
+    stats.push(Line::from(Span::styled(
+        format!(
+            "Repositories missing a security policy: {}",
+            repositories.missing_security_policy_count()
+        ),
+        Style::default().fg(theme.text),
+    )));
+
+    let unchecked_count = repositories.unchecked_count();
+    stats.push(Line::from(Span::styled(
+        if unchecked_count > 0 {
+            format!(
+                "Repositories with no open alerts: {} ({} not checked)",
+                repositories.without_open_alerts_count(),
+                unchecked_count
+            )
+        } else {
+            format!(
+                "Repositories with no open alerts: {}",
+                repositories.without_open_alerts_count()
+            )
+        },
+        Style::default().fg(theme.text),
+    )));
+
+    let inaccessible_repos = repositories.repository_names_with_status(AlertsStatus::NoAccess);
+    if !inaccessible_repos.is_empty() {
+        stats.push(Line::from(Span::styled(
+            format!(
+                "Token cannot access {} repositories (showing last known data): {}",
+                inaccessible_repos.len(),
+                inaccessible_repos.join(", ")
+            ),
+            Style::default().fg(theme.warning),
+        )));
+    }
+    let alerts_disabled_repos = repositories.repository_names_with_status(AlertsStatus::Disabled);
+    if !alerts_disabled_repos.is_empty() {
+        stats.push(Line::from(Span::styled(
+            format!(
+                "Dependabot alerts are disabled for {} repositories: {}",
+                alerts_disabled_repos.len(),
+                alerts_disabled_repos.join(", ")
+            ),
+            Style::default().fg(theme.warning),
+        )));
+    }
+
+    let overview_chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(1),
+            Constraint::Length(stats.len() as u16 + 1),
+        ])
+        .split(chunks[1]);
+
+    let stats_paragraph = Paragraph::new(stats)
+        .block(Block::default().borders(Borders::TOP))
+        .wrap(Wrap { trim: true });
+
+    let chart_chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
+        .split(overview_chunks[0]);
+    let side_chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(chart_chunks[1]);
+    let ecosystems = repositories.open_alerts_by_ecosystem();
+    let ecosystem_barchart = get_ecosystem_bar_chart(&ecosystems, &theme);
+    frame.render_widget(ecosystem_barchart, side_chunks[0]);
+    render_riskiest_repositories(
+        frame,
+        side_chunks[1],
+        &repositories,
+        &app.config.risk,
+        &theme,
     );
 
-    frame.render_widget(barchart, chunks[1]);
+    if app.show_alert_trend {
+        render_alert_trend(app, frame, chart_chunks[0]);
+    } else if app.show_severity_percentages {
+        render_severity_distribution(
+            frame,
+            chart_chunks[0],
+            &app.theme,
+            &title,
+            low_alerts_count,
+            medium_alerts_count,
+            high_alerts_count,
+            critical_alerts_count,
+        );
+    } else {
+        let barchart = get_dependabot_bar_chart(
+            &app.theme,
+            &title,
+            low_alerts_count,
+            medium_alerts_count,
+            high_alerts_count,
+            critical_alerts_count,
+        );
+        frame.render_widget(barchart, chart_chunks[0]);
+    }
+    frame.render_widget(stats_paragraph, overview_chunks[1]);
+}
+
+/// Draw the `/query` line at the top of `area` while a search is active, returning the area
+/// left below it.
+fn render_search_line(
+    frame: &mut Frame,
+    area: Rect,
+    search: &RepositorySearch,
+    theme: &Theme,
+) -> Rect {
+    if !search.is_active() {
+        return area;
+    }
+
+    let search_chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(1), Constraint::Min(0)])
+        .split(area);
+    let search_style = if search.editing {
+        Style::default().fg(theme.accent)
+    } else {
+        Style::default().fg(theme.text)
+    };
+    let mut search_line = vec![Span::styled(format!("/{}", search.query), search_style)];
+    if search.editing {
+        search_line.push(Span::styled(" ", search_style.reversed()));
+    }
+    frame.render_widget(Paragraph::new(Line::from(search_line)), search_chunks[0]);
+
+    search_chunks[1]
 }
 
 fn render_project_list(app: &mut App, frame: &mut Frame, chunks: &[Rect]) {
-    let mut list_repos = Vec::<ListItem>::new();
+    let theme = app.theme;
+    let rows = app.repository_rows();
+    let highlighted_row = app.highlighted_row(&rows);
+    let repositories = app.visible_repositories();
+    let mut table_rows = Vec::<Row>::new();
+
+    for row in rows.iter() {
+        let index = match row {
+            TreeRow::Section(header) => {
+                table_rows.push(section_header_row(header, &theme));
+                continue;
+            }
+            TreeRow::Repository(index) => *index,
+        };
+        let repo = &repositories.repos[index];
+        let mut name = repo.name.clone();
+        if let Some(badge) = repo.alerts_status.badge() {
+            name.push(' ');
+            name.push_str(badge);
+        }
+        if repo.has_security_policy == Some(false) {
+            name.push_str(" [no policy]");
+        }
+        let critical_style = if repo.critical_alerts > 0 {
+            Style::default().fg(theme.critical).bold()
+        } else {
+            Style::default()
+        };
+        let last_alert = repo
+            .last_alert_update()
+            .and_then(|updated_at| DateTime::parse_from_rfc3339(updated_at).ok())
+            .map(|updated_at| {
+                app.config
+                    .timezone
+                    .format(updated_at.with_timezone(&Utc), "%Y-%m-%d")
+            })
+            .unwrap_or_else(|| "-".to_string());
+        table_rows.push(
+            Row::new(vec![
+                Cell::from(name),
+                Cell::from(repo.visibility_label().to_string()),
+                Cell::from(repo.critical_alerts.to_string()).style(critical_style),
+                Cell::from(repo.high_alerts.to_string()),
+                Cell::from(repo.medium_alerts.to_string()),
+                Cell::from(repo.low_alerts.to_string()),
+                Cell::from(repo.total_active_alerts.to_string()),
+                Cell::from(format_risk_score(app.config.risk.score(repo))),
+                Cell::from(last_alert),
+            ])
+            // the counts of repositories whose alerts couldn't be read aren't to be trusted
+            .style(Style::default().fg(match repo.alerts_status {
+                AlertsStatus::Enabled => theme.heading,
+                _ => theme.muted,
+            })),
+        );
+    }
+
+    // a highlighted section header has no repository to preview
+    let preview_repo = highlighted_row
+        .filter(|row| matches!(rows[*row], TreeRow::Repository(_)))
+        .and_then(|_| repositories.get_selected_repository().cloned());
+    let sort_mode = repositories.sort_mode();
+    drop(repositories);
+
+    let list_chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
+        .split(chunks[1]);
+
+    // the sorted column is marked in the header
+    let header = Row::new(REPOSITORY_COLUMNS.map(|(title, column_sort)| {
+        if column_sort == Some(sort_mode) {
+            format!("{} ▼", title)
+        } else {
+            title.to_string()
+        }
+    }))
+    .style(Style::default().fg(theme.accent).bold());
+    let table = Table::new(
+        table_rows,
+        [
+            Constraint::Min(20),
+            Constraint::Length(10),
+            Constraint::Length(10),
+            Constraint::Length(6),
+            Constraint::Length(8),
+            Constraint::Length(5),
+            Constraint::Length(7),
+            Constraint::Length(6),
+            Constraint::Length(12),
+        ],
+    )
+    .header(header)
+    .highlight_style(Style::default().fg(theme.text))
+    .highlight_symbol(">> ");
+
+    let list_area = render_search_line(frame, list_chunks[0], &app.repository_search, &theme);
+
+    // the table's rows include the section headers, so it's given the highlighted row rather
+    // than the selected repository's index
+    let mut table_state = TableState::default()
+        .with_offset(app.repositories.offset())
+        .with_selected(highlighted_row);
+    frame.render_stateful_widget(table, list_area, &mut table_state);
+    app.repositories.set_offset(table_state.offset());
+    // the rows start below the column headers
+    app.hit_areas.repository_list = Some(RepositoryListArea {
+        area: Rect {
+            y: list_area.y + 1,
+            height: list_area.height.saturating_sub(1),
+            ..list_area
+        },
+        offset: table_state.offset(),
+        rows,
+    });
+
+    if let Some(preview_repo) = preview_repo {
+        render_repository_preview(frame, list_chunks[1], &preview_repo, &app.theme);
+    }
+}
+
+/// A section header's row: the owner, or the watched repositories, marked `▸` when collapsed,
+/// with the number of repositories in the section and their alert subtotals.
+fn section_header_row<'a>(header: &SectionHeader, theme: &Theme) -> Row<'a> {
+    let marker = if header.collapsed { "▸" } else { "▾" };
+    Row::new(vec![
+        format!(
+            "{} {} ({})",
+            marker,
+            header.section.title(),
+            header.repository_count
+        ),
+        String::new(),
+        header.critical_alerts.to_string(),
+        header.high_alerts.to_string(),
+        header.medium_alerts.to_string(),
+        header.low_alerts.to_string(),
+        header.total_active_alerts.to_string(),
+        String::new(),
+        String::new(),
+    ])
+    .style(Style::default().fg(theme.accent).bold())
+}
+
+/// The highlighted repository's severity chart and worst open alerts, shown beside the list.
+fn render_repository_preview(frame: &mut Frame, area: Rect, repo: &Repository, theme: &Theme) {
+    let preview_block = Block::default()
+        .title(repo.name.as_str())
+        .borders(Borders::LEFT);
+    let preview_chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(preview_block.inner(area));
+
+    let barchart = get_dependabot_bar_chart(
+        theme,
+        "Alert Levels",
+        repo.low_alerts as u64,
+        repo.medium_alerts as u64,
+        repo.high_alerts as u64,
+        repo.critical_alerts as u64,
+    );
+
+    let top_alerts: Vec<ListItem> = repo
+        .open_alerts_by_severity()
+        .into_iter()
+        .take(PREVIEW_ALERT_COUNT)
+        .map(|dependabot| {
+            ListItem::new(Line::from(Span::styled(
+                format!(
+                    "{: <10} {}",
+                    dependabot.severity, dependabot.dependency_name
+                ),
+                Style::default().fg(theme.heading),
+            )))
+        })
+        .collect();
+    let top_alerts_list = List::new(top_alerts).block(Block::default().title("Top Alerts"));
 
-    for repo in app.repositories.repos.iter() {
-        list_repos.push(ListItem::new(Line::from(Span::styled(
-            format!("{: <35} : {} alerts", repo.name, repo.total_active_alerts),
-            Style::default().fg(Color::Yellow),
+    frame.render_widget(preview_block, area);
+    frame.render_widget(barchart, preview_chunks[0]);
+    frame.render_widget(top_alerts_list, preview_chunks[1]);
+}
+
+fn render_distinct_alerts(app: &mut App, frame: &mut Frame, chunks: &[Rect]) {
+    let theme = app.theme;
+    let distinct_alerts = app.visible_repositories().distinct_alerts();
+    let mut list_alerts = Vec::<ListItem>::new();
+
+    for distinct_alert in distinct_alerts.iter() {
+        list_alerts.push(ListItem::new(Line::from(Span::styled(
+            format!(
+                "{: <30} {: <15} {: <20} {: <10} : {: >3} repos : {}",
+                distinct_alert.dependency_name,
+                distinct_alert
+                    .vulnerable_version_range
+                    .as_deref()
+                    .unwrap_or("N/A"),
+                distinct_alert.ghsa_id.as_deref().unwrap_or("N/A"),
+                distinct_alert.severity,
+                distinct_alert.repositories.len(),
+                distinct_alert.repositories.join(", ")
+            ),
+            Style::default().fg(theme.heading),
+        ))));
+    }
+
+    let list = List::new(list_alerts)
+        .block(Block::default().title(format!(
+            "{} Distinct Open Alerts (package / vulnerable range / advisory / worst severity)",
+            distinct_alerts.len()
+        )))
+        .highlight_style(Style::default().fg(theme.text))
+        .highlight_symbol(">> ");
+
+    frame.render_stateful_widget(list, chunks[1], &mut app.distinct_alerts_state);
+}
+
+fn render_dependencies(app: &mut App, frame: &mut Frame, chunks: &[Rect]) {
+    let theme = app.theme;
+    let dependencies = app.visible_repositories().vulnerable_dependencies();
+    let dependency_chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
+        .split(chunks[1]);
+
+    let list_dependencies: Vec<ListItem> = dependencies
+        .iter()
+        .map(|dependency| {
+            ListItem::new(Line::from(Span::styled(
+                format!(
+                    "{: <30} {: <10} {: <10} : {: >3} repos : {: >3} alerts",
+                    dependency.dependency_name,
+                    dependency.dependency_ecosystem,
+                    dependency.severity,
+                    dependency.repositories.len(),
+                    dependency.alert_count()
+                ),
+                Style::default().fg(theme.heading),
+            )))
+        })
+        .collect();
+
+    let drilled_in = app.dependency_repositories_state.is_some();
+    let focused_style = |focused: bool| {
+        if focused {
+            Style::default().fg(theme.text)
+        } else {
+            Style::default()
+        }
+    };
+    let list = List::new(list_dependencies)
+        .block(Block::default().title(format!(
+            "{} Vulnerable Packages (package / ecosystem / worst severity)",
+            dependencies.len()
+        )))
+        .highlight_style(focused_style(!drilled_in))
+        .highlight_symbol(">> ");
+    frame.render_stateful_widget(list, dependency_chunks[0], &mut app.dependencies_state);
+
+    let Some(dependency) = app
+        .dependencies_state
+        .selected()
+        .and_then(|index| dependencies.get(index))
+    else {
+        return;
+    };
+    let list_repositories: Vec<ListItem> = dependency
+        .repositories
+        .iter()
+        .map(|affected| {
+            ListItem::new(Line::from(Span::styled(
+                format!(
+                    "{: <30} {: <10} : {: >3} alerts",
+                    affected.name, affected.severity, affected.alert_count
+                ),
+                Style::default().fg(theme.heading),
+            )))
+        })
+        .collect();
+    let repositories_list = List::new(list_repositories)
+        .block(
+            Block::default()
+                .title(format!("Repositories Using {}", dependency.dependency_name))
+                .borders(Borders::LEFT),
+        )
+        .highlight_style(focused_style(drilled_in))
+        .highlight_symbol(">> ");
+    let mut preview_state = ListState::default();
+    let repositories_state = app
+        .dependency_repositories_state
+        .as_mut()
+        .unwrap_or(&mut preview_state);
+    frame.render_stateful_widget(repositories_list, dependency_chunks[1], repositories_state);
+}
+
+fn render_copyleft_report(app: &mut App, frame: &mut Frame, chunks: &[Rect]) {
+    let theme = app.theme;
+    let repositories = app.visible_repositories();
+    let copyleft_dependencies = repositories.copyleft_vulnerable_dependencies();
+    let mut list_dependencies = Vec::<ListItem>::new();
+
+    for (repo_name, dependabot) in copyleft_dependencies.iter() {
+        list_dependencies.push(ListItem::new(Line::from(Span::styled(
+            format!(
+                "{: <30} {: <20} {: <10} : {}",
+                dependabot.dependency_name,
+                dependabot.license.as_deref().unwrap_or("N/A"),
+                dependabot.severity,
+                repo_name
+            ),
+            Style::default().fg(theme.heading),
         ))));
     }
 
-    let list = List::new(list_repos)
-        .highlight_style(Style::default().fg(Color::Blue))
+    let list = List::new(list_dependencies)
+        .block(Block::default().title(format!(
+            "{} Open Alerts on Copyleft-Licensed Dependencies (package / license / severity / repository)",
+            copyleft_dependencies.len()
+        )))
+        .highlight_style(Style::default().fg(theme.text))
         .highlight_symbol(">> ");
 
-    frame.render_stateful_widget(list, chunks[1], app.repositories.get_mut_state());
+    frame.render_stateful_widget(list, chunks[1], &mut app.copyleft_report_state);
+}
+
+fn change_style(kind: AlertEventKind, theme: &Theme) -> Style {
+    match kind {
+        AlertEventKind::Opened => Style::default().fg(theme.error),
+        AlertEventKind::Fixed => Style::default().fg(theme.accent),
+        AlertEventKind::Dismissed => Style::default().fg(theme.muted),
+        AlertEventKind::SeverityChanged => Style::default().fg(theme.warning),
+    }
+}
+
+fn change_line<'a>(event: &'a AlertEvent, theme: &Theme) -> Line<'a> {
+    Line::from(vec![
+        Span::styled(
+            format!("{: <10}", event.kind),
+            change_style(event.kind, theme),
+        ),
+        Span::raw(event.describe()),
+    ])
+}
+
+fn render_changes(app: &mut App, frame: &mut Frame, chunks: &[Rect]) {
+    let theme = app.theme;
+    let list_changes: Vec<ListItem> = app
+        .last_changes
+        .iter()
+        .map(|event| ListItem::new(change_line(event, &theme)))
+        .collect();
+
+    let list = List::new(list_changes)
+        .block(Block::default().title(format!(
+            "{} Changes Since the Previous Refresh (change / repository / alert / package / severity)",
+            app.last_changes.len()
+        )))
+        .highlight_style(Style::default().fg(theme.text))
+        .highlight_symbol(">> ");
+
+    frame.render_stateful_widget(list, chunks[1], &mut app.changes_state);
+}
+
+fn render_profiles(app: &mut App, frame: &mut Frame, chunks: &[Rect]) {
+    let theme = app.theme;
+    let profiles = &app.profiles;
+    let list_profiles: Vec<ListItem> = profiles
+        .iter()
+        .map(|profile| {
+            let mut line = format!(
+                "{: <20} {: <8} {: <25} {}",
+                profile.name,
+                profile.provider.to_string(),
+                if profile.username.is_empty() {
+                    "(no username)"
+                } else {
+                    &profile.username
+                },
+                profile.data_dir.display()
+            );
+            if profile.name == app.profile.name {
+                line.push_str(" [current]");
+            }
+            ListItem::new(Line::from(Span::styled(
+                line,
+                Style::default().fg(theme.heading),
+            )))
+        })
+        .collect();
+
+    let list = List::new(list_profiles)
+        .block(Block::default().title(format!(
+            "{} Profiles (name / provider / username / data directory)",
+            profiles.len()
+        )))
+        .highlight_style(Style::default().fg(theme.text))
+        .highlight_symbol(">> ");
+
+    frame.render_stateful_widget(list, chunks[1], &mut app.profiles_state);
+}
+
+fn render_audit_comparison(app: &mut App, frame: &mut Frame, chunks: &[Rect]) {
+    let theme = app.theme;
+    let (Some(report), Some(current_repo)) = (app.current_audit_report(), &app.current_repository)
+    else {
+        return;
+    };
+    let comparison = AuditComparison::new(report, current_repo);
+    let mut lines = Vec::<Line>::new();
+
+    lines.push(Line::from(Span::styled(
+        format!(
+            "Found by the local scanner but not GitHub ({})",
+            comparison.local_only.len()
+        ),
+        Style::default().fg(theme.error),
+    )));
+    for finding in comparison.local_only.iter() {
+        lines.push(Line::from(Span::styled(
+            format!(
+                "  {} ({})",
+                finding.package,
+                finding.advisory_ids.join(", ")
+            ),
+            Style::default().fg(theme.heading),
+        )));
+    }
+
+    lines.push(Line::from(Span::styled(
+        format!(
+            "Flagged by GitHub but not the local scanner ({})",
+            comparison.github_only.len()
+        ),
+        Style::default().fg(theme.error),
+    )));
+    for dependabot in comparison.github_only.iter() {
+        lines.push(Line::from(Span::styled(
+            format!(
+                "  {} ({}, {})",
+                dependabot.dependency_name,
+                dependabot.ghsa_id.as_deref().unwrap_or("N/A"),
+                dependabot.severity
+            ),
+            Style::default().fg(theme.heading),
+        )));
+    }
+
+    lines.push(Line::from(Span::styled(
+        format!("Found by both ({})", comparison.in_both.len()),
+        Style::default().fg(theme.accent),
+    )));
+    for (finding, dependabot) in comparison.in_both.iter() {
+        lines.push(Line::from(Span::styled(
+            format!(
+                "  {} (#{}, {})",
+                finding.package, dependabot.number, dependabot.severity
+            ),
+            Style::default().fg(theme.text),
+        )));
+    }
+
+    let paragraph = Paragraph::new(lines)
+        .block(Block::default().title(format!("Local Audit vs GitHub for {}", current_repo.name)))
+        .wrap(Wrap { trim: false });
+
+    frame.render_widget(paragraph, chunks[1]);
 }
 
 fn render_project(app: &mut App, frame: &mut Frame, chunks: &[Rect]) {
+    let theme = app.theme;
     let tab_chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([Constraint::Length(3), Constraint::Min(1)])
@@ -173,32 +964,137 @@ fn render_project(app: &mut App, frame: &mut Frame, chunks: &[Rect]) {
         .constraints([Constraint::Percentage(30), Constraint::Percentage(70)])
         .split(tab_chunks[1]);
 
-    let current_repo = app.current_repository.as_ref().unwrap();
+    let current_repo = app.visible_current_repository().unwrap();
     let mut lines = Vec::<Line>::new();
     lines.push(Line::from(vec![Span::styled(
         format!("ID: {}", current_repo.id),
-        Style::default().fg(Color::Blue),
+        Style::default().fg(theme.text),
     )]));
     lines.push(Line::from(vec![Span::styled(
         format!("Name: {}", current_repo.name),
-        Style::default().fg(Color::Blue),
+        Style::default().fg(theme.text),
     )]));
     lines.push(Line::from(vec![Span::styled(
         format!("Private: {}", current_repo.private),
-        Style::default().fg(Color::Blue),
+        Style::default().fg(theme.text),
     )]));
     lines.push(Line::from(vec![Span::styled(
         format!("URL: {}", current_repo.url),
-        Style::default().fg(Color::Blue),
+        Style::default().fg(theme.text),
+    )]));
+    lines.push(Line::from(vec![Span::styled(
+        format!(
+            "Visibility: {}",
+            current_repo.visibility.as_deref().unwrap_or("N/A")
+        ),
+        Style::default().fg(theme.text),
     )]));
     lines.push(Line::from(vec![Span::styled(
         format!("Archived: {}", current_repo.archived),
-        Style::default().fg(Color::Blue),
+        Style::default().fg(theme.text),
+    )]));
+    lines.push(Line::from(vec![Span::styled(
+        format!("Fork: {}", current_repo.fork),
+        Style::default().fg(theme.text),
+    )]));
+    lines.push(Line::from(vec![Span::styled(
+        format!(
+            "Last pushed: {}",
+            current_repo
+                .pushed_at
+                .map(|pushed_at| app.config.timezone.format_timestamp(pushed_at))
+                .unwrap_or_else(|| "N/A".to_string())
+        ),
+        Style::default().fg(theme.text),
+    )]));
+    lines.push(Line::from(vec![Span::styled(
+        format!("Topics: {}", current_repo.topics.join(", ")),
+        Style::default().fg(theme.text),
     )]));
     lines.push(Line::from(vec![Span::styled(
         format!("Total active alerts: {}", current_repo.total_active_alerts),
-        Style::default().fg(Color::Blue),
+        Style::default().fg(theme.text),
+    )]));
+    lines.push(Line::from(vec![Span::styled(
+        format!(
+            "Open code scanning alerts: {}",
+            open_alert_count(&current_repo.code_scanning_alerts)
+        ),
+        Style::default().fg(theme.text),
+    )]));
+    lines.push(Line::from(vec![Span::styled(
+        format!(
+            "Open secret scanning alerts: {}",
+            open_alert_count(&current_repo.secret_scanning_alerts)
+        ),
+        Style::default().fg(theme.text),
     )]));
+    lines.push(Line::from(vec![Span::styled(
+        format!(
+            "Open Dependabot pull requests: {} ({} failing CI)",
+            current_repo.pull_requests.len(),
+            current_repo
+                .pull_requests
+                .iter()
+                .filter(|pull_request| pull_request.ci_status == CiStatus::Failing)
+                .count()
+        ),
+        Style::default().fg(theme.text),
+    )]));
+    lines.push(Line::from(vec![Span::styled(
+        format!(
+            "Mean open alert age: {}",
+            format_age(current_repo.mean_open_alert_age())
+        ),
+        Style::default().fg(theme.text),
+    )]));
+    lines.push(Line::from(vec![Span::styled(
+        format!(
+            "Max open alert age: {}",
+            format_age(current_repo.max_open_alert_age())
+        ),
+        Style::default().fg(theme.text),
+    )]));
+
+    lines.push(Line::from(vec![Span::styled(
+        format!(
+            "Last fetched: {}",
+            current_repo
+                .last_fetched_at
+                .map(|last_fetched_at| app.config.timezone.format_timestamp(last_fetched_at))
+                .unwrap_or_else(|| "N/A".to_string())
+        ),
+        Style::default().fg(theme.text),
+    )]));
+    lines.push(Line::from(vec![Span::styled(
+        format!(
+            "Security policy: {}",
+            match current_repo.has_security_policy {
+                Some(true) => "present",
+                Some(false) => "missing",
+                None => "unknown",
+            }
+        ),
+        Style::default().fg(theme.text),
+    )]));
+    match current_repo.alerts_status {
+        AlertsStatus::Enabled => {}
+        AlertsStatus::NoAccess => lines.push(Line::from(vec![Span::styled(
+            "Token cannot access this repository's alerts, showing last known data",
+            Style::default().fg(theme.heading),
+        )])),
+        AlertsStatus::Disabled => lines.push(Line::from(vec![Span::styled(
+            format!(
+                "Dependabot alerts are disabled for this repository, so it has no alerts to show. Press {} to enable them",
+                app.config.keymap.describe(Action::EnableAlerts)
+            ),
+            Style::default().fg(theme.warning),
+        )])),
+        AlertsStatus::Error => lines.push(Line::from(vec![Span::styled(
+            "The last fetch of this repository's alerts failed, showing last known data",
+            Style::default().fg(theme.warning),
+        )])),
+    }
 
     let project_info = Paragraph::new(lines)
         .block(Block::default().borders(Borders::NONE))
@@ -207,6 +1103,7 @@ fn render_project(app: &mut App, frame: &mut Frame, chunks: &[Rect]) {
     let title = format!("Alert Levels for {}", current_repo.name);
 
     let barchart = get_dependabot_bar_chart(
+        &app.theme,
         &title,
         current_repo.low_alerts as u64,
         current_repo.medium_alerts as u64,
@@ -214,62 +1111,89 @@ fn render_project(app: &mut App, frame: &mut Frame, chunks: &[Rect]) {
         current_repo.critical_alerts as u64,
     );
 
-    frame.render_widget(get_tab_info(app), tab_chunks[0]);
+    render_tab_bar(app, frame, tab_chunks[0]);
     frame.render_widget(project_info, project_chunks[0]);
     frame.render_widget(barchart, project_chunks[1]);
 }
 
 fn render_dependabot_details(app: &mut App, frame: &mut Frame, chunks: &[Rect]) {
+    let theme = app.theme;
     let tab_chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([Constraint::Length(3), Constraint::Min(1)])
         .split(chunks[1]);
 
-    let current_repo = app.current_repository.as_ref().unwrap();
-    let dependabots: Vec<Line> = current_repo
-        .dependabots
+    let visible_alerts = app.visible_alerts();
+    let now = Utc::now();
+    let dependabots: Vec<ListItem> = visible_alerts
         .iter()
-        .flat_map(|dependabot| dependabot.to_text())
+        .map(|dependabot| {
+            let mut text = dependabot.to_text(&app.config.timezone, &theme);
+            if let Some(overdue_by) = app.config.sla.overdue_by(dependabot, now) {
+                text[0] = Line::from(vec![Span::styled(
+                    format!("{} [overdue]", "-".repeat(20)),
+                    Style::default().fg(theme.error),
+                )]);
+                text.insert(1, sla_line(dependabot, overdue_by, now, &theme));
+            }
+            if app.marked_alerts.contains(&dependabot.number) {
+                text[0] = Line::from(vec![Span::styled(
+                    format!("{} [marked]", "-".repeat(20)),
+                    Style::default().fg(theme.heading),
+                )]);
+            }
+            if let Some(pull_request) = app
+                .current_repository
+                .as_ref()
+                .and_then(|repo| repo.pull_request_for(dependabot))
+            {
+                text.insert(1, pull_request_line(pull_request, &theme));
+            }
+            ListItem::new(text)
+        })
         .collect();
-    let dependabot_line_count = dependabots.len();
-    let resized_window = app.chunk_height != tab_chunks[1].height;
+    let item_heights: Vec<usize> = dependabots.iter().map(ListItem::height).collect();
 
-    let paragraph = Paragraph::new(dependabots)
-        .scroll((app.scrollbar.position as u16, 0))
-        .block(Block::default().borders(Borders::RIGHT));
+    let mut list_block = Block::default().borders(Borders::RIGHT);
+    if let Some(filter_label) = app.alert_filter.label() {
+        list_block = list_block.title(Span::styled(
+            filter_label,
+            Style::default().fg(theme.heading),
+        ));
+    }
+    let list = List::new(dependabots)
+        .highlight_style(Style::default().bold())
+        .highlight_symbol(">> ")
+        .block(list_block);
 
     let scrollbar = Scrollbar::new(ScrollbarOrientation::VerticalRight);
 
-    if resized_window {
-        match app
-            .scrollbar
-            .get_length()
-            .cmp(&(tab_chunks[1].height as usize))
-        {
-            Ordering::Greater => {
-                app.scrollbar
-                    .resize(dependabot_line_count - tab_chunks[1].height as usize);
-            }
-            Ordering::Less => {
-                if dependabot_line_count < tab_chunks[1].height as usize {
-                    app.scrollbar.resize(0);
-                } else {
-                    app.scrollbar
-                        .resize(dependabot_line_count - tab_chunks[1].height as usize);
-                }
-            }
-            Ordering::Equal => {
-                app.scrollbar.resize(0);
-            }
-        }
-    }
-    app.chunk_height = tab_chunks[1].height;
+    render_tab_bar(app, frame, tab_chunks[0]);
+    let list_area = render_search_line(
+        frame,
+        tab_chunks[1],
+        &app.alert_filter.package_search,
+        &theme,
+    );
+    frame.render_stateful_widget(list, list_area, app.scrollbar.get_mut_list_state());
+    app.hit_areas.alert_list = Some(list_area);
 
-    frame.render_widget(get_tab_info(app), tab_chunks[0]);
-    frame.render_widget(paragraph, tab_chunks[1]);
+    // count the alerts that fit from the top of the list, for page sizes and the scrollbar
+    let offset = app.scrollbar.get_mut_list_state().offset();
+    let mut remaining_height = list_area.height as usize;
+    let viewport = item_heights
+        .iter()
+        .skip(offset)
+        .take_while(|height| {
+            let fits = **height <= remaining_height;
+            remaining_height = remaining_height.saturating_sub(**height);
+            fits
+        })
+        .count();
+    app.scrollbar.set_viewport(viewport);
     frame.render_stateful_widget(
         scrollbar,
-        tab_chunks[1].inner(&Margin {
+        list_area.inner(&Margin {
             vertical: 1,
             horizontal: 0,
         }),
@@ -277,38 +1201,490 @@ fn render_dependabot_details(app: &mut App, frame: &mut Frame, chunks: &[Rect])
     );
 }
 
-fn get_tab_info(app: &App) -> Paragraph {
+fn sla_line<'a>(
+    dependabot: &'a Dependabot,
+    overdue_by: Duration,
+    now: DateTime<Utc>,
+    theme: &Theme,
+) -> Line<'a> {
+    Line::from(vec![Span::styled(
+        format!(
+            "Open {}, {} days past the {} SLA",
+            format_age(dependabot.age(now)),
+            overdue_by.num_days(),
+            dependabot.severity.to_string().to_lowercase()
+        ),
+        Style::default().fg(theme.error).bold(),
+    )])
+}
+
+fn render_alert_detail(app: &mut App, frame: &mut Frame, chunks: &[Rect]) {
+    let theme = app.theme;
+    let tab_chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(3), Constraint::Min(1)])
+        .split(chunks[1]);
+    render_tab_bar(app, frame, tab_chunks[0]);
+
+    let Some(dependabot) = app.detailed_alert() else {
+        return;
+    };
+    let timezone = &app.config.timezone;
+    let format_time = |time: &Option<String>| {
+        time.as_deref()
+            .map(|time| timezone.format_rfc3339(time))
+            .unwrap_or_else(|| "N/A".to_string())
+    };
+
     let mut lines = Vec::<Line>::new();
-    let mut project_style = Style::default().fg(Color::Green).underlined();
-    let mut dependabot_style = Style::default().fg(Color::Blue);
+    lines.push(Line::from(vec![
+        Span::styled(
+            format!("{} ", dependabot.severity.to_string().to_uppercase()),
+            Style::default()
+                .fg(app.theme.severity_color(dependabot.severity))
+                .bold(),
+        ),
+        Span::styled(
+            dependabot
+                .summary
+                .clone()
+                .unwrap_or_else(|| format!("Alert #{}", dependabot.number)),
+            Style::default().bold(),
+        ),
+    ]));
+    lines.push(Line::from(""));
+    for (label, value) in [
+        ("State", dependabot.state.to_string()),
+        (
+            "Package",
+            format!(
+                "{} ({})",
+                dependabot.dependency_name, dependabot.dependency_ecosystem
+            ),
+        ),
+        (
+            "Manifest",
+            dependabot
+                .manifest_path
+                .clone()
+                .unwrap_or_else(|| "N/A".to_string()),
+        ),
+        (
+            "Scope",
+            dependabot
+                .scope
+                .map(|scope| scope.to_string())
+                .unwrap_or_else(|| "N/A".to_string()),
+        ),
+        (
+            "Fix",
+            dependabot
+                .upgrade_hint()
+                .unwrap_or_else(|| "no patched version has been released yet".to_string()),
+        ),
+        (
+            "GHSA ID",
+            dependabot
+                .ghsa_id
+                .clone()
+                .unwrap_or_else(|| "N/A".to_string()),
+        ),
+        (
+            "CVE ID",
+            dependabot
+                .cve_id
+                .clone()
+                .unwrap_or_else(|| "N/A".to_string()),
+        ),
+        (
+            "CVSS Score",
+            dependabot
+                .cvss_score
+                .map(|score| format!("{:.1}", score))
+                .unwrap_or_else(|| "N/A".to_string()),
+        ),
+        (
+            "Pull Request",
+            app.current_repository
+                .as_ref()
+                .and_then(|repo| repo.pull_request_for(&dependabot))
+                .map(|pull_request| {
+                    format!(
+                        "#{} {} ({}) {}",
+                        pull_request.number,
+                        pull_request.title,
+                        pull_request.ci_status,
+                        pull_request.html_url
+                    )
+                })
+                .unwrap_or_else(|| "N/A".to_string()),
+        ),
+        ("URL", dependabot.html_url.clone()),
+    ] {
+        lines.push(Line::from(vec![
+            Span::styled(format!("{}: ", label), Style::default().fg(theme.text)),
+            Span::raw(value),
+        ]));
+    }
 
-    if let CurrentScreen::DependabotDetails = app.current_screen {
-        project_style = Style::default().fg(Color::Blue);
-        dependabot_style = Style::default().fg(Color::Green).underlined();
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        "Timeline",
+        Style::default().fg(theme.accent).bold(),
+    )));
+    for (label, time) in [
+        ("Created", Some(dependabot.created_at.clone())),
+        ("Updated", Some(dependabot.updated_at.clone())),
+        ("Dismissed", dependabot.dismissed_at.clone()),
+        ("Auto dismissed", dependabot.auto_dismissed_at.clone()),
+        ("Fixed", dependabot.fixed_at.clone()),
+    ] {
+        lines.push(Line::from(vec![
+            Span::styled(format!("{}: ", label), Style::default().fg(theme.text)),
+            Span::raw(format_time(&time)),
+        ]));
+    }
+
+    if !matches!(app.osv_lookup, OsvLookup::Unavailable) {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            "OSV.dev",
+            Style::default().fg(theme.accent).bold(),
+        )));
+        match &app.osv_lookup {
+            OsvLookup::Done(Some(advisory)) => lines.extend(osv_lines(advisory, &theme)),
+            OsvLookup::Done(None) if app.config.offline => lines.push(Line::from(Span::styled(
+                "Not cached, and not looked up while offline",
+                Style::default().fg(theme.muted),
+            ))),
+            OsvLookup::Done(None) => lines.push(Line::from(Span::styled(
+                "No entry found",
+                Style::default().fg(theme.muted),
+            ))),
+            _ => lines.push(Line::from(Span::styled(
+                "Looking up...",
+                Style::default().fg(theme.muted),
+            ))),
+        }
     }
 
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        "Description",
+        Style::default().fg(theme.accent).bold(),
+    )));
+    lines.extend(markdown_lines(
+        dependabot.description.as_deref().unwrap_or("N/A"),
+    ));
+
+    let detail = Paragraph::new(lines)
+        .block(Block::default().padding(Padding::horizontal(1)))
+        .wrap(Wrap { trim: false })
+        .scroll((app.alert_detail_scroll, 0));
+    frame.render_widget(detail, tab_chunks[1]);
+}
+
+/// An advisory's OSV.dev aliases, affected version ranges per package, and references.
+fn osv_lines(advisory: &OsvAdvisory, theme: &Theme) -> Vec<Line<'static>> {
+    let mut lines = Vec::<Line>::new();
+    let aliases = if advisory.aliases.is_empty() {
+        "N/A".to_string()
+    } else {
+        advisory.aliases.join(", ")
+    };
     lines.push(Line::from(vec![
-        Span::styled("Project", project_style),
-        Span::styled(" | ", Style::default().fg(Color::Blue)),
-        Span::styled("Dependabot Details", dependabot_style),
+        Span::styled("Aliases: ", Style::default().fg(theme.text)),
+        Span::raw(aliases),
     ]));
 
+    lines.push(Line::from(Span::styled(
+        "Affected:",
+        Style::default().fg(theme.text),
+    )));
+    for affected in advisory.affected.iter() {
+        let package = affected
+            .package
+            .as_ref()
+            .map(|package| format!("{} ({})", package.name, package.ecosystem))
+            .unwrap_or_else(|| advisory.id.clone());
+        let ranges: Vec<String> = affected
+            .ranges
+            .iter()
+            .flat_map(|range| range.describe())
+            .collect();
+        let ranges = if ranges.is_empty() {
+            "the versions listed on OSV.dev".to_string()
+        } else {
+            ranges.join("; ")
+        };
+        lines.push(Line::from(format!("  • {}: {}", package, ranges)));
+    }
+
+    lines.push(Line::from(Span::styled(
+        "References:",
+        Style::default().fg(theme.text),
+    )));
+    for reference in advisory.references.iter() {
+        lines.push(Line::from(format!(
+            "  • {} {}",
+            reference.reference_type.to_lowercase(),
+            reference.url
+        )));
+    }
+
+    lines
+}
+
+/// Roughly render an advisory's markdown description: headings are bolded, list markers become
+/// bullets, code fences are dropped, and runs of blank lines are collapsed.
+fn markdown_lines(markdown: &str) -> Vec<Line<'static>> {
+    let mut lines = Vec::<Line>::new();
+    let mut previous_blank = false;
+    for line in markdown.lines().map(str::trim_end) {
+        if line.trim_start().starts_with("```") {
+            continue;
+        }
+        if line.trim().is_empty() {
+            if !previous_blank {
+                lines.push(Line::from(""));
+            }
+            previous_blank = true;
+            continue;
+        }
+        previous_blank = false;
+
+        let trimmed = line.trim_start();
+        if trimmed.starts_with('#') {
+            lines.push(Line::from(Span::styled(
+                trimmed.trim_start_matches('#').trim().to_string(),
+                Style::default().bold(),
+            )));
+        } else if let Some(item) = trimmed
+            .strip_prefix("- ")
+            .or_else(|| trimmed.strip_prefix("* "))
+        {
+            let indent = " ".repeat(line.len() - trimmed.len());
+            lines.push(Line::from(format!("{}• {}", indent, item)));
+        } else {
+            lines.push(Line::from(line.to_string()));
+        }
+    }
+
+    lines
+}
+
+fn pull_request_line(pull_request: &DependabotPullRequest, theme: &Theme) -> Line<'static> {
+    let color = match pull_request.ci_status {
+        CiStatus::Passing => theme.accent,
+        CiStatus::Failing => theme.error,
+        CiStatus::Pending | CiStatus::Unknown => theme.warning,
+    };
+
+    Line::from(vec![Span::styled(
+        format!(
+            "Fix waiting in PR #{} ({}): {}",
+            pull_request.number, pull_request.ci_status, pull_request.html_url
+        ),
+        Style::default().fg(color),
+    )])
+}
+
+fn render_scanning_alerts(app: &mut App, frame: &mut Frame, chunks: &[Rect]) {
+    let theme = app.theme;
+    let tab_chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(3), Constraint::Min(1)])
+        .split(chunks[1]);
+    render_tab_bar(app, frame, tab_chunks[0]);
+
+    let Some(repo) = &app.current_repository else {
+        return;
+    };
+    let timezone = &app.config.timezone;
+    let (title, alerts) = match app.current_screen {
+        CurrentScreen::CodeScanning => (
+            "Code Scanning Alerts",
+            security_alert_items(&repo.code_scanning_alerts, timezone, &theme),
+        ),
+        _ => (
+            "Secret Scanning Alerts",
+            security_alert_items(&repo.secret_scanning_alerts, timezone, &theme),
+        ),
+    };
+
+    let list = List::new(alerts)
+        .block(Block::default().title(format!("{} {}", repo.name, title)))
+        .highlight_style(Style::default().bold())
+        .highlight_symbol(">> ");
+    frame.render_stateful_widget(list, tab_chunks[1], &mut app.security_alerts_state);
+}
+
+fn render_resolved_alerts(app: &mut App, frame: &mut Frame, chunks: &[Rect]) {
+    let theme = app.theme;
+    let tab_chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(3), Constraint::Min(1)])
+        .split(chunks[1]);
+    render_tab_bar(app, frame, tab_chunks[0]);
+
+    let Some(repo) = &app.current_repository else {
+        return;
+    };
+    let timezone = &app.config.timezone;
+    let alerts: Vec<ListItem> = app
+        .resolved_alerts()
+        .iter()
+        .map(|dependabot| ListItem::new(resolved_alert_lines(dependabot, timezone, &theme)))
+        .collect();
+
+    let list = List::new(alerts)
+        .block(Block::default().title(format!("{} Resolved Alerts", repo.name)))
+        .highlight_style(Style::default().bold())
+        .highlight_symbol(">> ");
+    frame.render_stateful_widget(list, tab_chunks[1], &mut app.security_alerts_state);
+}
+
+/// A resolved alert, and how, when, and by whom it was resolved.
+fn resolved_alert_lines(
+    dependabot: &Dependabot,
+    timezone: &DisplayTimezone,
+    theme: &Theme,
+) -> Vec<Line<'static>> {
+    let resolved_at = dependabot
+        .resolved_at()
+        .map(|resolved_at| timezone.format_rfc3339(resolved_at))
+        .unwrap_or_else(|| "an unknown time".to_string());
+    let resolution = match dependabot.state {
+        DependabotState::Fixed => format!("Fixed at {}", resolved_at),
+        DependabotState::AutoDismissed => format!("Auto dismissed at {}", resolved_at),
+        _ => format!(
+            "Dismissed by {} at {}: {}",
+            dependabot.dismissed_by.as_deref().unwrap_or("unknown"),
+            resolved_at,
+            dependabot
+                .dismissed_reason_label()
+                .unwrap_or_else(|| "no reason given".to_string())
+        ),
+    };
+    let resolution_color = match dependabot.state {
+        DependabotState::Fixed => theme.accent,
+        _ => theme.muted,
+    };
+
+    let mut lines = vec![
+        Line::from(vec![Span::styled(
+            "-".repeat(20),
+            Style::default().fg(theme.accent),
+        )]),
+        Line::from(vec![
+            Span::styled(
+                format!("{} ", dependabot.severity.to_string().to_uppercase()),
+                Style::default().fg(theme.severity_color(dependabot.severity)),
+            ),
+            Span::styled(
+                format!(
+                    "#{} {} ({})",
+                    dependabot.number, dependabot.dependency_name, dependabot.dependency_ecosystem
+                ),
+                Style::default().fg(theme.text),
+            ),
+        ]),
+        Line::from(Span::styled(
+            resolution,
+            Style::default().fg(resolution_color),
+        )),
+    ];
+    if let Some(dismissed_comment) = &dependabot.dismissed_comment {
+        lines.push(Line::from(Span::styled(
+            format!("\"{}\"", dismissed_comment.trim()),
+            Style::default().fg(theme.text).italic(),
+        )));
+    }
+
+    lines
+}
+
+fn security_alert_items<'a, A: AlertText>(
+    alerts: &'a [A],
+    timezone: &DisplayTimezone,
+    theme: &Theme,
+) -> Vec<ListItem<'a>> {
+    alerts
+        .iter()
+        .map(|alert| ListItem::new(alert.to_text(timezone, theme)))
+        .collect()
+}
+
+// the repository's tabs, in the order `Tab` cycles through them
+const TABS: [(&str, CurrentScreen); 5] = [
+    ("Project", CurrentScreen::Project),
+    ("Dependabot Details", CurrentScreen::DependabotDetails),
+    ("Resolved Alerts", CurrentScreen::ResolvedAlerts),
+    ("Code Scanning", CurrentScreen::CodeScanning),
+    ("Secret Scanning", CurrentScreen::SecretScanning),
+];
+const TAB_SEPARATOR: &str = " | ";
+
+/// Render the tab bar, noting where each tab's label is so it can be clicked.
+fn render_tab_bar(app: &mut App, frame: &mut Frame, area: Rect) {
+    frame.render_widget(get_tab_info(app), area);
+
+    // the labels start inside the border, one after another
+    let mut x = area.x + 1;
+    for (label, screen) in TABS {
+        let width = (label.len() as u16).min(area.right().saturating_sub(x + 1));
+        app.hit_areas
+            .tabs
+            .push((Rect::new(x, area.y + 1, width, 1), screen));
+        x += (label.len() + TAB_SEPARATOR.len()) as u16;
+    }
+}
+
+fn get_tab_info(app: &App) -> Paragraph<'_> {
+    let theme = app.theme;
+    let mut lines = Vec::<Line>::new();
+    let selected_style = Style::default().fg(theme.accent).underlined();
+    let tab_style = Style::default().fg(theme.text);
+    let selected_tab = match app.current_screen {
+        CurrentScreen::DependabotDetails | CurrentScreen::AlertDetail => 1,
+        CurrentScreen::ResolvedAlerts => 2,
+        CurrentScreen::CodeScanning => 3,
+        CurrentScreen::SecretScanning => 4,
+        _ => 0,
+    };
+
+    let mut tabs = Vec::<Span>::new();
+    for (index, (tab, _)) in TABS.into_iter().enumerate() {
+        if index > 0 {
+            tabs.push(Span::styled(TAB_SEPARATOR, tab_style));
+        }
+        tabs.push(Span::styled(
+            tab,
+            if index == selected_tab {
+                selected_style
+            } else {
+                tab_style
+            },
+        ));
+    }
+    lines.push(Line::from(tabs));
+
     Paragraph::new(lines)
         .block(Block::default().borders(Borders::ALL))
         .wrap(Wrap { trim: true })
 }
 
-fn render_update_popup(frame: &mut Frame) {
+fn render_update_popup(frame: &mut Frame, theme: &Theme) {
     frame.render_widget(Clear, frame.size()); //this clears the entire screen and anything already drawn
     let popup_block = Block::default()
         .title("Repositories Update")
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Blue))
+        .border_style(Style::default().fg(theme.text))
         .style(Style::default());
 
     let update_text = Text::styled(
-        "Would you like to update the current list of repositories? (y/n)",
-        Style::default().fg(Color::Red),
+        "Would you like to update the current list of repositories? (y/n)\n\nPress (s) to only refresh repositories that haven't been fetched recently.",
+        Style::default().fg(theme.hint),
     );
     // the `trim: false` will stop the text from being cut off when over the edge of the block
     let update_paragraph = Paragraph::new(update_text)
@@ -319,49 +1695,514 @@ fn render_update_popup(frame: &mut Frame) {
     frame.render_widget(update_paragraph, area);
 }
 
+fn render_confirm_open_alerts_popup(app: &mut App, frame: &mut Frame) {
+    let theme = app.theme;
+    frame.render_widget(Clear, frame.size()); //this clears the entire screen and anything already drawn
+    let popup_block = Block::default()
+        .title("Open Alerts")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.text))
+        .style(Style::default());
+
+    let confirm_text = Text::styled(
+        format!(
+            "Open {} marked alerts in the browser? (y/n)",
+            app.marked_alerts.len()
+        ),
+        Style::default().fg(theme.hint),
+    );
+    let confirm_paragraph = Paragraph::new(confirm_text)
+        .block(popup_block)
+        .wrap(Wrap { trim: false });
+
+    let area = centered_rect(60, 25, frame.size());
+    frame.render_widget(confirm_paragraph, area);
+}
+
+fn render_whats_new_popup(app: &mut App, frame: &mut Frame) {
+    let theme = app.theme;
+    frame.render_widget(Clear, frame.size()); //this clears the entire screen and anything already drawn
+    let popup_block = Block::default()
+        .title("What's New")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.text))
+        .style(Style::default());
+
+    let counts = [
+        AlertEventKind::Opened,
+        AlertEventKind::Fixed,
+        AlertEventKind::Dismissed,
+        AlertEventKind::SeverityChanged,
+    ]
+    .into_iter()
+    .map(|kind| {
+        Span::styled(
+            format!("{}: {}  ", kind, count_events(&app.last_changes, kind)),
+            change_style(kind, &theme),
+        )
+    })
+    .collect::<Vec<_>>();
+    let mut lines = vec![Line::from(counts), Line::default()];
+    let area = centered_rect(60, 50, frame.size());
+    // leave room for the borders, the counts, and the line saying how many more there are
+    let shown = (area.height as usize).saturating_sub(5);
+    lines.extend(
+        app.last_changes
+            .iter()
+            .take(shown)
+            .map(|event| change_line(event, &theme)),
+    );
+    if app.last_changes.len() > shown {
+        lines.push(Line::from(Span::styled(
+            format!(
+                "and {} more, press (c) to view all changes",
+                app.last_changes.len() - shown
+            ),
+            Style::default().fg(theme.text),
+        )));
+    }
+
+    let whats_new_paragraph = Paragraph::new(lines).block(popup_block);
+    frame.render_widget(whats_new_paragraph, area);
+}
+
+fn render_error_popup(app: &mut App, frame: &mut Frame) {
+    let theme = app.theme;
+    frame.render_widget(Clear, frame.size()); //this clears the entire screen and anything already drawn
+    let popup_block = Block::default()
+        .title("Error")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.error))
+        .style(Style::default());
+
+    let error_text = Text::styled(
+        app.error.clone().unwrap_or_default(),
+        Style::default().fg(theme.error),
+    );
+    let error_paragraph = Paragraph::new(error_text)
+        .block(popup_block)
+        .wrap(Wrap { trim: false });
+
+    let area = centered_rect(60, 25, frame.size());
+    frame.render_widget(error_paragraph, area);
+}
+
+fn render_export_popup(app: &mut App, frame: &mut Frame) {
+    let theme = app.theme;
+    frame.render_widget(Clear, frame.size()); //this clears the entire screen and anything already drawn
+    let popup_block = Block::default()
+        .title("Export Alerts")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.text))
+        .style(Style::default());
+
+    let lines = vec![
+        Line::from(Span::styled(
+            "Save every alert in the repositories in view as CSV to:",
+            Style::default().fg(theme.text),
+        )),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled(app.export_path.clone(), Style::default().fg(theme.accent)),
+            Span::styled(" ", Style::default().reversed()),
+        ]),
+    ];
+    let export_paragraph = Paragraph::new(lines)
+        .block(popup_block)
+        .wrap(Wrap { trim: false });
+
+    let area = centered_rect(60, 25, frame.size());
+    frame.render_widget(export_paragraph, area);
+}
+
+/// The keybindings for every screen, generated from the keymap, over whatever screen is showing.
+pub fn render_help_popup(app: &mut App, frame: &mut Frame) {
+    let theme = app.theme;
+    let area = centered_rect(70, 80, frame.size());
+    let popup_block = Block::default()
+        .title("Keybindings")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.text))
+        .style(Style::default());
+
+    let sections = help_sections(&app.config.keymap, app.current_screen);
+    let key_width = sections
+        .iter()
+        .flat_map(|section| section.entries.iter())
+        .map(|(keys, _)| keys.chars().count())
+        .max()
+        .unwrap_or_default();
+    let mut lines = Vec::new();
+    for section in sections {
+        if !lines.is_empty() {
+            lines.push(Line::default());
+        }
+        lines.push(Line::from(Span::styled(
+            section.title,
+            Style::default().fg(theme.heading).bold(),
+        )));
+        lines.extend(section.entries.into_iter().map(|(keys, description)| {
+            Line::from(vec![
+                Span::styled(
+                    format!("  {:<width$}  ", keys, width = key_width),
+                    Style::default().fg(theme.accent),
+                ),
+                Span::raw(description),
+            ])
+        }));
+    }
+
+    // leave room for the borders
+    app.help.max_scroll = lines
+        .len()
+        .saturating_sub(area.height.saturating_sub(2) as usize);
+    app.help.scroll = app.help.scroll.min(app.help.max_scroll);
+    let help_paragraph = Paragraph::new(lines)
+        .block(popup_block)
+        .scroll((app.help.scroll as u16, 0));
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(help_paragraph, area);
+}
+
+fn render_login_popup(app: &mut App, frame: &mut Frame) {
+    let theme = app.theme;
+    frame.render_widget(Clear, frame.size()); //this clears the entire screen and anything already drawn
+    let popup_block = Block::default()
+        .title("GitHub Login")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.text))
+        .style(Style::default());
+
+    let lines = match app.login.as_ref().and_then(|login| login.code.as_ref()) {
+        Some(code) => vec![
+            Line::from(Span::styled(
+                format!("Enter this code at {}:", code.verification_uri),
+                Style::default().fg(theme.text),
+            )),
+            Line::from(""),
+            Line::from(Span::styled(
+                code.user_code.clone(),
+                Style::default().fg(theme.accent).bold(),
+            )),
+            Line::from(""),
+            Line::from(Span::styled(
+                "Waiting for the login to be authorized...",
+                Style::default().fg(theme.text),
+            )),
+        ],
+        None => vec![Line::from(Span::styled(
+            "Requesting a login code from GitHub...",
+            Style::default().fg(theme.text),
+        ))],
+    };
+    let login_paragraph = Paragraph::new(lines)
+        .block(popup_block)
+        .wrap(Wrap { trim: false });
+
+    let area = centered_rect(60, 25, frame.size());
+    frame.render_widget(login_paragraph, area);
+}
+
 fn render_updating_popup(app: &mut App, frame: &mut Frame) {
+    let theme = app.theme;
     frame.render_widget(Clear, frame.size()); //this clears the entire screen and anything already drawn
 
+    let popup_block = Block::default()
+        .title("Updating")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.text));
+    let area = centered_rect(60, 50, frame.size());
+    let popup_chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Min(1),
+        ])
+        .split(popup_block.inner(area));
+    frame.render_widget(popup_block, area);
+
+    let progress = &app.fetch_progress;
+    let label = match progress.summary() {
+        Some(summary) => format!("Fetching GitHub Repositories... {}", summary),
+        None => "Fetching GitHub Repositories...".to_string(),
+    };
     let spinner = throbber_widgets_tui::Throbber::default()
-        .label("Fetching GitHub Repositories...")
-        .style(ratatui::style::Style::default().fg(ratatui::style::Color::Cyan))
+        .label(label)
+        .style(ratatui::style::Style::default().fg(theme.progress))
         .throbber_style(
             ratatui::style::Style::default()
-                .fg(ratatui::style::Color::Red)
+                .fg(theme.error)
                 .add_modifier(ratatui::style::Modifier::BOLD),
         )
         .throbber_set(throbber_widgets_tui::BRAILLE_SIX);
 
+    let gauge = Gauge::default()
+        .gauge_style(Style::default().fg(theme.accent))
+        .ratio(progress.ratio())
+        .label(format!("{:.0}%", progress.ratio() * 100.0));
+
+    // only the most recent repositories that fit are shown, so the log scrolls as they finish
+    let log_height = popup_chunks[3].height as usize;
+    let log_lines: Vec<Line> = progress
+        .log
+        .iter()
+        .skip(progress.log.len().saturating_sub(log_height))
+        .map(|(name, failed)| {
+            if *failed {
+                Line::from(Span::styled(
+                    format!("✗ {}", name),
+                    Style::default().fg(theme.error),
+                ))
+            } else {
+                Line::from(Span::styled(
+                    format!("✓ {}", name),
+                    Style::default().fg(theme.accent),
+                ))
+            }
+        })
+        .collect();
+
+    frame.render_stateful_widget(spinner, popup_chunks[0], &mut app.spinner_state);
+    frame.render_widget(gauge, popup_chunks[1]);
+    frame.render_widget(Paragraph::new(log_lines), popup_chunks[3]);
+}
+
+fn render_credentials_popup(app: &mut App, frame: &mut Frame) {
+    let theme = app.theme;
+    frame.render_widget(Clear, frame.size()); //this clears the entire screen and anything already drawn
+    let popup_block = Block::default()
+        .title("GitHub Credentials")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.text))
+        .style(Style::default());
+
+    let input = &app.credentials_input;
+    let field_style = |field: CredentialField| {
+        if input.focus == field {
+            Style::default().fg(theme.accent).underlined()
+        } else {
+            Style::default().fg(theme.text)
+        }
+    };
+
+    let config_hint = match &app.config_path {
+        Some(config_path) => format!(
+            "Set PAT and GH_USERNAME, or add them to the [auth] section of {}, to skip this next time.",
+            config_path.display()
+        ),
+        None => "Set PAT and GH_USERNAME to skip this next time.".to_string(),
+    };
+    let lines = vec![
+        Line::from(Span::styled(
+            "No GitHub credentials are configured. Enter them to talk to GitHub.",
+            Style::default().fg(theme.error),
+        )),
+        Line::from(Span::styled(config_hint, Style::default().fg(theme.text))),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled("Username: ", Style::default().fg(theme.text)),
+            Span::styled(
+                input.username.clone(),
+                field_style(CredentialField::Username),
+            ),
+        ]),
+        Line::from(vec![
+            Span::styled("Token: ", Style::default().fg(theme.text)),
+            Span::styled(input.masked_token(), field_style(CredentialField::Token)),
+        ]),
+        Line::from(vec![
+            Span::styled("Save to keyring: ", Style::default().fg(theme.text)),
+            Span::styled(
+                if input.persist { "[x]" } else { "[ ]" },
+                field_style(CredentialField::Persist),
+            ),
+        ]),
+    ];
+
+    let credentials_paragraph = Paragraph::new(lines)
+        .block(popup_block)
+        .wrap(Wrap { trim: false });
+
     let area = centered_rect(60, 25, frame.size());
-    frame.render_stateful_widget(spinner, area, &mut app.spinner_state);
+    frame.render_widget(credentials_paragraph, area);
 }
 
-fn get_dependabot_bar_chart(
-    title: &str,
+fn render_setup_popup(app: &mut App, frame: &mut Frame) {
+    let theme = app.theme;
+    frame.render_widget(Clear, frame.size()); //this clears the entire screen and anything already drawn
+    let setup = &app.setup;
+    let popup_block = Block::default()
+        .title(format!(
+            "Setup ({}/{}): {}",
+            setup.step.number(),
+            SetupStep::ALL.len(),
+            setup.step.title()
+        ))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.text))
+        .style(Style::default());
+
+    let (question, answer) = match setup.step {
+        SetupStep::Username => (
+            "Which GitHub user's repositories should be tracked?".to_string(),
+            setup.username.clone(),
+        ),
+        SetupStep::Token => (
+            "Paste a personal access token for them, with access to Dependabot alerts:".to_string(),
+            setup.masked_token(),
+        ),
+        SetupStep::Organizations => (
+            "Which organizations' repositories should be tracked too? Separate them with commas, or leave this empty:"
+                .to_string(),
+            setup.organizations.clone(),
+        ),
+        SetupStep::DataDir => (
+            "Where should the fetched data be kept?".to_string(),
+            setup.data_dir.clone(),
+        ),
+    };
+    let mut lines = vec![
+        Line::from(Span::styled(
+            "Welcome! Answer a few questions to start tracking your Dependabot alerts.",
+            Style::default().fg(theme.accent),
+        )),
+        Line::from(""),
+        Line::from(Span::styled(question, Style::default().fg(theme.text))),
+        Line::from(Span::styled(
+            answer,
+            Style::default().fg(theme.accent).underlined(),
+        )),
+    ];
+    if setup.step == SetupStep::Token && app.config.auth.client_id.is_some() {
+        lines.push(Line::from(Span::styled(
+            "Or press ctrl+l to log in with GitHub instead.",
+            Style::default().fg(theme.text),
+        )));
+    }
+    if setup.is_last_step() {
+        let saved_to = match &app.config_path {
+            Some(config_path) => format!(
+                "The answers are saved to {}, and the token to the OS keyring.",
+                config_path.display()
+            ),
+            None => "The token is saved to the OS keyring.".to_string(),
+        };
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            saved_to,
+            Style::default().fg(theme.text),
+        )));
+    }
+
+    let setup_paragraph = Paragraph::new(lines)
+        .block(popup_block)
+        .wrap(Wrap { trim: false });
+
+    let area = centered_rect(60, 25, frame.size());
+    frame.render_widget(setup_paragraph, area);
+}
+
+fn render_dismiss_popup(app: &mut App, frame: &mut Frame) {
+    let theme = app.theme;
+    let input = &app.dismissal_input;
+    let popup_block = Block::default()
+        .title(format!("Dismiss Alert #{}", input.alert_number))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.text))
+        .style(Style::default());
+
+    let mut lines = vec![Line::from(Span::styled(
+        "Reason:",
+        Style::default().fg(theme.text),
+    ))];
+    for (index, reason) in DismissReason::ALL.iter().enumerate() {
+        let selected = index == input.reason_index;
+        let style = if selected && input.focus == DismissalField::Reason {
+            Style::default().fg(theme.accent).underlined()
+        } else if selected {
+            Style::default().fg(theme.accent)
+        } else {
+            Style::default().fg(theme.text)
+        };
+        lines.push(Line::from(Span::styled(
+            format!("{} {}", if selected { ">>" } else { "  " }, reason.label()),
+            style,
+        )));
+    }
+
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        "Comment (optional):",
+        Style::default().fg(theme.text),
+    )));
+    let comment_style = if input.focus == DismissalField::Comment {
+        Style::default().fg(theme.accent)
+    } else {
+        Style::default().fg(theme.text)
+    };
+    if input.focus == DismissalField::Comment {
+        // draw the cursor as a reversed cell in the comment text
+        let (before, after) = input.comment.split_at(
+            input
+                .comment
+                .char_indices()
+                .nth(input.cursor)
+                .map(|(index, _)| index)
+                .unwrap_or(input.comment.len()),
+        );
+        let mut after_chars = after.chars();
+        let cursor_char = after_chars.next().unwrap_or(' ');
+        lines.push(Line::from(vec![
+            Span::styled(before.to_string(), comment_style),
+            Span::styled(cursor_char.to_string(), comment_style.reversed()),
+            Span::styled(after_chars.as_str().to_string(), comment_style),
+        ]));
+    } else {
+        lines.push(Line::from(Span::styled(
+            input.comment.clone(),
+            comment_style,
+        )));
+    }
+
+    let dismiss_paragraph = Paragraph::new(lines)
+        .block(popup_block)
+        .wrap(Wrap { trim: false });
+
+    let area = centered_rect(60, 50, frame.size());
+    frame.render_widget(Clear, area);
+    frame.render_widget(dismiss_paragraph, area);
+}
+
+fn get_dependabot_bar_chart<'a>(
+    theme: &Theme,
+    title: &'a str,
     low_alerts_count: u64,
     medium_alerts_count: u64,
     high_alerts_count: u64,
     critical_alerts_count: u64,
-) -> BarChart {
+) -> BarChart<'a> {
     let barchart = BarChart::default()
         .data(
             BarGroup::default().bars(&[
                 Bar::default()
                     .label("Low Alerts".into())
                     .value(low_alerts_count)
-                    .style(Style::default().fg(Color::Blue)),
+                    .style(Style::default().fg(theme.severity_color(DependabotSeverity::Low))),
                 Bar::default()
                     .label("Medium Alerts".into())
                     .value(medium_alerts_count)
-                    .style(Style::default().fg(Color::Green)),
+                    .style(Style::default().fg(theme.severity_color(DependabotSeverity::Medium))),
                 Bar::default()
                     .label("High Alerts".into())
                     .value(high_alerts_count)
-                    .style(Style::default().fg(Color::Rgb(255, 165, 0))),
+                    .style(Style::default().fg(theme.severity_color(DependabotSeverity::High))),
                 Bar::default()
                     .label("Critical Alerts".into())
                     .value(critical_alerts_count)
-                    .style(Style::default().fg(Color::Red)),
+                    .style(Style::default().fg(theme.severity_color(DependabotSeverity::Critical))),
             ]),
         )
         .bar_width(3)
@@ -371,6 +2212,219 @@ fn get_dependabot_bar_chart(
     barchart
 }
 
+/// The open alerts per package ecosystem, so the stack carrying the most risk stands out.
+fn get_ecosystem_bar_chart<'a>(ecosystems: &'a [(String, u64)], theme: &Theme) -> BarChart<'a> {
+    let bars: Vec<Bar> = ecosystems
+        .iter()
+        .map(|(ecosystem, count)| {
+            Bar::default()
+                .label(ecosystem.as_str().into())
+                .value(*count)
+                .style(Style::default().fg(theme.progress))
+        })
+        .collect();
+
+    BarChart::default()
+        .data(BarGroup::default().bars(&bars))
+        .bar_width(1)
+        .bar_gap(1)
+        .block(
+            Block::default()
+                .title("Open Alerts by Ecosystem")
+                .borders(Borders::LEFT)
+                .padding(Padding::new(1, 0, 1, 0)),
+        )
+        .direction(Direction::Horizontal)
+}
+
+/// The repositories with the highest risk scores, so one with a pile of critical alerts stands
+/// out from one with as many low alerts.
+fn render_riskiest_repositories(
+    frame: &mut Frame,
+    area: Rect,
+    repositories: &RepositoryList,
+    risk: &RiskConfig,
+    theme: &Theme,
+) {
+    let block = Block::default()
+        .title(format!(
+            "Top {} Riskiest Repositories",
+            RISKIEST_REPOSITORY_COUNT
+        ))
+        .borders(Borders::LEFT | Borders::TOP)
+        .padding(Padding::horizontal(1));
+    let riskiest = risk.riskiest(repositories.owned(), RISKIEST_REPOSITORY_COUNT);
+    if riskiest.is_empty() {
+        let paragraph = Paragraph::new("No open alerts")
+            .style(Style::default().fg(theme.text))
+            .block(block);
+        frame.render_widget(paragraph, area);
+        return;
+    }
+
+    let rows = riskiest.iter().map(|(repo, score)| {
+        let critical_style = if repo.critical_alerts > 0 {
+            Style::default().fg(theme.critical).bold()
+        } else {
+            Style::default()
+        };
+        Row::new(vec![
+            Cell::from(repo.name.clone()),
+            Cell::from(format_risk_score(*score)),
+            Cell::from(repo.critical_alerts.to_string()).style(critical_style),
+            Cell::from(repo.high_alerts.to_string()),
+            Cell::from(repo.total_active_alerts.to_string()),
+        ])
+        .style(Style::default().fg(theme.text))
+    });
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Min(15),
+            Constraint::Length(6),
+            Constraint::Length(8),
+            Constraint::Length(5),
+            Constraint::Length(5),
+        ],
+    )
+    .header(
+        Row::new(vec!["Repository", "Risk", "Critical", "High", "Total"])
+            .style(Style::default().fg(theme.accent).bold()),
+    )
+    .block(block);
+    frame.render_widget(table, area);
+}
+
+/// Draw the open alerts per severity over the latest fetches, to show whether they're going down.
+fn render_alert_trend(app: &App, frame: &mut Frame, area: Rect) {
+    let theme = app.theme;
+    let trend = &app.alert_trend;
+    let title = format!("Open Alerts over the Last {} Fetches", trend.len());
+    if trend.len() < 2 {
+        let paragraph = Paragraph::new("Update the repositories a few times to see a trend")
+            .style(Style::default().fg(theme.text))
+            .block(Block::default().title(title).padding(Padding::vertical(1)));
+        frame.render_widget(paragraph, area);
+        return;
+    }
+
+    let series = |alerts: fn(&AlertTrendPoint) -> u64| -> Vec<(f64, f64)> {
+        trend
+            .iter()
+            .enumerate()
+            .map(|(index, point)| (index as f64, alerts(point) as f64))
+            .collect()
+    };
+    let low_alerts = series(|point| point.low_alerts);
+    let medium_alerts = series(|point| point.medium_alerts);
+    let high_alerts = series(|point| point.high_alerts);
+    let critical_alerts = series(|point| point.critical_alerts);
+    let max_alerts = trend
+        .iter()
+        .flat_map(|point| {
+            [
+                point.low_alerts,
+                point.medium_alerts,
+                point.high_alerts,
+                point.critical_alerts,
+            ]
+        })
+        .max()
+        .unwrap_or(0)
+        .max(1);
+
+    let datasets = [
+        ("Low", DependabotSeverity::Low, &low_alerts),
+        ("Medium", DependabotSeverity::Medium, &medium_alerts),
+        ("High", DependabotSeverity::High, &high_alerts),
+        ("Critical", DependabotSeverity::Critical, &critical_alerts),
+    ]
+    .into_iter()
+    .map(|(name, severity, data)| {
+        Dataset::default()
+            .name(name)
+            .marker(Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(Style::default().fg(app.theme.severity_color(severity)))
+            .data(data)
+    })
+    .collect();
+
+    let date_label = |point: &AlertTrendPoint| {
+        Span::raw(app.config.timezone.format(point.fetched_at, "%Y-%m-%d"))
+    };
+    let chart = Chart::new(datasets)
+        .block(Block::default().title(title).padding(Padding::vertical(1)))
+        .x_axis(
+            Axis::default()
+                .bounds([0.0, (trend.len() - 1) as f64])
+                .labels(vec![
+                    date_label(&trend[0]),
+                    date_label(&trend[trend.len() - 1]),
+                ]),
+        )
+        .y_axis(
+            Axis::default()
+                .bounds([0.0, max_alerts as f64])
+                .labels(vec![Span::raw("0"), Span::raw(max_alerts.to_string())]),
+        );
+    frame.render_widget(chart, area);
+}
+
+/// Draw each severity's share of the alerts as a percentage gauge, so accounts of different sizes
+/// can be compared.
+#[allow(clippy::too_many_arguments)]
+fn render_severity_distribution(
+    frame: &mut Frame,
+    area: Rect,
+    theme: &Theme,
+    title: &str,
+    low_alerts_count: u64,
+    medium_alerts_count: u64,
+    high_alerts_count: u64,
+    critical_alerts_count: u64,
+) {
+    let block = Block::default()
+        .title(format!("{} (% of alerts)", title))
+        .padding(Padding::vertical(1));
+    let gauge_chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(1); 4])
+        .spacing(1)
+        .split(block.inner(area));
+    frame.render_widget(block, area);
+
+    let total_alerts_count =
+        low_alerts_count + medium_alerts_count + high_alerts_count + critical_alerts_count;
+    let severities = [
+        ("Low Alerts", low_alerts_count, DependabotSeverity::Low),
+        (
+            "Medium Alerts",
+            medium_alerts_count,
+            DependabotSeverity::Medium,
+        ),
+        ("High Alerts", high_alerts_count, DependabotSeverity::High),
+        (
+            "Critical Alerts",
+            critical_alerts_count,
+            DependabotSeverity::Critical,
+        ),
+    ];
+
+    for ((label, count, severity), chunk) in severities.into_iter().zip(gauge_chunks.iter()) {
+        let ratio = if total_alerts_count == 0 {
+            0.0
+        } else {
+            count as f64 / total_alerts_count as f64
+        };
+        let gauge = Gauge::default()
+            .gauge_style(Style::default().fg(theme.severity_color(severity)))
+            .ratio(ratio)
+            .label(format!("{}: {:.1}% ({})", label, ratio * 100.0, count));
+        frame.render_widget(gauge, *chunk);
+    }
+}
+
 /// helper function to create a centered rect using up certain percentage of the available rect `r`
 fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
     // Cut the given rectangle into three vertical pieces
@@ -0,0 +1,69 @@
+use serde::Deserialize;
+
+use crate::dependabot::{Dependabot, DependabotSeverity};
+use crate::repository::Repository;
+use crate::trace_dbg;
+
+// how many of the new alerts the notification lists before summarizing the rest
+const LISTED_ALERT_COUNT: usize = 5;
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct DesktopNotificationConfig {
+    // whether to skip desktop notifications entirely
+    pub mute: bool,
+    // the least severe newly opened alert that triggers a notification
+    pub min_severity: DependabotSeverity,
+}
+
+impl Default for DesktopNotificationConfig {
+    fn default() -> Self {
+        DesktopNotificationConfig {
+            mute: false,
+            min_severity: DependabotSeverity::High,
+        }
+    }
+}
+
+/// Show a desktop notification listing the alerts that opened since the last fetch, if any of
+/// them are at least `min_severity`. Failing to show it is only logged, since some terminals
+/// run without a notification server.
+pub fn notify_new_alerts(
+    config: &DesktopNotificationConfig,
+    new_alerts: &[(&Repository, &Dependabot)],
+) {
+    if config.mute {
+        return;
+    }
+    let alerts: Vec<_> = new_alerts
+        .iter()
+        .filter(|(_, dependabot)| dependabot.severity >= config.min_severity)
+        .collect();
+    if alerts.is_empty() {
+        return;
+    }
+
+    let mut body: Vec<String> = alerts
+        .iter()
+        .take(LISTED_ALERT_COUNT)
+        .map(|(repo, dependabot)| {
+            format!(
+                "{}: {} ({})",
+                repo.name, dependabot.dependency_name, dependabot.severity
+            )
+        })
+        .collect();
+    if alerts.len() > LISTED_ALERT_COUNT {
+        body.push(format!("and {} more", alerts.len() - LISTED_ALERT_COUNT));
+    }
+
+    if let Err(e) = notify_rust::Notification::new()
+        .appname(env!("CARGO_PKG_NAME"))
+        .summary(&format!("{} new Dependabot alert(s)", alerts.len()))
+        .body(&body.join("\n"))
+        .show()
+    {
+        let notification_error = format!("Failed to show desktop notification: {}", e);
+        trace_dbg!(level: tracing::Level::WARN, notification_error);
+    }
+}
@@ -0,0 +1,151 @@
+use std::time::Duration;
+
+use reqwest::header::{ACCEPT, USER_AGENT};
+use reqwest::Client;
+use serde::Deserialize;
+
+use crate::error::{check_response, DependabotTrackerError};
+use crate::repository::github_headers;
+
+const DEVICE_CODE_URL: &str = "https://github.com/login/device/code";
+const ACCESS_TOKEN_URL: &str = "https://github.com/login/oauth/access_token";
+const DEVICE_GRANT_TYPE: &str = "urn:ietf:params:oauth:grant-type:device_code";
+// the scopes an OAuth App needs to read (and dismiss) alerts; GitHub Apps ignore them and use
+// the app's permissions instead
+const OAUTH_SCOPES: &str = "repo security_events read:org";
+// how much longer to wait between polls each time GitHub says to slow down
+const SLOW_DOWN_SECONDS: u64 = 5;
+
+/// The code the user enters at `verification_uri` to authorize the app.
+#[derive(Debug, Clone, Deserialize)]
+pub struct DeviceCode {
+    device_code: String,
+    pub user_code: String,
+    pub verification_uri: String,
+    // how many seconds the codes are valid for
+    expires_in: u64,
+    // the minimum number of seconds to wait between polls
+    interval: u64,
+}
+
+#[derive(Deserialize)]
+struct AccessTokenResponse {
+    access_token: Option<String>,
+    error: Option<String>,
+    error_description: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct GitHubUser {
+    login: String,
+}
+
+fn device_flow_error(message: String) -> DependabotTrackerError {
+    DependabotTrackerError::Login(message)
+}
+
+/// Start the device flow for the OAuth or GitHub App with `client_id`.
+pub async fn request_device_code(
+    client: &Client,
+    client_id: &str,
+) -> Result<DeviceCode, DependabotTrackerError> {
+    let response = client
+        .post(DEVICE_CODE_URL)
+        .header(ACCEPT, "application/json")
+        .header(USER_AGENT, "reqwest")
+        .form(&[("client_id", client_id), ("scope", OAUTH_SCOPES)])
+        .send()
+        .await?;
+
+    if !response.status().is_success() {
+        return Err(device_flow_error(format!(
+            "GitHub rejected the device code request ({}), check that device flow is enabled for the app",
+            response.status()
+        )));
+    }
+
+    response
+        .json::<DeviceCode>()
+        .await
+        .map_err(DependabotTrackerError::from)
+}
+
+/// Poll GitHub at the interval it asked for until the user authorizes the device code, returning
+/// the access token.
+pub async fn poll_for_token(
+    client: &Client,
+    client_id: &str,
+    device_code: &DeviceCode,
+) -> Result<String, DependabotTrackerError> {
+    let mut interval = Duration::from_secs(device_code.interval);
+    let deadline = tokio::time::Instant::now() + Duration::from_secs(device_code.expires_in);
+
+    loop {
+        tokio::time::sleep(interval).await;
+        if tokio::time::Instant::now() >= deadline {
+            return Err(device_flow_error(
+                "The login code expired before it was entered".to_string(),
+            ));
+        }
+
+        let response = client
+            .post(ACCESS_TOKEN_URL)
+            .header(ACCEPT, "application/json")
+            .header(USER_AGENT, "reqwest")
+            .form(&[
+                ("client_id", client_id),
+                ("device_code", device_code.device_code.as_str()),
+                ("grant_type", DEVICE_GRANT_TYPE),
+            ])
+            .send()
+            .await?
+            .json::<AccessTokenResponse>()
+            .await?;
+
+        if let Some(access_token) = response.access_token {
+            return Ok(access_token);
+        }
+        match response.error.as_deref() {
+            Some("authorization_pending") => {}
+            Some("slow_down") => interval += Duration::from_secs(SLOW_DOWN_SECONDS),
+            Some("expired_token") => {
+                return Err(device_flow_error(
+                    "The login code expired before it was entered".to_string(),
+                ));
+            }
+            Some("access_denied") => {
+                return Err(device_flow_error(
+                    "The login was cancelled on GitHub".to_string(),
+                ));
+            }
+            error => {
+                return Err(device_flow_error(format!(
+                    "GitHub login failed: {}",
+                    response
+                        .error_description
+                        .as_deref()
+                        .or(error)
+                        .unwrap_or("no token was returned")
+                )));
+            }
+        }
+    }
+}
+
+/// The login of the user a token belongs to.
+pub async fn fetch_username(
+    client: &Client,
+    token: &str,
+) -> Result<String, DependabotTrackerError> {
+    let user = client
+        .get("https://api.github.com/user")
+        .headers(github_headers(token)?)
+        .send()
+        .await
+        .map_err(DependabotTrackerError::from)
+        .and_then(check_response)?
+        .json::<GitHubUser>()
+        .await?;
+
+    Ok(user.login)
+}
@@ -0,0 +1,259 @@
+use chrono::{DateTime, Utc};
+use lettre::message::MultiPart;
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{Message, SmtpTransport, Transport};
+use serde::Deserialize;
+
+use crate::config::Config;
+use crate::dependabot::DependabotSeverity;
+use crate::error::DependabotTrackerError;
+use crate::report::escape_html;
+use crate::repository::Repository;
+use crate::repository_list::RepositoryList;
+use crate::risk::RiskConfig;
+use crate::templates::{render_template, TemplateContext, Templates};
+
+// the port for SMTP submission with STARTTLS
+const DEFAULT_SMTP_PORT: u16 = 587;
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct DigestConfig {
+    pub smtp_host: Option<String>,
+    pub smtp_port: Option<u16>,
+    pub smtp_username: Option<String>,
+    // `SMTP_PASSWORD` takes precedence over this
+    pub smtp_password: Option<String>,
+    // the sender, e.g. `Dependabot Tracker <security@example.com>`
+    pub from: Option<String>,
+    pub recipients: Vec<String>,
+}
+
+/// One repository's line in the digest.
+struct DigestRow {
+    full_name: String,
+    critical_alerts: usize,
+    high_alerts: usize,
+    medium_alerts: usize,
+    low_alerts: usize,
+    total_active_alerts: usize,
+    // the change in open alerts since the baseline snapshot, if the repository was in it
+    change: Option<i64>,
+}
+
+/// An email summarizing each repository's open alerts and how they changed since a snapshot
+/// from a week earlier.
+pub struct Digest {
+    generated_at: DateTime<Utc>,
+    rows: Vec<DigestRow>,
+    // rendered from the `digest_subject` and `digest_summary` templates
+    subject: String,
+    headline: String,
+}
+
+impl Digest {
+    pub fn new(
+        repositories: &RepositoryList,
+        baseline: &[Repository],
+        now: DateTime<Utc>,
+        templates: &Templates,
+        risk: &RiskConfig,
+    ) -> Self {
+        let mut rows: Vec<DigestRow> = repositories
+            .owned()
+            .map(|repo| {
+                let change =
+                    baseline
+                        .iter()
+                        .find(|previous| previous.id == repo.id)
+                        .map(|previous| {
+                            repo.total_active_alerts as i64 - previous.total_active_alerts as i64
+                        });
+                DigestRow {
+                    full_name: repo.full_name.clone(),
+                    critical_alerts: repo.critical_alerts,
+                    high_alerts: repo.high_alerts,
+                    medium_alerts: repo.medium_alerts,
+                    low_alerts: repo.low_alerts,
+                    total_active_alerts: repo.total_active_alerts,
+                    change,
+                }
+            })
+            .filter(|row| {
+                row.total_active_alerts > 0 || row.change.is_some_and(|change| change != 0)
+            })
+            .collect();
+        rows.sort_by_key(|row| {
+            std::cmp::Reverse((
+                row.critical_alerts,
+                row.high_alerts,
+                row.total_active_alerts,
+            ))
+        });
+
+        let total_open_alerts = repositories
+            .owned()
+            .map(|repo| repo.total_active_alerts)
+            .sum::<usize>();
+        let baseline_open_alerts = baseline
+            .iter()
+            .filter(|repo| !repo.watched)
+            .map(|repo| repo.total_active_alerts)
+            .sum::<usize>();
+        let has_baseline = !baseline.is_empty();
+        let owned_count =
+            |alerts: Vec<(&Repository, _)>| alerts.iter().filter(|(repo, _)| !repo.watched).count();
+
+        let total_change =
+            has_baseline.then(|| total_open_alerts as i64 - baseline_open_alerts as i64);
+        // without a baseline every open alert would count as new
+        let (new_alerts, new_critical_alerts) = if has_baseline {
+            (
+                owned_count(repositories.new_open_alerts(baseline)),
+                repositories.count_new_open_alerts(baseline, DependabotSeverity::Critical),
+            )
+        } else {
+            (0, 0)
+        };
+        let resolved_alerts = owned_count(repositories.resolved_alerts(baseline));
+        let weekly_change = match total_change {
+            Some(_) => format!(
+                "{} since last week: {} new, {} resolved",
+                format_change(total_change),
+                new_alerts,
+                resolved_alerts
+            ),
+            None => "with no snapshot from a week ago to compare against yet".to_string(),
+        };
+
+        let context = TemplateContext::from_repositories(repositories, new_critical_alerts, risk)
+            .with("date", now.format("%Y-%m-%d").to_string())
+            .with("total_change", format_change(total_change))
+            .with("new_count", new_alerts.to_string())
+            .with("resolved_count", resolved_alerts.to_string())
+            .with("weekly_change", weekly_change);
+        Digest {
+            generated_at: now,
+            rows,
+            subject: render_template(&templates.digest_subject, &context),
+            headline: render_template(&templates.digest_summary, &context),
+        }
+    }
+
+    pub fn subject(&self) -> &str {
+        &self.subject
+    }
+
+    fn cells(row: &DigestRow) -> [String; 7] {
+        [
+            row.full_name.clone(),
+            row.critical_alerts.to_string(),
+            row.high_alerts.to_string(),
+            row.medium_alerts.to_string(),
+            row.low_alerts.to_string(),
+            row.total_active_alerts.to_string(),
+            format_change(row.change),
+        ]
+    }
+
+    pub fn to_text(&self) -> String {
+        let mut text = format!(
+            "Dependabot digest for {}\n\n{}\n\n",
+            self.generated_at.format("%Y-%m-%d"),
+            self.headline
+        );
+        text.push_str(&format!(
+            "{:<40} {:>8} {:>6} {:>6} {:>6} {:>6} {:>8}\n",
+            "Repository", "Critical", "High", "Medium", "Low", "Total", "Change"
+        ));
+        for row in self.rows.iter() {
+            let [name, critical, high, medium, low, total, change] = Digest::cells(row);
+            text.push_str(&format!(
+                "{:<40} {:>8} {:>6} {:>6} {:>6} {:>6} {:>8}\n",
+                name, critical, high, medium, low, total, change
+            ));
+        }
+
+        text
+    }
+
+    pub fn to_html(&self) -> String {
+        let mut html = format!(
+            "<h1>Dependabot digest for {}</h1>\n<p>{}</p>\n<table>\n<tr><th>Repository</th><th>Critical</th><th>High</th><th>Medium</th><th>Low</th><th>Total</th><th>Change</th></tr>\n",
+            self.generated_at.format("%Y-%m-%d"),
+            escape_html(&self.headline)
+        );
+        for row in self.rows.iter() {
+            html.push_str("<tr>");
+            for cell in Digest::cells(row).iter() {
+                html.push_str(&format!("<td>{}</td>", escape_html(cell)));
+            }
+            html.push_str("</tr>\n");
+        }
+        html.push_str("</table>\n");
+
+        html
+    }
+}
+
+/// A change in alert count with its sign, e.g. `+3`, `-1`, or `n/a` without a baseline.
+fn format_change(change: Option<i64>) -> String {
+    match change {
+        Some(change) if change > 0 => format!("+{}", change),
+        Some(change) => change.to_string(),
+        None => "n/a".to_string(),
+    }
+}
+
+/// Email the digest to the configured recipients over SMTP with STARTTLS.
+pub fn send_digest(config: &Config, digest: &Digest) -> Result<(), DependabotTrackerError> {
+    let digest_config = &config.digest;
+    let missing = |setting: &str| {
+        DependabotTrackerError::Config(format!(
+            "{} must be set in the config's [digest] section to send a digest",
+            setting
+        ))
+    };
+    let invalid_address = |address: &str, e: lettre::address::AddressError| {
+        DependabotTrackerError::Config(format!(
+            "'{}' in the config's [digest] section isn't an email address: {}",
+            address, e
+        ))
+    };
+    let send_failed = |e: lettre::transport::smtp::Error| {
+        DependabotTrackerError::Other(format!("Failed to send the digest: {}", e))
+    };
+    let host = digest_config
+        .smtp_host
+        .as_ref()
+        .ok_or_else(|| missing("smtp_host"))?;
+    let from = digest_config.from.as_ref().ok_or_else(|| missing("from"))?;
+    if digest_config.recipients.is_empty() {
+        return Err(missing("recipients"));
+    }
+
+    let mut message = Message::builder().from(from.parse().map_err(|e| invalid_address(from, e))?);
+    for recipient in digest_config.recipients.iter() {
+        message = message.to(recipient
+            .parse()
+            .map_err(|e| invalid_address(recipient, e))?);
+    }
+    let message = message
+        .subject(digest.subject())
+        .multipart(MultiPart::alternative_plain_html(
+            digest.to_text(),
+            digest.to_html(),
+        ))
+        .map_err(|e| DependabotTrackerError::Other(format!("Failed to build the digest: {}", e)))?;
+
+    let mut transport = SmtpTransport::starttls_relay(host)
+        .map_err(send_failed)?
+        .port(digest_config.smtp_port.unwrap_or(DEFAULT_SMTP_PORT));
+    if let Some(username) = digest_config.smtp_username.as_ref() {
+        transport =
+            transport.credentials(Credentials::new(username.clone(), config.smtp_password()));
+    }
+    transport.build().send(&message).map_err(send_failed)?;
+
+    Ok(())
+}
@@ -0,0 +1,96 @@
+use crate::dependabot::DismissReason;
+
+#[derive(Clone, Copy, Default, PartialEq)]
+pub enum DismissalField {
+    #[default]
+    Reason,
+    Comment,
+}
+
+/// The state of the dismissal modal: the alert being dismissed, the highlighted reason,
+/// and an optional comment with a cursor for basic text editing.
+#[derive(Default)]
+pub struct DismissalInput {
+    pub alert_number: u32,
+    pub reason_index: usize,
+    pub comment: String,
+    // the cursor position in the comment, counted in characters
+    pub cursor: usize,
+    pub focus: DismissalField,
+}
+
+impl DismissalInput {
+    pub fn new(alert_number: u32) -> Self {
+        DismissalInput {
+            alert_number,
+            ..DismissalInput::default()
+        }
+    }
+
+    pub fn reason(&self) -> DismissReason {
+        DismissReason::ALL[self.reason_index]
+    }
+
+    pub fn toggle_focus(&mut self) {
+        self.focus = match self.focus {
+            DismissalField::Reason => DismissalField::Comment,
+            DismissalField::Comment => DismissalField::Reason,
+        };
+    }
+
+    pub fn next_reason(&mut self) {
+        self.reason_index = (self.reason_index + 1) % DismissReason::ALL.len();
+    }
+
+    pub fn previous_reason(&mut self) {
+        self.reason_index = match self.reason_index {
+            0 => DismissReason::ALL.len() - 1,
+            index => index - 1,
+        };
+    }
+
+    fn byte_index(&self, cursor: usize) -> usize {
+        self.comment
+            .char_indices()
+            .nth(cursor)
+            .map(|(index, _)| index)
+            .unwrap_or(self.comment.len())
+    }
+
+    pub fn insert(&mut self, c: char) {
+        let index = self.byte_index(self.cursor);
+        self.comment.insert(index, c);
+        self.cursor += 1;
+    }
+
+    pub fn backspace(&mut self) {
+        if self.cursor > 0 {
+            self.cursor -= 1;
+            let index = self.byte_index(self.cursor);
+            self.comment.remove(index);
+        }
+    }
+
+    pub fn delete(&mut self) {
+        if self.cursor < self.comment.chars().count() {
+            let index = self.byte_index(self.cursor);
+            self.comment.remove(index);
+        }
+    }
+
+    pub fn move_left(&mut self) {
+        self.cursor = self.cursor.saturating_sub(1);
+    }
+
+    pub fn move_right(&mut self) {
+        self.cursor = (self.cursor + 1).min(self.comment.chars().count());
+    }
+
+    pub fn move_home(&mut self) {
+        self.cursor = 0;
+    }
+
+    pub fn move_end(&mut self) {
+        self.cursor = self.comment.chars().count();
+    }
+}
@@ -0,0 +1,67 @@
+use std::time::{Duration, Instant};
+
+use crossterm::event::{self, Event, KeyEvent, MouseEvent};
+
+use crate::app::App;
+use crate::error::DependabotTrackerError;
+use crate::repository::{FetchOutcome, FetchProgress};
+use crate::script::Script;
+
+// how often the UI redraws when nothing else is happening, which also paces spinners, the
+// refresh countdown, and polling for a login
+const TICK_RATE: Duration = Duration::from_millis(200);
+
+/// Everything the main loop reacts to.
+pub enum AppEvent {
+    Key(KeyEvent),
+    Mouse(MouseEvent),
+    Tick,
+    FetchProgress(FetchProgress),
+    FetchDone(Result<FetchOutcome, DependabotTrackerError>),
+}
+
+/// Where the main loop's events come from: a running fetch's messages first, then the script's
+/// keys, then the keyboard and mouse, with a `Tick` every `TICK_RATE`.
+pub struct EventSource {
+    script: Option<Script>,
+    last_tick: Instant,
+}
+
+impl EventSource {
+    pub fn new(script: Option<Script>) -> EventSource {
+        EventSource {
+            script,
+            last_tick: Instant::now(),
+        }
+    }
+
+    pub fn next(&mut self, app: &mut App) -> Result<AppEvent, DependabotTrackerError> {
+        loop {
+            if let Some(event) = app.next_fetch_event()? {
+                return Ok(event);
+            }
+
+            let timeout = TICK_RATE.saturating_sub(self.last_tick.elapsed());
+            if timeout.is_zero() {
+                self.last_tick = Instant::now();
+                return Ok(AppEvent::Tick);
+            }
+
+            // a script waits for the Updating popup to close, since keys are ignored until then
+            if !app.is_updating() {
+                if let Some(Event::Key(key)) = self.script.as_mut().and_then(Script::next_event) {
+                    return Ok(AppEvent::Key(key));
+                }
+            }
+
+            if event::poll(timeout)? {
+                let event = event::read()?;
+                match event {
+                    Event::Key(key) => return Ok(AppEvent::Key(key)),
+                    Event::Mouse(mouse) => return Ok(AppEvent::Mouse(mouse)),
+                    _ => {}
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,75 @@
+pub mod sarif;
+
+use std::io::{self, Write};
+
+use serde::Serialize;
+
+use crate::dependabot::Dependabot;
+use crate::error::DependabotTrackerError;
+use crate::repository::Repository;
+
+/// An alert along with the repository it belongs to, as written by `export`.
+#[derive(Serialize)]
+struct ExportedAlert<'a> {
+    repository: &'a str,
+    #[serde(flatten)]
+    alert: &'a Dependabot,
+}
+
+fn exported_alerts(repositories: &[Repository]) -> impl Iterator<Item = ExportedAlert<'_>> {
+    repositories.iter().flat_map(|repo| {
+        repo.dependabots.iter().map(|alert| ExportedAlert {
+            repository: &repo.full_name,
+            alert,
+        })
+    })
+}
+
+pub fn export_json(
+    writer: &mut impl Write,
+    repositories: &[Repository],
+) -> Result<(), DependabotTrackerError> {
+    let alerts: Vec<ExportedAlert> = exported_alerts(repositories).collect();
+    serde_json::to_writer_pretty(&mut *writer, &alerts)?;
+    writeln!(writer)?;
+
+    Ok(())
+}
+
+pub fn export_csv(writer: &mut impl Write, repositories: &[Repository]) -> io::Result<()> {
+    writeln!(
+        writer,
+        "repository,number,state,severity,dependency_ecosystem,dependency_name,vulnerable_version_range,first_patched_version,ghsa_id,license,created_at,html_url"
+    )?;
+    for exported in exported_alerts(repositories) {
+        let alert = exported.alert;
+        let fields = [
+            exported.repository.to_string(),
+            alert.number.to_string(),
+            alert.state.to_string(),
+            alert.severity.to_string(),
+            alert.dependency_ecosystem.clone(),
+            alert.dependency_name.clone(),
+            alert.vulnerable_version_range.clone().unwrap_or_default(),
+            alert.first_patched_version.clone().unwrap_or_default(),
+            alert.ghsa_id.clone().unwrap_or_default(),
+            alert.license.clone().unwrap_or_default(),
+            alert.created_at.clone(),
+            alert.html_url.clone(),
+        ];
+        let row: Vec<String> = fields.iter().map(|field| csv_field(field)).collect();
+        writeln!(writer, "{}", row.join(","))?;
+    }
+
+    Ok(())
+}
+
+/// Quote a CSV field if it contains a comma, quote, or newline, e.g. a version range like
+/// `>= 1.0, < 1.2`.
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
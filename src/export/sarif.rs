@@ -0,0 +1,146 @@
+use std::collections::BTreeMap;
+use std::io::Write;
+
+use serde_json::{json, Value};
+
+use crate::dependabot::{Dependabot, DependabotSeverity, DependabotState};
+use crate::error::DependabotTrackerError;
+use crate::repository::Repository;
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+const SARIF_VERSION: &str = "2.1.0";
+
+/// The SARIF level for an alert's severity. Alerts that are no longer open are `none`, since
+/// they don't need attention.
+fn level(dependabot: &Dependabot) -> &'static str {
+    if dependabot.state != DependabotState::Open {
+        return "none";
+    }
+
+    match dependabot.severity {
+        DependabotSeverity::Critical | DependabotSeverity::High => "error",
+        DependabotSeverity::Medium => "warning",
+        DependabotSeverity::Low => "note",
+    }
+}
+
+/// The rule an alert is reported under: its advisory, or the package itself when the alert
+/// has no advisory ID.
+fn rule_id(dependabot: &Dependabot) -> String {
+    dependabot.ghsa_id.clone().unwrap_or_else(|| {
+        format!(
+            "{}/{}",
+            dependabot.dependency_ecosystem, dependabot.dependency_name
+        )
+    })
+}
+
+fn rule(dependabot: &Dependabot) -> Value {
+    let summary = dependabot.summary.clone().unwrap_or_else(|| {
+        format!(
+            "Vulnerable {} package {}",
+            dependabot.dependency_ecosystem, dependabot.dependency_name
+        )
+    });
+    let mut rule = json!({
+        "id": rule_id(dependabot),
+        "shortDescription": { "text": summary },
+        "fullDescription": {
+            "text": dependabot.description.clone().unwrap_or_else(|| summary.clone())
+        },
+        "defaultConfiguration": { "level": level(dependabot) },
+        "properties": {
+            "tags": ["security", "dependabot", dependabot.dependency_ecosystem],
+            "severity": dependabot.severity.to_string().to_lowercase(),
+        },
+    });
+    if let Some(ghsa_id) = &dependabot.ghsa_id {
+        rule["helpUri"] = json!(format!("https://github.com/advisories/{}", ghsa_id));
+    }
+    // code scanning tools sort and filter on this score, so it's only included when there is one
+    if let Some(cvss_score) = dependabot.cvss_score {
+        rule["properties"]["security-severity"] = json!(format!("{:.1}", cvss_score));
+    }
+
+    rule
+}
+
+fn result(repository: &Repository, dependabot: &Dependabot) -> Value {
+    let mut message = format!(
+        "{} {} is vulnerable ({})",
+        dependabot.dependency_name,
+        dependabot
+            .vulnerable_version_range
+            .as_deref()
+            .unwrap_or("(unknown versions)"),
+        dependabot.severity.to_string().to_lowercase()
+    );
+    if let Some(upgrade_hint) = dependabot.upgrade_hint() {
+        message.push_str(&format!(", {}", upgrade_hint));
+    }
+
+    let mut result = json!({
+        "ruleId": rule_id(dependabot),
+        "level": level(dependabot),
+        "kind": if dependabot.state == DependabotState::Fixed { "pass" } else { "fail" },
+        "message": { "text": message },
+        "locations": [{
+            "physicalLocation": {
+                "artifactLocation": {
+                    "uri": dependabot.manifest_path.as_deref().unwrap_or(&repository.full_name)
+                }
+            }
+        }],
+        "hostedViewerUri": dependabot.html_url,
+        "properties": {
+            "repository": repository.full_name,
+            "alertNumber": dependabot.number,
+            "state": dependabot.state,
+        },
+    });
+    if matches!(
+        dependabot.state,
+        DependabotState::Dismissed | DependabotState::AutoDismissed
+    ) {
+        result["suppressions"] = json!([{ "kind": "external", "status": "accepted" }]);
+    }
+
+    result
+}
+
+/// Write every alert across `repositories` as a SARIF 2.1.0 log with a single run, one rule per
+/// advisory and one result per alert.
+pub fn export_sarif(
+    writer: &mut impl Write,
+    repositories: &[Repository],
+) -> Result<(), DependabotTrackerError> {
+    let mut rules = BTreeMap::new();
+    let mut results = Vec::new();
+    for repository in repositories {
+        for dependabot in repository.dependabots.iter() {
+            rules
+                .entry(rule_id(dependabot))
+                .or_insert_with(|| rule(dependabot));
+            results.push(result(repository, dependabot));
+        }
+    }
+
+    let log = json!({
+        "$schema": SARIF_SCHEMA,
+        "version": SARIF_VERSION,
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": env!("CARGO_PKG_NAME"),
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "rules": rules.into_values().collect::<Vec<Value>>(),
+                }
+            },
+            "results": results,
+        }],
+    });
+    serde_json::to_writer_pretty(&mut *writer, &log)?;
+    writeln!(writer)?;
+
+    Ok(())
+}
@@ -0,0 +1,405 @@
+use std::fs::File;
+use std::io::{self, Write};
+use std::process::{Command as ProcessCommand, ExitCode, Stdio};
+use std::thread;
+use std::time::Duration as StdDuration;
+
+use chrono::{Duration, Utc};
+use reqwest::Client;
+use tokio::runtime::Runtime;
+use tokio_util::sync::CancellationToken;
+
+use crate::app::record_alert_events;
+use crate::cli::{Command, ExportFormat, OutputMode, ReportFormat};
+use crate::config::Config;
+use crate::dependabot::DependabotSeverity;
+use crate::digest::{send_digest, Digest};
+use crate::error::DependabotTrackerError;
+use crate::export::sarif::export_sarif;
+use crate::export::{export_csv, export_json};
+use crate::json_output::{JsonDocument, JsonWatchChange};
+use crate::profile::Profile;
+use crate::provider::SecurityProvider;
+use crate::report::SecurityReport;
+use crate::repository::{AlertsStatus, FetchOutcome, FetchReport, Repository};
+use crate::repository_list::RepositoryList;
+use crate::storage::{load_latest_snapshot, load_snapshot_before, DIGEST_WINDOW_DAYS};
+use crate::templates::TemplateContext;
+use crate::webhook::{post_webhook_summary, WebhookSummary};
+
+/// Run a subcommand without starting the TUI, for use from cron or CI, returning the status to
+/// exit with. With `--output json`, `fetch`, `report`, `check`, and `export` print a
+/// `JsonDocument` in place of their text.
+pub fn run(
+    command: &Command,
+    output_mode: OutputMode,
+    config: Config,
+    profile: &Profile,
+) -> Result<ExitCode, DependabotTrackerError> {
+    let json = output_mode == OutputMode::Json;
+    match command {
+        Command::Fetch => {
+            let repositories = fetch(&config, profile)?.repositories.repos;
+            if json {
+                print_json(
+                    &mut io::stdout().lock(),
+                    &JsonDocument::new(&repositories, Utc::now()),
+                )?;
+                return Ok(ExitCode::SUCCESS);
+            }
+            println!("Fetched {} repositories", repositories.len());
+            Ok(ExitCode::SUCCESS)
+        }
+        Command::Report { format, out_file } => {
+            let repositories = load_repositories()?;
+            let mut writer: Box<dyn Write> = match out_file {
+                Some(path) => Box::new(File::create(path)?),
+                None => Box::new(io::stdout().lock()),
+            };
+            if json {
+                print_json(&mut writer, &JsonDocument::new(&repositories, Utc::now()))?;
+                return Ok(ExitCode::SUCCESS);
+            }
+            match format {
+                ReportFormat::Table => print_report(&mut writer, &repositories)?,
+                ReportFormat::Markdown | ReportFormat::Html => {
+                    let report = SecurityReport::new(
+                        &RepositoryList::with_respositories(repositories),
+                        Utc::now(),
+                    );
+                    match format {
+                        ReportFormat::Html => write!(writer, "{}", report.to_html())?,
+                        _ => write!(writer, "{}", report.to_markdown())?,
+                    }
+                }
+            }
+            writer.flush()?;
+            Ok(ExitCode::SUCCESS)
+        }
+        Command::Check {
+            max_critical,
+            max_high,
+        } => check(&config, profile, *max_critical, *max_high, json),
+        Command::Digest { dry_run } => {
+            // compare against the latest snapshot from before the last week's fetches
+            let baseline = load_snapshot_before(Utc::now() - Duration::days(DIGEST_WINDOW_DAYS))
+                .unwrap_or_default();
+            let repositories =
+                RepositoryList::with_respositories(fetch(&config, profile)?.repositories.repos);
+            let digest = Digest::new(
+                &repositories,
+                &baseline,
+                Utc::now(),
+                &config.templates,
+                &config.risk,
+            );
+            if *dry_run {
+                println!("Subject: {}\n\n{}", digest.subject(), digest.to_text());
+                return Ok(ExitCode::SUCCESS);
+            }
+
+            send_digest(&config, &digest)?;
+            println!("Sent the digest to {}", config.digest.recipients.join(", "));
+            Ok(ExitCode::SUCCESS)
+        }
+        Command::Export { format, out_file } => {
+            let repositories = load_repositories()?;
+            let mut writer: Box<dyn Write> = match out_file {
+                Some(path) => Box::new(File::create(path)?),
+                None => Box::new(io::stdout().lock()),
+            };
+            if json {
+                print_json(&mut writer, &JsonDocument::new(&repositories, Utc::now()))?;
+                return Ok(ExitCode::SUCCESS);
+            }
+            match format {
+                ExportFormat::Json => export_json(&mut writer, &repositories)?,
+                ExportFormat::Csv => export_csv(&mut writer, &repositories)?,
+                ExportFormat::Sarif => export_sarif(&mut writer, &repositories)?,
+            }
+            writer.flush()?;
+            Ok(ExitCode::SUCCESS)
+        }
+        Command::Watch {
+            interval,
+            on_change,
+            exit_on_change,
+        } => watch(
+            &config,
+            profile,
+            *interval,
+            on_change.as_deref(),
+            *exit_on_change,
+        )
+        .map(|()| ExitCode::SUCCESS),
+    }
+}
+
+/// Fetch every repository and compare their open alerts with the thresholds. Repositories whose
+/// alerts couldn't be read fail the check along with the ones over the thresholds, rather than
+/// passing as if they had no alerts.
+fn check(
+    config: &Config,
+    profile: &Profile,
+    max_critical: Option<usize>,
+    max_high: Option<usize>,
+    json: bool,
+) -> Result<ExitCode, DependabotTrackerError> {
+    let outcome = fetch(config, profile)?;
+    let repositories = outcome.repositories.repos;
+    let violations = threshold_violations(&repositories, max_critical, max_high);
+    let unchecked = unchecked_repositories(&repositories, &outcome.report);
+    if json {
+        print_json(
+            &mut io::stdout().lock(),
+            &JsonDocument::new(&repositories, Utc::now())
+                .with_violations(&violations)
+                .with_unchecked(&unchecked),
+        )?;
+    } else {
+        if violations.is_empty() {
+            println!("No repositories exceed the alert thresholds");
+        }
+        for repo in violations.iter() {
+            println!("{}", violation_line(repo, max_critical, max_high));
+        }
+        for (repo_name, reason) in unchecked.iter() {
+            println!("{}: couldn't be checked, {}", repo_name, reason);
+        }
+    }
+
+    // fail the pipeline that's running the check
+    if violations.is_empty() && unchecked.is_empty() {
+        Ok(ExitCode::SUCCESS)
+    } else {
+        Ok(ExitCode::FAILURE)
+    }
+}
+
+/// Fetch every `interval`, reporting the alerts each fetch opens compared to the one before it,
+/// starting from the latest saved snapshot. A failed fetch is logged and tried again on the next
+/// tick.
+fn watch(
+    config: &Config,
+    profile: &Profile,
+    interval: StdDuration,
+    on_change: Option<&str>,
+    exit_on_change: bool,
+) -> Result<(), DependabotTrackerError> {
+    let mut baseline = load_latest_snapshot().unwrap_or_default().repositories;
+    loop {
+        match fetch(config, profile) {
+            Ok(outcome) => {
+                let repositories = outcome.repositories;
+                let new_alerts = repositories.new_open_alerts(&baseline);
+                let changed = !new_alerts.is_empty();
+                if changed {
+                    let change =
+                        serde_json::to_string(&JsonWatchChange::new(new_alerts, Utc::now()))?;
+                    match on_change {
+                        Some(on_change) => run_on_change(on_change, &change),
+                        None => println!("{}", change),
+                    }
+                }
+                baseline = repositories.repos;
+                if changed && exit_on_change {
+                    return Ok(());
+                }
+            }
+            Err(e) => eprintln!("Warning: Failed to fetch: {}", e),
+        }
+        thread::sleep(interval);
+    }
+}
+
+/// Run the `--on-change` command with the change's JSON on its stdin. A command that can't be
+/// started or that fails is only warned about so the watch keeps going.
+fn run_on_change(on_change: &str, change: &str) {
+    let result = ProcessCommand::new("sh")
+        .arg("-c")
+        .arg(on_change)
+        .stdin(Stdio::piped())
+        .spawn()
+        .and_then(|mut child| {
+            if let Some(mut stdin) = child.stdin.take() {
+                // a command that doesn't read its stdin closes the pipe, which isn't an error
+                let _ = writeln!(stdin, "{}", change);
+            }
+            child.wait()
+        });
+    match result {
+        Ok(status) if !status.success() => {
+            eprintln!("Warning: '{}' exited with {}", on_change, status)
+        }
+        Ok(_) => {}
+        Err(e) => eprintln!("Warning: Failed to run '{}': {}", on_change, e),
+    }
+}
+
+/// Fetch every repository with the profile's token, saving them and logging the alert changes
+/// like a refresh in the TUI does.
+pub fn fetch(config: &Config, profile: &Profile) -> Result<FetchOutcome, DependabotTrackerError> {
+    if config.offline {
+        return Err(DependabotTrackerError::Config(
+            "Offline mode is on, so nothing can be fetched. Drop --offline (or the config's offline = true) to fetch".to_string(),
+        ));
+    }
+    if profile.token.is_empty() {
+        return Err(DependabotTrackerError::Config(
+            "PAT or the config's auth.token (or the profile's token) must be set to fetch without the TUI".to_string(),
+        ));
+    }
+    let provider = profile.security_provider();
+    let previous_repos = load_latest_snapshot().unwrap_or_default().repositories;
+
+    let runtime = Runtime::new()?;
+    let outcome = runtime.block_on(provider.fetch_repos(
+        &previous_repos,
+        &config.fetch,
+        &|_| {},
+        &CancellationToken::new(),
+    ))?;
+    let new_critical_alerts = outcome
+        .repositories
+        .count_new_open_alerts(&previous_repos, DependabotSeverity::Critical);
+    // the first fetch has nothing to compare against, so every alert would look new
+    let webhook_summary = (!previous_repos.is_empty())
+        .then(|| {
+            WebhookSummary::new(
+                &config.webhook,
+                &config.templates.webhook_summary,
+                TemplateContext::from_repositories(
+                    &outcome.repositories,
+                    new_critical_alerts,
+                    &config.risk,
+                ),
+                &outcome.repositories,
+                &previous_repos,
+            )
+        })
+        .flatten();
+    if let Some(summary) = webhook_summary {
+        if let Err(e) = runtime.block_on(post_webhook_summary(
+            &config.webhook,
+            &summary,
+            &Client::new(),
+        )) {
+            eprintln!("Warning: Failed to post to webhook: {}", e);
+        }
+    }
+    record_alert_events(&previous_repos, &outcome.repositories.repos);
+    for warning in outcome.warnings.iter() {
+        eprintln!("Warning: {}", warning);
+    }
+    if let Some(failure_summary) = outcome.report.failure_summary() {
+        eprintln!("Warning: {}", failure_summary);
+        for (repo_name, error) in outcome.report.failed.iter() {
+            eprintln!("  {}: {}", repo_name, error);
+        }
+    }
+
+    Ok(outcome)
+}
+
+/// The user's repositories whose open alerts aren't known, and why: the ones that failed to
+/// fetch, and the ones with Dependabot alerts turned off or hidden from the token.
+fn unchecked_repositories(
+    repositories: &[Repository],
+    report: &FetchReport,
+) -> Vec<(String, String)> {
+    let mut unchecked = report.failed.clone();
+    for repo in repositories.iter().filter(|repo| !repo.watched) {
+        let reason = match repo.alerts_status {
+            AlertsStatus::Disabled => "Dependabot alerts are disabled",
+            AlertsStatus::NoAccess => "the token can't read its alerts",
+            AlertsStatus::Enabled | AlertsStatus::Error => continue,
+        };
+        unchecked.push((repo.full_name.clone(), reason.to_string()));
+    }
+
+    unchecked
+}
+
+fn print_json(
+    writer: &mut impl Write,
+    document: &JsonDocument,
+) -> Result<(), DependabotTrackerError> {
+    serde_json::to_writer_pretty(&mut *writer, document)?;
+    writeln!(writer)?;
+    writer.flush()?;
+
+    Ok(())
+}
+
+/// The user's repositories with more open alerts than the thresholds allow.
+fn threshold_violations(
+    repositories: &[Repository],
+    max_critical: Option<usize>,
+    max_high: Option<usize>,
+) -> Vec<&Repository> {
+    repositories
+        .iter()
+        .filter(|repo| !repo.watched)
+        .filter(|repo| {
+            max_critical.is_some_and(|max_critical| repo.critical_alerts > max_critical)
+                || max_high.is_some_and(|max_high| repo.high_alerts > max_high)
+        })
+        .collect()
+}
+
+/// A line saying which of the thresholds a repository exceeds.
+fn violation_line(
+    repo: &Repository,
+    max_critical: Option<usize>,
+    max_high: Option<usize>,
+) -> String {
+    let mut exceeded = Vec::new();
+    if max_critical.is_some_and(|max_critical| repo.critical_alerts > max_critical) {
+        exceeded.push(format!("{} critical", repo.critical_alerts));
+    }
+    if max_high.is_some_and(|max_high| repo.high_alerts > max_high) {
+        exceeded.push(format!("{} high", repo.high_alerts));
+    }
+
+    format!("{}: {} open alerts", repo.full_name, exceeded.join(", "))
+}
+
+fn load_repositories() -> Result<Vec<Repository>, DependabotTrackerError> {
+    // sort the saved repositories the same way the repository list does
+    Ok(RepositoryList::with_respositories(load_latest_snapshot()?.repositories).repos)
+}
+
+fn print_report(writer: &mut impl Write, repositories: &[Repository]) -> io::Result<()> {
+    writeln!(
+        writer,
+        "{: <40} {: >8} {: >8} {: >8} {: >8} {: >8}",
+        "Repository", "Critical", "High", "Medium", "Low", "Total"
+    )?;
+    for repo in repositories {
+        let mut name = repo.full_name.clone();
+        if repo.watched {
+            name.push_str(" (watched)");
+        }
+        writeln!(
+            writer,
+            "{: <40} {: >8} {: >8} {: >8} {: >8} {: >8}",
+            name,
+            repo.critical_alerts,
+            repo.high_alerts,
+            repo.medium_alerts,
+            repo.low_alerts,
+            repo.total_active_alerts
+        )?;
+    }
+
+    let owned = repositories.iter().filter(|repo| !repo.watched);
+    writeln!(
+        writer,
+        "\n{} open alerts across {} repositories",
+        owned
+            .clone()
+            .map(|repo| repo.total_active_alerts)
+            .sum::<usize>(),
+        owned.count()
+    )
+}
@@ -0,0 +1,127 @@
+use crate::app::{Movement, HALF_PAGE, PAGE};
+use crate::current_screen::CurrentScreen;
+use crate::keymap::{screen_actions, Action, Keymap};
+
+// the screens the help popup lists keys for, in the order they're shown
+const HELP_SCREENS: [(CurrentScreen, &str); 15] = [
+    (CurrentScreen::Overview, "Overview"),
+    (CurrentScreen::ProjectList, "Repository List"),
+    (CurrentScreen::Project, "Repository"),
+    (CurrentScreen::DependabotDetails, "Dependabot Alerts"),
+    (CurrentScreen::AlertDetail, "Alert Detail"),
+    (
+        CurrentScreen::CodeScanning,
+        "Resolved Alerts, Code Scanning, and Secret Scanning",
+    ),
+    (CurrentScreen::DistinctAlerts, "Distinct Alerts"),
+    (CurrentScreen::CopyleftReport, "Copyleft Report"),
+    (CurrentScreen::Dependencies, "Dependencies"),
+    (CurrentScreen::Changes, "Changes"),
+    (CurrentScreen::AuditComparison, "Audit Comparison"),
+    (CurrentScreen::Profiles, "Profiles"),
+    (CurrentScreen::Update, "Update"),
+    (CurrentScreen::ConfirmOpenAlerts, "Open Alerts"),
+    (CurrentScreen::WhatsNew, "What's New"),
+];
+
+// the actions that work on every screen that isn't taking text input
+const GLOBAL_ACTIONS: [Action; 5] = [
+    Action::Help,
+    Action::ToggleNotifications,
+    Action::ToggleFixableOnly,
+    Action::NextTheme,
+    Action::TogglePerformance,
+];
+
+/// The actions the help popup responds to while it's open.
+pub const HELP_POPUP_ACTIONS: [Action; 11] = [
+    Action::Up,
+    Action::Down,
+    Action::Top,
+    Action::Bottom,
+    Action::HalfPageDown,
+    Action::HalfPageUp,
+    Action::PageDown,
+    Action::PageUp,
+    Action::Help,
+    Action::Back,
+    Action::Quit,
+];
+
+/// A heading in the help popup and the keys listed under it, as the keys bound to each action
+/// and what the action does.
+pub struct HelpSection {
+    pub title: String,
+    pub entries: Vec<(String, &'static str)>,
+}
+
+/// The keybindings popup, opened with `?` over whatever screen is showing.
+#[derive(Default)]
+pub struct Help {
+    pub visible: bool,
+    // how many lines the popup is scrolled down
+    pub scroll: usize,
+    // the furthest the popup can scroll, as of the last draw
+    pub max_scroll: usize,
+}
+
+impl Help {
+    pub fn open(&mut self) {
+        self.visible = true;
+        self.scroll = 0;
+    }
+
+    pub fn close(&mut self) {
+        self.visible = false;
+    }
+
+    pub fn scroll(&mut self, movement: Movement) {
+        self.scroll = match movement {
+            Movement::Up => self.scroll.saturating_sub(1),
+            Movement::Down => self.scroll + 1,
+            Movement::Top => 0,
+            Movement::Bottom => self.max_scroll,
+            Movement::HalfPageDown => self.scroll + HALF_PAGE,
+            Movement::HalfPageUp => self.scroll.saturating_sub(HALF_PAGE),
+            Movement::PageDown => self.scroll + PAGE,
+            Movement::PageUp => self.scroll.saturating_sub(PAGE),
+        }
+        .min(self.max_scroll);
+    }
+}
+
+/// The help popup's sections, generated from the keymap so rebound keys show up. The current
+/// screen's keys come first, then the global keys, then every other screen's.
+pub fn help_sections(keymap: &Keymap, current_screen: CurrentScreen) -> Vec<HelpSection> {
+    let section = |title: String, actions: &[Action]| HelpSection {
+        title,
+        entries: actions
+            .iter()
+            .map(|action| (keymap.describe(*action), action.description()))
+            .collect(),
+    };
+    // the resolved alerts and scanning tabs share a section
+    let current_screen = match current_screen {
+        CurrentScreen::ResolvedAlerts | CurrentScreen::SecretScanning => {
+            CurrentScreen::CodeScanning
+        }
+        screen => screen,
+    };
+
+    let mut current = Vec::new();
+    let mut others = Vec::new();
+    for (screen, title) in HELP_SCREENS {
+        if screen == current_screen {
+            current.push(section(
+                format!("{} (current screen)", title),
+                screen_actions(screen),
+            ));
+        } else {
+            others.push(section(title.to_string(), screen_actions(screen)));
+        }
+    }
+
+    current.push(section("Everywhere".to_string(), &GLOBAL_ACTIONS));
+    current.extend(others);
+    current
+}
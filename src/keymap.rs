@@ -0,0 +1,535 @@
+use std::collections::HashMap;
+use std::fmt::{self, Display, Formatter};
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use serde::{Deserialize, Deserializer};
+
+use crate::app::Movement;
+use crate::current_screen::CurrentScreen;
+
+/// Something a key press can do. Screens match on actions rather than keys, so any action can
+/// be rebound from the config's `[keymap]` section.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Action {
+    Quit,
+    Up,
+    Down,
+    Top,
+    Bottom,
+    HalfPageDown,
+    HalfPageUp,
+    PageDown,
+    PageUp,
+    Select,
+    Back,
+    NextTab,
+    Overview,
+    Repositories,
+    Project,
+    Update,
+    Confirm,
+    Deny,
+    StaleRefresh,
+    Search,
+    Sort,
+    ToggleArchived,
+    Changes,
+    Profiles,
+    SecurityReport,
+    Export,
+    NextGroup,
+    NextTopic,
+    ToggleSection,
+    ToggleAllSections,
+    DistinctAlerts,
+    Dependencies,
+    CopyleftReport,
+    SeverityPercentages,
+    AlertTrend,
+    CopySummary,
+    CopyUrl,
+    OpenSecurityTab,
+    AuditComparison,
+    Dismiss,
+    MarkAlert,
+    OpenMarkedAlerts,
+    OpenInBrowser,
+    ToggleLow,
+    ToggleMedium,
+    ToggleHigh,
+    ToggleCritical,
+    ToggleClosedAlerts,
+    ToggleDevelopmentAlerts,
+    EnableAlerts,
+    ToggleNotifications,
+    ToggleFixableOnly,
+    TogglePerformance,
+    NextTheme,
+    SendToBackground,
+    Help,
+}
+
+impl Action {
+    /// How the action moves a list's selection, for the navigation actions.
+    pub fn movement(self) -> Option<Movement> {
+        match self {
+            Action::Up => Some(Movement::Up),
+            Action::Down => Some(Movement::Down),
+            Action::Top => Some(Movement::Top),
+            Action::Bottom => Some(Movement::Bottom),
+            Action::HalfPageDown => Some(Movement::HalfPageDown),
+            Action::HalfPageUp => Some(Movement::HalfPageUp),
+            Action::PageDown => Some(Movement::PageDown),
+            Action::PageUp => Some(Movement::PageUp),
+            _ => None,
+        }
+    }
+
+    /// The keys the action is bound to unless the config rebinds it.
+    fn default_keys(self) -> &'static [&'static str] {
+        match self {
+            Action::Quit => &["q"],
+            Action::Up => &["up", "k"],
+            Action::Down => &["down", "j"],
+            Action::Top => &["home", "g"],
+            Action::Bottom => &["end", "G"],
+            Action::HalfPageDown => &["ctrl-d"],
+            Action::HalfPageUp => &["ctrl-u"],
+            Action::PageDown => &["pagedown"],
+            Action::PageUp => &["pageup"],
+            Action::Select => &["enter"],
+            Action::Back => &["esc"],
+            Action::NextTab => &["tab"],
+            Action::Overview => &["o"],
+            Action::Repositories => &["r"],
+            Action::Project => &["p"],
+            Action::Update => &["u"],
+            Action::Confirm => &["y"],
+            Action::Deny => &["n"],
+            Action::StaleRefresh => &["s"],
+            Action::Search => &["/"],
+            Action::Sort => &["s"],
+            Action::ToggleArchived => &["h"],
+            Action::Changes => &["c"],
+            Action::Profiles => &["P"],
+            Action::SecurityReport => &["e"],
+            Action::Export => &["x"],
+            // `g` jumps to the top of the repository list, so groups are cycled with `ctrl-g`
+            // there
+            Action::NextGroup => &["g", "ctrl-g"],
+            Action::NextTopic => &["t"],
+            Action::ToggleSection => &["z"],
+            Action::ToggleAllSections => &["Z"],
+            Action::DistinctAlerts => &["d"],
+            Action::Dependencies => &["k"],
+            Action::CopyleftReport => &["l"],
+            Action::SeverityPercentages => &["p"],
+            Action::AlertTrend => &["v"],
+            Action::CopySummary => &["c"],
+            Action::CopyUrl => &["y"],
+            Action::OpenSecurityTab => &["s"],
+            Action::AuditComparison => &["a"],
+            Action::Dismiss => &["d"],
+            Action::MarkAlert => &["m"],
+            Action::OpenMarkedAlerts => &["b"],
+            Action::OpenInBrowser => &["b"],
+            Action::ToggleLow => &["1"],
+            Action::ToggleMedium => &["2"],
+            Action::ToggleHigh => &["3"],
+            Action::ToggleCritical => &["4"],
+            Action::ToggleClosedAlerts => &["a"],
+            Action::ToggleDevelopmentAlerts => &["D"],
+            Action::EnableAlerts => &["E"],
+            Action::ToggleNotifications => &["n"],
+            Action::ToggleFixableOnly => &["f"],
+            Action::TogglePerformance => &["f12"],
+            Action::NextTheme => &["T"],
+            Action::SendToBackground => &["b"],
+            Action::Help => &["?"],
+        }
+    }
+
+    /// What the action does, for the help popup.
+    pub fn description(self) -> &'static str {
+        match self {
+            Action::Quit => "quit",
+            Action::Up => "move up",
+            Action::Down => "move down",
+            Action::Top => "jump to the top",
+            Action::Bottom => "jump to the bottom",
+            Action::HalfPageDown => "move down half a page",
+            Action::HalfPageUp => "move up half a page",
+            Action::PageDown => "move down a page",
+            Action::PageUp => "move up a page",
+            Action::Select => "open the selection",
+            Action::Back => "go back or clear the search",
+            Action::NextTab => "switch tabs",
+            Action::Overview => "view overview",
+            Action::Repositories => "view repositories",
+            Action::Project => "view repository",
+            Action::Update => "update repositories",
+            Action::Confirm => "confirm",
+            Action::Deny => "cancel",
+            Action::StaleRefresh => "refresh stale repositories",
+            Action::Search => "search",
+            Action::Sort => "change sort",
+            Action::ToggleArchived => "toggle archived",
+            Action::Changes => "view changes since last refresh",
+            Action::Profiles => "switch profile",
+            Action::SecurityReport => "export security report",
+            Action::Export => "export alerts as CSV",
+            Action::NextGroup => "switch group",
+            Action::NextTopic => "switch topic",
+            Action::ToggleSection => "collapse/expand owner",
+            Action::ToggleAllSections => "collapse/expand all owners",
+            Action::DistinctAlerts => "view distinct alerts",
+            Action::Dependencies => "view dependencies",
+            Action::CopyleftReport => "view copyleft report",
+            Action::SeverityPercentages => "toggle percentages",
+            Action::AlertTrend => "toggle trend",
+            Action::CopySummary => "copy summary",
+            Action::CopyUrl => "copy URL",
+            Action::OpenSecurityTab => "open security tab",
+            Action::AuditComparison => "compare audit report",
+            Action::Dismiss => "dismiss alert",
+            Action::MarkAlert => "mark alert",
+            Action::OpenMarkedAlerts => "open marked alerts",
+            Action::OpenInBrowser => "open in browser",
+            Action::ToggleLow => "toggle low alerts",
+            Action::ToggleMedium => "toggle medium alerts",
+            Action::ToggleHigh => "toggle high alerts",
+            Action::ToggleCritical => "toggle critical alerts",
+            Action::ToggleClosedAlerts => "toggle dismissed/fixed",
+            Action::ToggleDevelopmentAlerts => "toggle development dependencies",
+            Action::EnableAlerts => "enable Dependabot alerts",
+            Action::ToggleNotifications => "toggle notifications",
+            Action::ToggleFixableOnly => "toggle fixable only",
+            Action::TogglePerformance => "toggle debug overlay",
+            Action::NextTheme => "switch theme",
+            Action::SendToBackground => "keep working while the update runs",
+            Action::Help => "show keybindings",
+        }
+    }
+}
+
+/// The actions each screen responds to. When several share a key, the one listed first wins.
+/// Screens that take text input, like the credentials prompt, read their keys directly instead.
+pub fn screen_actions(screen: CurrentScreen) -> &'static [Action] {
+    match screen {
+        CurrentScreen::Overview => &[
+            Action::ToggleArchived,
+            Action::Changes,
+            Action::Profiles,
+            Action::SecurityReport,
+            Action::Export,
+            Action::NextGroup,
+            Action::NextTopic,
+            Action::DistinctAlerts,
+            Action::Dependencies,
+            Action::CopyleftReport,
+            Action::SeverityPercentages,
+            Action::AlertTrend,
+            Action::Repositories,
+            Action::Update,
+            Action::Quit,
+        ],
+        CurrentScreen::Update => &[
+            Action::Confirm,
+            Action::StaleRefresh,
+            Action::Deny,
+            Action::Quit,
+        ],
+        CurrentScreen::ProjectList => &[
+            Action::Up,
+            Action::Down,
+            Action::Top,
+            Action::Bottom,
+            Action::HalfPageDown,
+            Action::HalfPageUp,
+            Action::PageDown,
+            Action::PageUp,
+            Action::Select,
+            Action::Search,
+            Action::Sort,
+            Action::Back,
+            Action::ToggleArchived,
+            Action::NextGroup,
+            Action::NextTopic,
+            Action::ToggleSection,
+            Action::ToggleAllSections,
+            Action::Overview,
+            Action::DistinctAlerts,
+            Action::Update,
+            Action::Quit,
+        ],
+        CurrentScreen::Project => &[
+            Action::NextTab,
+            Action::Repositories,
+            Action::CopySummary,
+            Action::CopyUrl,
+            Action::OpenSecurityTab,
+            Action::AuditComparison,
+            Action::EnableAlerts,
+            Action::Quit,
+        ],
+        CurrentScreen::DependabotDetails => &[
+            Action::Up,
+            Action::Down,
+            Action::Top,
+            Action::Bottom,
+            Action::HalfPageDown,
+            Action::HalfPageUp,
+            Action::PageDown,
+            Action::PageUp,
+            Action::Select,
+            Action::NextTab,
+            Action::Overview,
+            Action::Dismiss,
+            Action::MarkAlert,
+            Action::OpenMarkedAlerts,
+            Action::CopyUrl,
+            Action::ToggleLow,
+            Action::ToggleMedium,
+            Action::ToggleHigh,
+            Action::ToggleCritical,
+            Action::ToggleClosedAlerts,
+            Action::ToggleDevelopmentAlerts,
+            Action::Sort,
+            Action::Search,
+            Action::Back,
+            Action::Quit,
+        ],
+        CurrentScreen::ResolvedAlerts
+        | CurrentScreen::CodeScanning
+        | CurrentScreen::SecretScanning => &[
+            Action::Up,
+            Action::Down,
+            Action::Top,
+            Action::Bottom,
+            Action::HalfPageDown,
+            Action::HalfPageUp,
+            Action::PageDown,
+            Action::PageUp,
+            Action::Select,
+            Action::CopyUrl,
+            Action::NextTab,
+            Action::Overview,
+            Action::Repositories,
+            Action::Quit,
+        ],
+        CurrentScreen::AlertDetail => &[
+            Action::Up,
+            Action::Down,
+            Action::Top,
+            Action::HalfPageDown,
+            Action::HalfPageUp,
+            Action::PageDown,
+            Action::PageUp,
+            Action::Dismiss,
+            Action::OpenInBrowser,
+            Action::CopyUrl,
+            Action::Back,
+            Action::NextTab,
+            Action::Quit,
+        ],
+        CurrentScreen::DistinctAlerts | CurrentScreen::Changes | CurrentScreen::CopyleftReport => {
+            &[
+                Action::Up,
+                Action::Down,
+                Action::Top,
+                Action::Bottom,
+                Action::HalfPageDown,
+                Action::HalfPageUp,
+                Action::PageDown,
+                Action::PageUp,
+                Action::Overview,
+                Action::Repositories,
+                Action::Quit,
+            ]
+        }
+        CurrentScreen::Dependencies => &[
+            Action::Up,
+            Action::Down,
+            Action::Top,
+            Action::Bottom,
+            Action::HalfPageDown,
+            Action::HalfPageUp,
+            Action::PageDown,
+            Action::PageUp,
+            Action::Select,
+            Action::Back,
+            Action::Overview,
+            Action::Repositories,
+            Action::Quit,
+        ],
+        CurrentScreen::Profiles => &[
+            Action::Up,
+            Action::Down,
+            Action::Top,
+            Action::Bottom,
+            Action::HalfPageDown,
+            Action::HalfPageUp,
+            Action::PageDown,
+            Action::PageUp,
+            Action::Select,
+            Action::Back,
+            Action::Quit,
+        ],
+        CurrentScreen::AuditComparison => &[
+            Action::Project,
+            Action::Back,
+            Action::Repositories,
+            Action::Quit,
+        ],
+        CurrentScreen::ConfirmOpenAlerts => &[Action::Confirm, Action::Deny, Action::Back],
+        CurrentScreen::WhatsNew => &[Action::Changes, Action::Select, Action::Back, Action::Quit],
+        CurrentScreen::Error => &[Action::Select, Action::Back],
+        CurrentScreen::Login => &[Action::Back],
+        CurrentScreen::Updating => &[Action::Back, Action::SendToBackground],
+        CurrentScreen::Credentials
+        | CurrentScreen::Setup
+        | CurrentScreen::Dismiss
+        | CurrentScreen::Export => &[],
+    }
+}
+
+/// A key and the modifiers held with it, written like `j`, `G`, `ctrl-d`, `enter`, or `f12`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyBinding {
+    pub code: KeyCode,
+    pub modifiers: KeyModifiers,
+}
+
+impl KeyBinding {
+    pub fn parse(binding: &str) -> Option<KeyBinding> {
+        let (modifiers, key) = if let Some(key) = binding.strip_prefix("ctrl-") {
+            (KeyModifiers::CONTROL, key)
+        } else if let Some(key) = binding.strip_prefix("alt-") {
+            (KeyModifiers::ALT, key)
+        } else {
+            (KeyModifiers::NONE, binding)
+        };
+
+        Some(KeyBinding {
+            code: parse_key_code(key)?,
+            modifiers,
+        })
+    }
+
+    /// Whether the key press is this binding. Shift is ignored, since it's already part of
+    /// characters like `G` and `?`.
+    pub fn matches(&self, key: &KeyEvent) -> bool {
+        key.code == self.code && key.modifiers.difference(KeyModifiers::SHIFT) == self.modifiers
+    }
+}
+
+impl Display for KeyBinding {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            write!(f, "ctrl-")?;
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            write!(f, "alt-")?;
+        }
+        match self.code {
+            KeyCode::Enter => write!(f, "enter"),
+            KeyCode::Tab => write!(f, "tab"),
+            KeyCode::Esc => write!(f, "esc"),
+            KeyCode::Char(' ') => write!(f, "space"),
+            KeyCode::Backspace => write!(f, "backspace"),
+            KeyCode::Delete => write!(f, "delete"),
+            KeyCode::Up => write!(f, "up"),
+            KeyCode::Down => write!(f, "down"),
+            KeyCode::Left => write!(f, "left"),
+            KeyCode::Right => write!(f, "right"),
+            KeyCode::Home => write!(f, "home"),
+            KeyCode::End => write!(f, "end"),
+            KeyCode::PageUp => write!(f, "pageup"),
+            KeyCode::PageDown => write!(f, "pagedown"),
+            KeyCode::F(number) => write!(f, "f{}", number),
+            KeyCode::Char(c) => write!(f, "{}", c),
+            code => write!(f, "{:?}", code),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for KeyBinding {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let binding = String::deserialize(deserializer)?;
+        KeyBinding::parse(&binding)
+            .ok_or_else(|| serde::de::Error::custom(format!("unrecognized key: {}", binding)))
+    }
+}
+
+/// A key by name, e.g. `enter`, `tab`, `up`, or `f12`, or a single character.
+fn parse_key_code(name: &str) -> Option<KeyCode> {
+    let key_code = match name.to_lowercase().as_str() {
+        "enter" => KeyCode::Enter,
+        "tab" => KeyCode::Tab,
+        "esc" => KeyCode::Esc,
+        "space" => KeyCode::Char(' '),
+        "backspace" => KeyCode::Backspace,
+        "delete" => KeyCode::Delete,
+        "up" => KeyCode::Up,
+        "down" => KeyCode::Down,
+        "left" => KeyCode::Left,
+        "right" => KeyCode::Right,
+        "home" => KeyCode::Home,
+        "end" => KeyCode::End,
+        "pageup" => KeyCode::PageUp,
+        "pagedown" => KeyCode::PageDown,
+        lowercase => match lowercase.strip_prefix('f').map(str::parse) {
+            Some(Ok(number)) => KeyCode::F(number),
+            _ => {
+                let mut chars = name.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) => KeyCode::Char(c),
+                    _ => return None,
+                }
+            }
+        },
+    };
+
+    Some(key_code)
+}
+
+/// The config's `[keymap]` section: the keys for any actions being rebound, e.g.
+/// `down = ["j", "ctrl-n"]`. Actions it leaves out keep their default keys.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(transparent)]
+pub struct Keymap {
+    bindings: HashMap<Action, Vec<KeyBinding>>,
+}
+
+impl Keymap {
+    /// The keys bound to an action.
+    pub fn keys(&self, action: Action) -> Vec<KeyBinding> {
+        match self.bindings.get(&action) {
+            Some(bindings) => bindings.clone(),
+            None => action
+                .default_keys()
+                .iter()
+                .filter_map(|key| KeyBinding::parse(key))
+                .collect(),
+        }
+    }
+
+    /// The keys bound to an action, written like `up/k`.
+    pub fn describe(&self, action: Action) -> String {
+        self.keys(action)
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join("/")
+    }
+
+    /// The first of `actions` the key press is bound to.
+    pub fn resolve(&self, key: &KeyEvent, actions: &[Action]) -> Option<Action> {
+        actions.iter().copied().find(|action| {
+            self.keys(*action)
+                .iter()
+                .any(|binding| binding.matches(key))
+        })
+    }
+}
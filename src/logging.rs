@@ -1,6 +1,7 @@
 use std::path::PathBuf;
 
 use color_eyre::eyre::Result;
+use directories::ProjectDirs;
 use lazy_static::lazy_static;
 use tracing_error::ErrorLayer;
 use tracing_subscriber::{self, layer::SubscriberExt, util::SubscriberInitExt, Layer};
@@ -11,13 +12,22 @@ lazy_static! {
     pub static ref LOG_FILE: String = format!("{}.log", env!("CARGO_PKG_NAME"));
 }
 
-pub fn get_data_dir() -> PathBuf {
-    PathBuf::from(".").join(".data")
+/// Where the log and crash reports are written: the platform's state directory, e.g.
+/// `~/.local/state/dependabot-tracker` on Linux, or its local data directory on platforms
+/// without one.
+pub fn get_state_dir() -> PathBuf {
+    ProjectDirs::from("", "", env!("CARGO_PKG_NAME"))
+        .map(|dirs| {
+            dirs.state_dir()
+                .unwrap_or_else(|| dirs.data_local_dir())
+                .to_path_buf()
+        })
+        .unwrap_or_else(|| PathBuf::from(".").join(".data"))
 }
 
-/// Initialize the logging system. This will create a log file in project-name/.data/project-name.log.
+/// Initialize the logging system. This will create a log file in the state directory.
 pub fn initialize_logging() -> Result<()> {
-    let directory = get_data_dir();
+    let directory = get_state_dir();
     std::fs::create_dir_all(directory.clone())?;
     let log_path = directory.join(LOG_FILE.clone());
     let log_file = std::fs::File::create(log_path)?;
@@ -35,29 +45,3 @@ pub fn initialize_logging() -> Result<()> {
         .init();
     Ok(())
 }
-
-/// Similar to the `std::dbg!` macro, but generates `tracing` events rather
-/// than printing to stdout.
-///
-/// By default, the verbosity level for the generated events is `DEBUG`, but
-/// this can be customized.
-#[macro_export]
-macro_rules! trace_dbg {
-    (target: $target:expr, level: $level:expr, $ex:expr) => {{
-        match $ex {
-            value => {
-                tracing::event!(target: $target, $level, ?value, stringify!($ex));
-                value
-            }
-        }
-    }};
-    (level: $level:expr, $ex:expr) => {
-        trace_dbg!(target: module_path!(), level: $level, $ex)
-    };
-    (target: $target:expr, $ex:expr) => {
-        trace_dbg!(target: $target, level: tracing::Level::DEBUG, $ex)
-    };
-    ($ex:expr) => {
-        trace_dbg!(level: tracing::Level::DEBUG, $ex)
-    };
-}
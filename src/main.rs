@@ -1,14 +1,15 @@
 use std::{
     error::Error,
-    io,
+    fs, io,
     panic::{set_hook, take_hook},
-    sync::mpsc::{self, TryRecvError},
-    thread,
+    process::ExitCode,
+    time::Instant,
 };
 
+use clap::Parser;
 use color_eyre::eyre::Result;
 use crossterm::{
-    event::{self, Event, KeyCode},
+    event::{self, DisableMouseCapture, EnableMouseCapture, KeyCode, KeyEvent, KeyModifiers},
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
@@ -17,35 +18,116 @@ use ratatui::{
     backend::{Backend, CrosstermBackend},
     Terminal,
 };
-use repository_list::RepositoryList;
 
+mod alert_filter;
+mod alert_text;
 mod app;
+mod audit;
+mod cli;
+mod config;
+mod crash_report;
+mod credentials;
 mod current_screen;
-mod dependabot;
+mod desktop_notification;
+mod device_flow;
+mod digest;
+mod dismissal;
+mod events;
+mod export;
+mod headless;
+mod help;
+mod keymap;
 mod logging;
-mod repository;
-mod repository_list;
+mod mouse;
+mod notifications;
+mod performance;
+mod profile;
+mod script;
+mod setup;
+mod templates;
+mod theme;
 mod ui;
-use crate::app::{App, DependabotScrollbar, DependabotTrackerError};
+mod update_progress;
+mod webhook;
+
+// the models, clients, and snapshots live in the core library, imported here so the rest of
+// the app reaches them through `crate::`
+use crate::app::App;
+use crate::audit::AuditReport;
+use crate::cli::Cli;
+use crate::config::Config;
+use crate::crash_report::{update_crash_context, write_crash_report};
 use crate::current_screen::CurrentScreen;
+use crate::dependabot::DependabotSeverity;
+use crate::dismissal::DismissalField;
+use crate::error::DependabotTrackerError;
+use crate::events::{AppEvent, EventSource};
+use crate::help::HELP_POPUP_ACTIONS;
+use crate::keymap::{screen_actions, Action};
 use crate::logging::initialize_logging;
-use crate::repository::fetch_github_repos;
+use crate::mouse::handle_mouse;
+use crate::repository::FetchProgress;
+use crate::script::Script;
+use crate::setup::SetupStep;
+use crate::storage::set_data_directory;
+use dependabot_tracker_core::{
+    dependabot, error, event_log, fetch_config, github_client, gitlab, json_output, osv, provider,
+    pull_request, report, repository, repository_list, repository_tree, risk, scanning, search,
+    security_alert, sla, storage, timezone, trace_dbg,
+};
 
-fn main() -> Result<(), Box<dyn Error>> {
+fn main() -> Result<ExitCode, Box<dyn Error>> {
+    let cli = Cli::parse();
     dotenv().ok();
     initialize_logging()?;
     init_panic_hook();
+    let script = cli.script.as_deref().map(Script::load).transpose()?;
+    let config = Config::resolve(cli.config.as_deref(), cli.config_overrides())?;
+    let audit_reports = cli
+        .audit
+        .iter()
+        .map(|(repository, path)| AuditReport::load(repository, path))
+        .collect::<io::Result<Vec<_>>>()?;
+    let profile = cli
+        .profile
+        .as_deref()
+        .map(|name| {
+            config
+                .profile(name)
+                .ok_or_else(|| io::Error::other(format!("No profile named {} in the config", name)))
+        })
+        .transpose()?;
+    let data_dir = profile
+        .as_ref()
+        .map_or_else(|| config.data_dir(), |profile| profile.data_dir.clone());
+    fs::create_dir_all(&data_dir)?;
+    set_data_directory(data_dir);
+    if let Some(command) = &cli.command {
+        let profile = profile.unwrap_or_else(|| config.default_profile());
+        return Ok(headless::run(command, cli.output, config, &profile)?);
+    }
 
     let mut tui = init_tui()?;
-    let mut app = App::new();
-    let res = run_app(&mut tui, &mut app);
+    let mut app = match profile {
+        Some(profile) => App::new(config, profile),
+        None => App::with_config(config),
+    };
+    app.audit_reports = audit_reports;
+    // a config file passed with --config counts as being set up
+    if cli.config.is_none() && app.needs_setup() {
+        app.open_setup();
+    }
+    if cli.worst_first || app.config.worst_first {
+        app.open_highest_risk_repository();
+    }
+    let res = run_app(&mut tui, &mut app, script);
     let _ = restore_tui();
 
     if let Err(err) = res {
         println!("{err:?}");
     }
 
-    Ok(())
+    Ok(ExitCode::SUCCESS)
 }
 
 pub fn init_panic_hook() {
@@ -53,14 +135,18 @@ pub fn init_panic_hook() {
     set_hook(Box::new(move |panic_info| {
         // intentionally ignore errors here since we're already in a panic
         let _ = restore_tui();
+        let crash_report = write_crash_report(panic_info);
         original_hook(panic_info);
+        if let Ok(path) = crash_report {
+            eprintln!("A crash report was written to {}", path.display());
+        }
     }));
 }
 
 pub fn init_tui() -> io::Result<Terminal<impl Backend>> {
     enable_raw_mode()?;
     let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen)?;
+    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
 
     let backend = CrosstermBackend::new(stdout);
 
@@ -70,7 +156,7 @@ pub fn init_tui() -> io::Result<Terminal<impl Backend>> {
 pub fn restore_tui() -> io::Result<()> {
     disable_raw_mode()?;
     let mut stdout = io::stdout();
-    execute!(stdout, LeaveAlternateScreen,)?;
+    execute!(stdout, LeaveAlternateScreen, DisableMouseCapture)?;
 
     Ok(())
 }
@@ -78,141 +164,489 @@ pub fn restore_tui() -> io::Result<()> {
 fn run_app<B: Backend>(
     terminal: &mut Terminal<B>,
     app: &mut App,
+    script: Option<Script>,
 ) -> Result<(), DependabotTrackerError> {
+    let mut events = EventSource::new(script);
+    let mut redraw = true;
+    let mut last_key: Option<Instant> = None;
     loop {
-        terminal
-            .draw(|f| ui::ui(f, app))
-            .map_err(|e| Box::new(e) as DependabotTrackerError)?;
-
-        if let Event::Key(key) = event::read().map_err(|e| Box::new(e) as DependabotTrackerError)? {
-            if key.kind == event::KeyEventKind::Release {
-                // Skip events that are not KeyEventKind::Press
-                continue;
-            }
-            match app.current_screen {
-                CurrentScreen::Overview => match key.code {
-                    KeyCode::Char('r') => {
-                        app.current_screen = CurrentScreen::ProjectList;
-                    }
-                    KeyCode::Char('q') => {
-                        return Ok(());
-                    }
-                    KeyCode::Char('u') => {
-                        app.current_screen = CurrentScreen::Update;
-                    }
-                    _ => {}
-                },
-                CurrentScreen::Update => match key.code {
-                    KeyCode::Char('y') => {
-                        app.current_screen = CurrentScreen::Updating;
-                        let (tx, rx) = mpsc::channel();
-                        let username = app.username.clone();
-                        let token = app.token.clone();
-
-                        thread::spawn(move || {
-                            let result: Result<RepositoryList, DependabotTrackerError> =
-                                fetch_github_repos(&username, &token);
-                            tx.send(result).unwrap();
-                        });
-
-                        app.current_screen = CurrentScreen::Updating;
-                        app.fetching = Some(rx);
-                    }
-                    KeyCode::Char('n') => {
-                        app.current_screen = CurrentScreen::ProjectList;
-                    }
-                    KeyCode::Char('q') => {
-                        return Ok(());
-                    }
-                    _ => {}
-                },
-                CurrentScreen::ProjectList => match key.code {
-                    KeyCode::Enter => {
-                        if let Some(repo) = app.repositories.get_selected_repository() {
-                            app.current_repository = Some(repo.clone());
-                            app.current_screen = CurrentScreen::Project;
-                            app.scrollbar = DependabotScrollbar::new(repo.total_active_alerts * 10);
-
-                            trace_dbg!(level: tracing::Level::INFO, app.scrollbar.get_length());
-                        }
-                    }
-                    KeyCode::Up => {
-                        app.repositories.previous();
-                    }
-                    KeyCode::Down => {
-                        app.repositories.next();
-                    }
-                    KeyCode::Char('o') => {
-                        app.current_screen = CurrentScreen::Overview;
-                    }
-                    KeyCode::Char('u') => {
-                        app.current_screen = CurrentScreen::Update;
-                    }
-                    KeyCode::Char('q') => {
-                        return Ok(());
-                    }
-                    _ => {}
-                },
-                CurrentScreen::Project => match key.code {
-                    KeyCode::Char('r') => {
-                        app.current_screen = CurrentScreen::ProjectList;
-                    }
-                    KeyCode::Tab => {
-                        app.current_screen = CurrentScreen::DependabotDetails;
-                    }
-                    KeyCode::Char('q') => {
-                        return Ok(());
-                    }
-                    _ => {}
-                },
-                CurrentScreen::DependabotDetails => match key.code {
-                    KeyCode::Up => {
-                        app.scrollbar.scroll_up();
-                    }
-                    KeyCode::Down => {
-                        app.scrollbar.scroll_down();
-                    }
-                    KeyCode::Tab => {
-                        app.current_screen = CurrentScreen::Project;
-                    }
-                    KeyCode::Char('o') => {
-                        app.current_screen = CurrentScreen::Overview;
-                    }
-                    KeyCode::Char('t') => {
-                        app.scrollbar.top();
-                    }
-                    KeyCode::Char('q') => {
-                        return Ok(());
-                    }
-                    _ => {}
-                },
-                _ => {}
+        if redraw {
+            update_crash_context(
+                format!("{:?}", app.current_screen),
+                app.repositories
+                    .get_selected_repository()
+                    .map(|repo| repo.full_name.clone()),
+            );
+            let render_start = Instant::now();
+            terminal.draw(|f| ui::ui(f, app))?;
+            app.performance.render_time = render_start.elapsed();
+            if let Some(last_key) = last_key.take() {
+                app.performance.event_latency = last_key.elapsed();
             }
         }
 
-        while let Some(rx) = &app.fetching {
-            match rx.try_recv() {
-                Ok(result) => {
-                    app.repositories = result?;
-                    app.fetching = None;
+        // fetch messages only update state, the next tick draws them
+        redraw = false;
+        match events.next(app)? {
+            AppEvent::Key(key) => {
+                last_key = Some(Instant::now());
+                if handle_key(app, key) {
+                    return Ok(());
+                }
+                redraw = true;
+            }
+            AppEvent::Mouse(mouse) => {
+                last_key = Some(Instant::now());
+                handle_mouse(app, mouse);
+                redraw = true;
+            }
+            AppEvent::Tick => {
+                app.on_tick();
+                redraw = true;
+            }
+            AppEvent::FetchProgress(FetchProgress::RepositoryFetched(repo)) => {
+                app.on_repository_fetched(*repo);
+                redraw = true;
+            }
+            AppEvent::FetchProgress(progress) => {
+                app.fetch_progress.apply(progress);
+            }
+            AppEvent::FetchDone(result) => {
+                app.on_fetch_done(result);
+            }
+        }
+    }
+}
+
+/// Apply a key press to the app, returning whether the app should quit.
+fn handle_key(app: &mut App, key: KeyEvent) -> bool {
+    if key.kind == event::KeyEventKind::Release {
+        // Skip events that are not KeyEventKind::Press
+        return false;
+    }
+    if matches!(app.current_screen, CurrentScreen::Updating) {
+        // the Updating popup blocks input until the fetch is done, unless it's cancelled or
+        // sent to the background
+        match app
+            .config
+            .keymap
+            .resolve(&key, screen_actions(CurrentScreen::Updating))
+        {
+            Some(Action::Back) => app.cancel_fetch(),
+            Some(Action::SendToBackground) => app.send_fetch_to_background(),
+            _ => {}
+        }
+        return false;
+    }
+    if app
+        .config
+        .keymap
+        .resolve(&key, &[Action::TogglePerformance])
+        .is_some()
+    {
+        app.performance.toggle();
+        return false;
+    }
+    if app.help.visible {
+        match app.config.keymap.resolve(&key, &HELP_POPUP_ACTIONS) {
+            Some(Action::Quit) => return true,
+            Some(Action::Help | Action::Back) => app.help.close(),
+            Some(action) => {
+                if let Some(movement) = action.movement() {
+                    app.help.scroll(movement);
+                }
+            }
+            None => {}
+        }
+        return false;
+    }
+    if !app.is_editing_text() && app.config.keymap.resolve(&key, &[Action::Help]).is_some() {
+        app.help.open();
+        return false;
+    }
+    if !app.is_prompting() {
+        match app.config.keymap.resolve(
+            &key,
+            &[
+                Action::ToggleNotifications,
+                Action::ToggleFixableOnly,
+                Action::NextTheme,
+            ],
+        ) {
+            Some(Action::ToggleNotifications) => {
+                app.notifications.toggle();
+                return false;
+            }
+            Some(Action::ToggleFixableOnly) => {
+                app.toggle_fixable_only();
+                return false;
+            }
+            Some(Action::NextTheme) => {
+                app.next_theme();
+                return false;
+            }
+            _ => {}
+        }
+    }
+    // screens taking text input read their keys directly, since typed letters aren't actions
+    match app.current_screen {
+        CurrentScreen::ProjectList if app.repository_search.editing => {
+            match key.code {
+                KeyCode::Char(c) => {
+                    app.push_repository_search(c);
+                }
+                KeyCode::Backspace => {
+                    app.pop_repository_search();
+                }
+                KeyCode::Up => {
+                    app.previous_repository();
+                }
+                KeyCode::Down => {
+                    app.next_repository();
+                }
+                KeyCode::Enter => {
+                    app.repository_search.editing = false;
+                }
+                KeyCode::Esc => {
+                    app.clear_repository_search();
+                }
+                _ => {}
+            }
+            return false;
+        }
+        CurrentScreen::DependabotDetails if app.alert_filter.package_search.editing => {
+            match key.code {
+                KeyCode::Char(c) => {
+                    app.push_package_search(c);
+                }
+                KeyCode::Backspace => {
+                    app.pop_package_search();
+                }
+                KeyCode::Up => {
+                    app.scrollbar.scroll_up();
+                }
+                KeyCode::Down => {
+                    app.scrollbar.scroll_down();
+                }
+                KeyCode::Enter => {
+                    app.alert_filter.package_search.editing = false;
+                }
+                KeyCode::Esc => {
+                    app.clear_package_search();
+                }
+                _ => {}
+            }
+            return false;
+        }
+        CurrentScreen::Dismiss => {
+            match (app.dismissal_input.focus, key.code) {
+                (_, KeyCode::Tab) => {
+                    app.dismissal_input.toggle_focus();
+                }
+                (_, KeyCode::Enter) => {
+                    app.submit_dismissal();
+                }
+                (_, KeyCode::Esc) => {
+                    app.cancel_dismissal();
+                }
+                (DismissalField::Reason, KeyCode::Up) => {
+                    app.dismissal_input.previous_reason();
+                }
+                (DismissalField::Reason, KeyCode::Down) => {
+                    app.dismissal_input.next_reason();
+                }
+                (DismissalField::Comment, KeyCode::Char(c)) => {
+                    app.dismissal_input.insert(c);
+                }
+                (DismissalField::Comment, KeyCode::Backspace) => {
+                    app.dismissal_input.backspace();
+                }
+                (DismissalField::Comment, KeyCode::Delete) => {
+                    app.dismissal_input.delete();
+                }
+                (DismissalField::Comment, KeyCode::Left) => {
+                    app.dismissal_input.move_left();
+                }
+                (DismissalField::Comment, KeyCode::Right) => {
+                    app.dismissal_input.move_right();
+                }
+                (DismissalField::Comment, KeyCode::Home) => {
+                    app.dismissal_input.move_home();
+                }
+                (DismissalField::Comment, KeyCode::End) => {
+                    app.dismissal_input.move_end();
+                }
+                _ => {}
+            }
+            return false;
+        }
+        CurrentScreen::Credentials => {
+            match key.code {
+                KeyCode::Tab => {
+                    app.credentials_input.next_field();
+                }
+                KeyCode::Char('l') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    app.start_login();
+                }
+                KeyCode::Char(c) => {
+                    app.credentials_input.push(c);
+                }
+                KeyCode::Backspace => {
+                    app.credentials_input.pop();
+                }
+                KeyCode::Enter => {
+                    app.submit_credentials();
+                }
+                KeyCode::Esc => {
+                    app.close_credentials_prompt();
+                }
+                _ => {}
+            }
+            return false;
+        }
+        CurrentScreen::Setup => {
+            match key.code {
+                KeyCode::Char('l')
+                    if key.modifiers.contains(KeyModifiers::CONTROL)
+                        && app.setup.step == SetupStep::Token =>
+                {
+                    app.start_login();
+                }
+                KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    app.setup.push(c);
+                }
+                KeyCode::Backspace => {
+                    app.setup.pop();
+                }
+                KeyCode::Enter => {
+                    app.next_setup_step();
+                }
+                KeyCode::Esc => {
+                    app.previous_setup_step();
+                }
+                _ => {}
+            }
+            return false;
+        }
+        CurrentScreen::Export => {
+            match key.code {
+                KeyCode::Enter => {
+                    app.submit_export();
+                }
+                KeyCode::Esc => {
                     app.current_screen = CurrentScreen::Overview;
                 }
-                Err(TryRecvError::Empty) => {
-                    // The fetch is still in progress, update the UI as usual
-                    app.on_tick();
-                    terminal
-                        .draw(|f| ui::ui(f, app))
-                        .map_err(|e| Box::new(e) as DependabotTrackerError)?;
+                KeyCode::Backspace => {
+                    app.export_path.pop();
                 }
-                Err(TryRecvError::Disconnected) => {
-                    // The fetch thread has panicked or been unexpectedly terminated
-                    return Err(Box::new(std::io::Error::new(
-                        std::io::ErrorKind::Other,
-                        "Fetch thread terminated unexpectedly",
-                    )));
+                KeyCode::Char(c) => {
+                    app.export_path.push(c);
                 }
+                _ => {}
             }
-            std::thread::sleep(std::time::Duration::from_millis(200));
+            return false;
         }
+        _ => {}
     }
+
+    match app
+        .config
+        .keymap
+        .resolve(&key, screen_actions(app.current_screen))
+    {
+        Some(action) => handle_action(app, action),
+        None => false,
+    }
+}
+
+/// Apply an action to the current screen, returning whether the app should quit.
+fn handle_action(app: &mut App, action: Action) -> bool {
+    if let Some(movement) = action.movement() {
+        app.move_selection(movement);
+        return false;
+    }
+
+    match (app.current_screen, action) {
+        (_, Action::Quit) => {
+            return true;
+        }
+        (_, Action::Overview) => {
+            app.current_screen = CurrentScreen::Overview;
+        }
+        (_, Action::Repositories) => {
+            app.current_screen = CurrentScreen::ProjectList;
+        }
+        (_, Action::Update) => {
+            app.open_update_prompt();
+        }
+        (_, Action::ToggleArchived) => {
+            app.toggle_hide_archived();
+        }
+        (_, Action::Changes) => {
+            app.open_changes();
+        }
+        (_, Action::Profiles) => {
+            app.open_profiles();
+        }
+        (_, Action::SecurityReport) => {
+            app.write_security_report();
+        }
+        (_, Action::Export) => {
+            app.current_screen = CurrentScreen::Export;
+        }
+        (_, Action::NextGroup) => {
+            app.next_group();
+        }
+        (_, Action::NextTopic) => {
+            app.next_topic();
+        }
+        (_, Action::DistinctAlerts) => {
+            app.open_distinct_alerts();
+        }
+        (_, Action::Dependencies) => {
+            app.open_dependencies();
+        }
+        (_, Action::CopyleftReport) => {
+            app.open_copyleft_report();
+        }
+        (_, Action::SeverityPercentages) => {
+            app.show_severity_percentages = !app.show_severity_percentages;
+        }
+        (_, Action::AlertTrend) => {
+            app.show_alert_trend = !app.show_alert_trend;
+        }
+        (CurrentScreen::Update, Action::Confirm) => {
+            app.start_fetch();
+        }
+        (CurrentScreen::Update, Action::StaleRefresh) => {
+            app.start_stale_refresh();
+        }
+        (CurrentScreen::Update, Action::Deny) => {
+            app.current_screen = CurrentScreen::ProjectList;
+        }
+        (CurrentScreen::ProjectList, Action::Select) => {
+            app.open_highlighted_row();
+        }
+        (CurrentScreen::ProjectList, Action::ToggleSection) => {
+            app.toggle_repository_section();
+        }
+        (CurrentScreen::ProjectList, Action::ToggleAllSections) => {
+            app.toggle_all_repository_sections();
+        }
+        (CurrentScreen::ProjectList, Action::Search) => {
+            app.repository_search.editing = true;
+        }
+        (CurrentScreen::ProjectList, Action::Sort) => {
+            app.next_sort_mode();
+        }
+        (CurrentScreen::ProjectList, Action::Back) => {
+            app.clear_repository_search();
+        }
+        (CurrentScreen::AlertDetail, Action::NextTab | Action::Back) => {
+            app.close_alert_detail();
+        }
+        (_, Action::NextTab) => {
+            app.next_tab();
+        }
+        (_, Action::CopySummary) => {
+            app.copy_repository_summary();
+        }
+        (_, Action::CopyUrl) => {
+            app.copy_url();
+        }
+        (_, Action::OpenSecurityTab) => {
+            app.open_security_tab();
+        }
+        (_, Action::AuditComparison) => {
+            app.open_audit_comparison();
+        }
+        (_, Action::Dismiss) => {
+            app.start_dismissal();
+        }
+        (_, Action::MarkAlert) => {
+            app.toggle_marked_alert();
+        }
+        (_, Action::OpenMarkedAlerts) => {
+            app.start_opening_marked_alerts();
+        }
+        (_, Action::ToggleLow) => {
+            app.toggle_alert_severity(DependabotSeverity::Low);
+        }
+        (_, Action::ToggleMedium) => {
+            app.toggle_alert_severity(DependabotSeverity::Medium);
+        }
+        (_, Action::ToggleHigh) => {
+            app.toggle_alert_severity(DependabotSeverity::High);
+        }
+        (_, Action::ToggleCritical) => {
+            app.toggle_alert_severity(DependabotSeverity::Critical);
+        }
+        (_, Action::ToggleClosedAlerts) => {
+            app.toggle_closed_alerts();
+        }
+        (_, Action::ToggleDevelopmentAlerts) => {
+            app.toggle_development_alerts();
+        }
+        (CurrentScreen::Project, Action::EnableAlerts) => {
+            app.enable_alerts();
+        }
+        (CurrentScreen::DependabotDetails, Action::Sort) => {
+            app.next_alert_sort();
+        }
+        (CurrentScreen::DependabotDetails, Action::Search) => {
+            app.alert_filter.package_search.editing = true;
+        }
+        (CurrentScreen::DependabotDetails, Action::Back)
+            if !app.alert_filter.package_search.query.is_empty() =>
+        {
+            app.clear_package_search();
+        }
+        (CurrentScreen::DependabotDetails, Action::Select) => {
+            app.open_alert_detail();
+        }
+        (
+            CurrentScreen::ResolvedAlerts
+            | CurrentScreen::CodeScanning
+            | CurrentScreen::SecretScanning,
+            Action::Select,
+        ) => {
+            app.open_selected_security_alert();
+        }
+        (_, Action::OpenInBrowser) => {
+            app.open_detailed_alert();
+        }
+        (CurrentScreen::Dependencies, Action::Select) => {
+            app.drill_into_dependency();
+        }
+        (CurrentScreen::Dependencies, Action::Back) => {
+            app.leave_dependency();
+        }
+        (CurrentScreen::AuditComparison, Action::Project | Action::Back) => {
+            app.current_screen = CurrentScreen::Project;
+        }
+        (CurrentScreen::ConfirmOpenAlerts, Action::Confirm) => {
+            app.open_marked_alerts();
+        }
+        (CurrentScreen::ConfirmOpenAlerts, Action::Deny | Action::Back) => {
+            app.current_screen = CurrentScreen::DependabotDetails;
+        }
+        (CurrentScreen::Profiles, Action::Select) => {
+            app.switch_to_selected_profile();
+        }
+        (CurrentScreen::Profiles, Action::Back) => {
+            app.current_screen = CurrentScreen::Overview;
+        }
+        (CurrentScreen::WhatsNew, Action::Select | Action::Back) => {
+            app.current_screen = CurrentScreen::Overview;
+        }
+        (CurrentScreen::Error, Action::Select | Action::Back) => {
+            app.dismiss_error();
+        }
+        (CurrentScreen::Login, Action::Back) => {
+            app.cancel_login();
+        }
+        _ => {}
+    }
+
+    false
 }
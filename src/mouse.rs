@@ -0,0 +1,104 @@
+use crossterm::event::{MouseButton, MouseEvent, MouseEventKind};
+use ratatui::layout::{Position, Rect};
+
+use crate::app::{App, Movement};
+use crate::current_screen::CurrentScreen;
+use crate::repository_tree::TreeRow;
+
+/// The repository list as it was last drawn.
+pub struct RepositoryListArea {
+    pub area: Rect,
+    // the first row scrolled into view
+    pub offset: usize,
+    // what's on each row of the list
+    pub rows: Vec<TreeRow>,
+}
+
+/// Where the clickable parts of the last drawn frame were, so a mouse event can be matched to
+/// what was under the cursor. It's started over on every draw.
+#[derive(Default)]
+pub struct HitAreas {
+    pub repository_list: Option<RepositoryListArea>,
+    pub alert_list: Option<Rect>,
+    // each tab label and the screen it switches to
+    pub tabs: Vec<(Rect, CurrentScreen)>,
+}
+
+impl HitAreas {
+    /// The repository or section header at `position` in the repository list, if there's one
+    /// there.
+    fn repository_row_at(&self, position: Position) -> Option<&TreeRow> {
+        let list = self.repository_list.as_ref()?;
+        if !list.area.contains(position) {
+            return None;
+        }
+        let row = list.offset + usize::from(position.y - list.area.y);
+
+        list.rows.get(row)
+    }
+
+    fn is_over_alert_list(&self, position: Position) -> bool {
+        self.alert_list.is_some_and(|area| area.contains(position))
+    }
+
+    fn tab_at(&self, position: Position) -> Option<CurrentScreen> {
+        self.tabs
+            .iter()
+            .find(|(area, _)| area.contains(position))
+            .map(|(_, screen)| *screen)
+    }
+}
+
+/// Apply a mouse event to the app. Clicking a repository selects it, or opens it if it was
+/// already selected, clicking a section header collapses or expands it, clicking a tab label switches to that tab, and the wheel scrolls the
+/// repository list and the Dependabot Details alerts, or the help popup while it's open.
+pub fn handle_mouse(app: &mut App, mouse: MouseEvent) {
+    let position = Position::new(mouse.column, mouse.row);
+    if app.help.visible {
+        // the help popup covers the screen, so the wheel scrolls it instead
+        match mouse.kind {
+            MouseEventKind::ScrollDown => app.help.scroll(Movement::Down),
+            MouseEventKind::ScrollUp => app.help.scroll(Movement::Up),
+            _ => {}
+        }
+        return;
+    }
+    match (app.current_screen, mouse.kind) {
+        (CurrentScreen::ProjectList, MouseEventKind::Down(MouseButton::Left)) => {
+            match app.hit_areas.repository_row_at(position).cloned() {
+                Some(TreeRow::Repository(index)) => app.click_repository(index),
+                Some(TreeRow::Section(header)) => app.click_section(header.section),
+                None => {}
+            }
+        }
+        (CurrentScreen::ProjectList, MouseEventKind::ScrollDown) => {
+            app.next_repository();
+        }
+        (CurrentScreen::ProjectList, MouseEventKind::ScrollUp) => {
+            app.previous_repository();
+        }
+        (CurrentScreen::DependabotDetails, MouseEventKind::ScrollDown)
+            if app.hit_areas.is_over_alert_list(position) =>
+        {
+            app.scrollbar.scroll_down();
+        }
+        (CurrentScreen::DependabotDetails, MouseEventKind::ScrollUp)
+            if app.hit_areas.is_over_alert_list(position) =>
+        {
+            app.scrollbar.scroll_up();
+        }
+        (
+            CurrentScreen::Project
+            | CurrentScreen::DependabotDetails
+            | CurrentScreen::ResolvedAlerts
+            | CurrentScreen::CodeScanning
+            | CurrentScreen::SecretScanning,
+            MouseEventKind::Down(MouseButton::Left),
+        ) => {
+            if let Some(screen) = app.hit_areas.tab_at(position) {
+                app.open_tab(screen);
+            }
+        }
+        _ => {}
+    }
+}
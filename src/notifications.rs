@@ -0,0 +1,110 @@
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Utc};
+use ratatui::style::Color;
+
+use crate::theme::Theme;
+
+// how long each status message stays in the footer
+const STATUS_DURATION: Duration = Duration::from_secs(3);
+// how many status messages can wait to be shown, counting the one showing
+const MAX_QUEUED_STATUS_MESSAGES: usize = 5;
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum NotificationLevel {
+    Info,
+    Warning,
+    Error,
+}
+
+impl NotificationLevel {
+    pub fn color(&self, theme: &Theme) -> Color {
+        match self {
+            NotificationLevel::Info => theme.text,
+            NotificationLevel::Warning => theme.warning,
+            NotificationLevel::Error => theme.error,
+        }
+    }
+}
+
+pub struct Notification {
+    pub timestamp: DateTime<Utc>,
+    pub level: NotificationLevel,
+    pub message: String,
+}
+
+/// The events that happened during this session, reviewable in the notification drawer.
+#[derive(Default)]
+pub struct Notifications {
+    pub entries: Vec<Notification>,
+    // whether the drawer is open
+    pub visible: bool,
+}
+
+impl Notifications {
+    pub fn push(&mut self, level: NotificationLevel, message: impl Into<String>) {
+        self.entries.push(Notification {
+            timestamp: Utc::now(),
+            level,
+            message: message.into(),
+        });
+    }
+
+    pub fn toggle(&mut self) {
+        self.visible = !self.visible;
+    }
+}
+
+/// A short message shown in the footer for a few seconds, like "Copied URL".
+pub struct StatusMessage {
+    pub level: NotificationLevel,
+    pub message: String,
+    // when the message first showed, once it's reached the front of the queue
+    shown_at: Option<Instant>,
+}
+
+/// The messages waiting to show in the footer, each shown in turn for `STATUS_DURATION`.
+#[derive(Default)]
+pub struct StatusMessages {
+    queue: VecDeque<StatusMessage>,
+}
+
+impl StatusMessages {
+    pub fn push(&mut self, level: NotificationLevel, message: impl Into<String>) {
+        // drop the oldest waiting message rather than fall behind
+        if self.queue.len() == MAX_QUEUED_STATUS_MESSAGES {
+            self.queue.remove(1);
+        }
+        self.queue.push_back(StatusMessage {
+            level,
+            message: message.into(),
+            shown_at: None,
+        });
+        self.start_next();
+    }
+
+    /// The message showing in the footer, if any.
+    pub fn current(&self) -> Option<&StatusMessage> {
+        self.queue.front()
+    }
+
+    /// Move on from the current message once it's been shown long enough.
+    pub fn tick(&mut self) {
+        if self
+            .queue
+            .front()
+            .and_then(|status| status.shown_at)
+            .is_some_and(|shown_at| shown_at.elapsed() >= STATUS_DURATION)
+        {
+            self.queue.pop_front();
+        }
+        self.start_next();
+    }
+
+    fn start_next(&mut self) {
+        if let Some(status) = self.queue.front_mut() {
+            status.shown_at.get_or_insert_with(Instant::now);
+        }
+    }
+}
@@ -0,0 +1,46 @@
+use std::mem::size_of;
+use std::time::Duration;
+
+use crate::dependabot::Dependabot;
+use crate::repository::Repository;
+
+/// Timings for the hidden debug overlay, toggled with F12.
+#[derive(Default)]
+pub struct PerformanceStats {
+    pub visible: bool,
+    // how long the last frame took to render
+    pub render_time: Duration,
+    // how long it took from receiving the last key event to finishing the next frame
+    pub event_latency: Duration,
+}
+
+impl PerformanceStats {
+    pub fn toggle(&mut self) {
+        self.visible = !self.visible;
+    }
+}
+
+/// A rough estimate of the heap and inline memory used by the loaded repositories.
+pub fn estimate_data_size(repositories: &[Repository]) -> usize {
+    repositories
+        .iter()
+        .map(|repo| {
+            size_of::<Repository>()
+                + repo.name.capacity()
+                + repo.full_name.capacity()
+                + repo.url.capacity()
+                + repo
+                    .dependabots
+                    .iter()
+                    .map(|dependabot| {
+                        size_of::<Dependabot>()
+                            + dependabot.html_url.capacity()
+                            + dependabot.created_at.capacity()
+                            + dependabot.updated_at.capacity()
+                            + dependabot.dependency_ecosystem.capacity()
+                            + dependabot.dependency_name.capacity()
+                    })
+                    .sum::<usize>()
+        })
+        .sum()
+}
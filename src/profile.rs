@@ -0,0 +1,64 @@
+use std::path::PathBuf;
+
+use serde::Deserialize;
+
+use crate::github_client::GITHUB_API_URL;
+use crate::gitlab::{GitLabProvider, GITLAB_URL};
+use crate::provider::{Provider, ProviderKind};
+use crate::repository::GitHubProvider;
+
+// the profile made from `PAT`/`GH_USERNAME` and the `[auth]` section
+pub const DEFAULT_PROFILE: &str = "default";
+
+/// A named GitHub or GitLab account from a `[profiles.<name>]` section of the config.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct ProfileConfig {
+    pub username: Option<String>,
+    // read from the OS keyring under the username when it isn't set
+    pub token: Option<String>,
+    // where the profile's snapshots and event log are kept, `<data_dir>/<name>` by default
+    pub data_dir: Option<PathBuf>,
+    // `github` or `gitlab`
+    pub provider: ProviderKind,
+    // the GitLab instance to fetch from, `https://gitlab.com` by default
+    pub gitlab_url: Option<String>,
+}
+
+/// The account the app tracks: who it fetches as and where it keeps what it fetched.
+#[derive(Debug, Clone)]
+pub struct Profile {
+    pub name: String,
+    pub username: String,
+    pub token: String,
+    pub data_dir: PathBuf,
+    pub provider: ProviderKind,
+    pub gitlab_url: Option<String>,
+}
+
+impl Profile {
+    pub fn is_default(&self) -> bool {
+        self.name == DEFAULT_PROFILE
+    }
+
+    pub fn has_credentials(&self) -> bool {
+        !self.token.is_empty() && !self.username.is_empty()
+    }
+
+    /// The provider to fetch the profile's repositories and alerts from.
+    pub fn security_provider(&self) -> Provider {
+        match self.provider {
+            ProviderKind::GitHub => Provider::GitHub(GitHubProvider {
+                token: self.token.clone(),
+                api_url: GITHUB_API_URL.to_string(),
+            }),
+            ProviderKind::GitLab => Provider::GitLab(GitLabProvider {
+                token: self.token.clone(),
+                base_url: self
+                    .gitlab_url
+                    .clone()
+                    .unwrap_or_else(|| GITLAB_URL.to_string()),
+            }),
+        }
+    }
+}
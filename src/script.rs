@@ -0,0 +1,71 @@
+use std::collections::VecDeque;
+use std::path::Path;
+use std::time::Duration;
+use std::{fs, io, thread};
+
+use crossterm::event::{Event, KeyEvent};
+
+use crate::keymap::KeyBinding;
+
+/// A single step of a keystroke script.
+enum ScriptStep {
+    Key(KeyBinding),
+    Wait(Duration),
+}
+
+/// A sequence of key commands fed to the app in place of user input, loaded from a file with one
+/// step per line, e.g. `u`, `y`, `enter`, `ctrl-d`, or `wait 500`. Blank lines and lines starting
+/// with `#` are ignored.
+pub struct Script {
+    steps: VecDeque<ScriptStep>,
+}
+
+impl Script {
+    pub fn load(path: &Path) -> io::Result<Script> {
+        let contents = fs::read_to_string(path)?;
+        let mut steps = VecDeque::new();
+
+        for (index, line) in contents.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let step = parse_step(line).ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("Unrecognized script step on line {}: {}", index + 1, line),
+                )
+            })?;
+            steps.push_back(step);
+        }
+
+        Ok(Script { steps })
+    }
+
+    /// The next key press from the script, after waiting out any pauses before it. Returns `None`
+    /// once the script has run out, at which point input comes from the keyboard again.
+    pub fn next_event(&mut self) -> Option<Event> {
+        while let Some(step) = self.steps.pop_front() {
+            match step {
+                ScriptStep::Key(binding) => {
+                    return Some(Event::Key(KeyEvent::new(binding.code, binding.modifiers)))
+                }
+                ScriptStep::Wait(duration) => thread::sleep(duration),
+            }
+        }
+
+        None
+    }
+}
+
+fn parse_step(step: &str) -> Option<ScriptStep> {
+    if let Some(milliseconds) = step.strip_prefix("wait ") {
+        return milliseconds
+            .trim()
+            .parse()
+            .ok()
+            .map(|milliseconds| ScriptStep::Wait(Duration::from_millis(milliseconds)));
+    }
+
+    KeyBinding::parse(step).map(ScriptStep::Key)
+}
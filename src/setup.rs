@@ -0,0 +1,177 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use toml::{Table, Value};
+
+use crate::error::DependabotTrackerError;
+
+/// The pages of the setup wizard, in the order they're shown.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum SetupStep {
+    #[default]
+    Username,
+    Token,
+    Organizations,
+    DataDir,
+}
+
+impl SetupStep {
+    pub const ALL: [SetupStep; 4] = [
+        SetupStep::Username,
+        SetupStep::Token,
+        SetupStep::Organizations,
+        SetupStep::DataDir,
+    ];
+
+    /// The step's position in the wizard, counting from 1.
+    pub fn number(self) -> usize {
+        SetupStep::ALL
+            .iter()
+            .position(|step| *step == self)
+            .unwrap_or_default()
+            + 1
+    }
+
+    pub fn title(self) -> &'static str {
+        match self {
+            SetupStep::Username => "Username",
+            SetupStep::Token => "Token",
+            SetupStep::Organizations => "Organizations",
+            SetupStep::DataDir => "Data Location",
+        }
+    }
+}
+
+/// The answers given on the setup wizard shown on the first run, before there's a config file
+/// or any credentials.
+#[derive(Default)]
+pub struct SetupWizard {
+    pub step: SetupStep,
+    pub username: String,
+    pub token: String,
+    // organizations whose repositories to track too, separated by commas
+    pub organizations: String,
+    pub data_dir: String,
+}
+
+impl SetupWizard {
+    /// A wizard suggesting `data_dir` as the data location.
+    pub fn new(data_dir: &Path) -> Self {
+        SetupWizard {
+            data_dir: data_dir.display().to_string(),
+            ..SetupWizard::default()
+        }
+    }
+
+    fn field(&mut self) -> &mut String {
+        match self.step {
+            SetupStep::Username => &mut self.username,
+            SetupStep::Token => &mut self.token,
+            SetupStep::Organizations => &mut self.organizations,
+            SetupStep::DataDir => &mut self.data_dir,
+        }
+    }
+
+    pub fn push(&mut self, c: char) {
+        self.field().push(c);
+    }
+
+    pub fn pop(&mut self) {
+        self.field().pop();
+    }
+
+    /// Whether the current step has been answered. Only the organizations can be left empty.
+    pub fn is_step_complete(&self) -> bool {
+        match self.step {
+            SetupStep::Username => !self.username.trim().is_empty(),
+            SetupStep::Token => !self.token.trim().is_empty(),
+            SetupStep::Organizations => true,
+            SetupStep::DataDir => !self.data_dir.trim().is_empty(),
+        }
+    }
+
+    pub fn is_last_step(&self) -> bool {
+        self.step == SetupStep::DataDir
+    }
+
+    pub fn next_step(&mut self) {
+        self.step = match self.step {
+            SetupStep::Username => SetupStep::Token,
+            SetupStep::Token => SetupStep::Organizations,
+            SetupStep::Organizations | SetupStep::DataDir => SetupStep::DataDir,
+        };
+    }
+
+    /// Go back a step, returning false if this is already the first one.
+    pub fn previous_step(&mut self) -> bool {
+        self.step = match self.step {
+            SetupStep::Username => return false,
+            SetupStep::Token => SetupStep::Username,
+            SetupStep::Organizations => SetupStep::Token,
+            SetupStep::DataDir => SetupStep::Organizations,
+        };
+        true
+    }
+
+    pub fn masked_token(&self) -> String {
+        "*".repeat(self.token.chars().count())
+    }
+
+    pub fn organizations(&self) -> Vec<String> {
+        self.organizations
+            .split(',')
+            .map(str::trim)
+            .filter(|organization| !organization.is_empty())
+            .map(String::from)
+            .collect()
+    }
+
+    pub fn data_dir(&self) -> PathBuf {
+        PathBuf::from(self.data_dir.trim())
+    }
+}
+
+/// Write the username, organizations, and data directory chosen on the setup wizard into the
+/// config file at `path`, keeping anything else that's already in it. The data directory is
+/// left out when it's the default one.
+pub fn save_setup(
+    path: &Path,
+    username: &str,
+    organizations: &[String],
+    data_dir: Option<&Path>,
+) -> Result<(), DependabotTrackerError> {
+    let mut config = match fs::read_to_string(path) {
+        Ok(contents) => contents.parse::<Table>().map_err(|e| {
+            DependabotTrackerError::Config(format!("Failed to parse {}: {}", path.display(), e))
+        })?,
+        Err(_) => Table::new(),
+    };
+
+    if !organizations.is_empty() {
+        config.insert(
+            "organizations".to_string(),
+            Value::Array(organizations.iter().cloned().map(Value::String).collect()),
+        );
+    }
+    if let Some(data_dir) = data_dir {
+        config.insert(
+            "data_dir".to_string(),
+            Value::String(data_dir.display().to_string()),
+        );
+    }
+    let auth = config
+        .entry("auth")
+        .or_insert_with(|| Value::Table(Table::new()));
+    if let Value::Table(auth) = auth {
+        auth.insert("username".to_string(), Value::String(username.to_string()));
+    }
+
+    let contents =
+        toml::to_string(&config).map_err(|e| DependabotTrackerError::Config(e.to_string()))?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, contents)?;
+
+    Ok(())
+}
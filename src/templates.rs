@@ -0,0 +1,107 @@
+use serde::Deserialize;
+
+use crate::dependabot::DependabotSeverity;
+use crate::repository_list::RepositoryList;
+use crate::risk::RiskConfig;
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct Templates {
+    // the summary posted after a refresh finishes
+    pub refresh_summary: String,
+    // the text of the Slack message posted to the webhook after a refresh
+    pub webhook_summary: String,
+    // the subject of the email sent by `dependabot-tracker digest`
+    pub digest_subject: String,
+    // the line above the digest's table of repositories
+    pub digest_summary: String,
+}
+
+impl Default for Templates {
+    fn default() -> Self {
+        Templates {
+            refresh_summary: "Refresh finished: {repo_count} repositories, {total} open alerts ({critical} critical, {high} high)".to_string(),
+            webhook_summary: "*Dependabot:* {new_count} new and {resolved_count} resolved alert(s){new_alerts}{resolved_alerts}".to_string(),
+            digest_subject: "Dependabot digest: {total} open alerts ({total_change})".to_string(),
+            digest_summary: "{total} open alerts, {weekly_change}".to_string(),
+        }
+    }
+}
+
+/// The placeholder values available to a message template, e.g. `{critical}`.
+pub struct TemplateContext {
+    values: Vec<(&'static str, String)>,
+}
+
+impl TemplateContext {
+    pub fn from_repositories(
+        repositories: &RepositoryList,
+        new_critical_alerts: usize,
+        risk: &RiskConfig,
+    ) -> Self {
+        let count = |severity: DependabotSeverity| -> usize {
+            repositories
+                .owned()
+                .map(|repo| match severity {
+                    DependabotSeverity::Low => repo.low_alerts,
+                    DependabotSeverity::Medium => repo.medium_alerts,
+                    DependabotSeverity::High => repo.high_alerts,
+                    DependabotSeverity::Critical => repo.critical_alerts,
+                })
+                .sum()
+        };
+
+        let riskiest = risk.riskiest(repositories.owned(), 5);
+
+        TemplateContext {
+            values: vec![
+                ("repo_count", repositories.owned().count().to_string()),
+                (
+                    "total",
+                    repositories
+                        .owned()
+                        .map(|repo| repo.total_active_alerts)
+                        .sum::<usize>()
+                        .to_string(),
+                ),
+                ("critical", count(DependabotSeverity::Critical).to_string()),
+                ("high", count(DependabotSeverity::High).to_string()),
+                ("medium", count(DependabotSeverity::Medium).to_string()),
+                ("low", count(DependabotSeverity::Low).to_string()),
+                ("new_critical", new_critical_alerts.to_string()),
+                (
+                    "top_repos",
+                    riskiest
+                        .iter()
+                        .map(|(repo, _)| repo.name.as_str())
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                ),
+                (
+                    "top_links",
+                    riskiest
+                        .iter()
+                        .map(|(repo, _)| repo.security_tab_url())
+                        .collect::<Vec<_>>()
+                        .join("\n"),
+                ),
+            ],
+        }
+    }
+
+    /// Add a placeholder only one kind of message has, like the webhook's `{new_alerts}`.
+    pub fn with(mut self, key: &'static str, value: impl Into<String>) -> Self {
+        self.values.push((key, value.into()));
+        self
+    }
+}
+
+/// Fill in the `{placeholder}`s in `template`. Unknown placeholders are left as-is.
+pub fn render_template(template: &str, context: &TemplateContext) -> String {
+    context
+        .values
+        .iter()
+        .fold(template.to_string(), |message, (key, value)| {
+            message.replace(&format!("{{{}}}", key), value)
+        })
+}
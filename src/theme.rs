@@ -0,0 +1,195 @@
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+
+use ratatui::style::Color;
+use serde::Deserialize;
+
+use crate::dependabot::DependabotSeverity;
+
+/// The built-in color schemes, cycled through at runtime with `T`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ThemeName {
+    #[default]
+    Dark,
+    Light,
+    HighContrast,
+}
+
+impl ThemeName {
+    pub fn next(self) -> ThemeName {
+        match self {
+            ThemeName::Dark => ThemeName::Light,
+            ThemeName::Light => ThemeName::HighContrast,
+            ThemeName::HighContrast => ThemeName::Dark,
+        }
+    }
+}
+
+impl Display for ThemeName {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            ThemeName::Dark => write!(f, "dark"),
+            ThemeName::Light => write!(f, "light"),
+            ThemeName::HighContrast => write!(f, "high contrast"),
+        }
+    }
+}
+
+/// The colors the interface is drawn with.
+#[derive(Debug, Clone, Copy)]
+pub struct Theme {
+    // body text, borders, and list selections
+    pub text: Color,
+    // titles, the current selection, and things that went well
+    pub accent: Color,
+    // screen names and section headings
+    pub heading: Color,
+    // the key hints in the footer
+    pub hint: Color,
+    pub error: Color,
+    pub warning: Color,
+    // the names of popups in the footer
+    pub popup: Color,
+    // timestamps and other secondary text
+    pub muted: Color,
+    // spinners and progress
+    pub progress: Color,
+    // the debug overlay
+    pub debug: Color,
+    pub low: Color,
+    pub medium: Color,
+    pub high: Color,
+    pub critical: Color,
+}
+
+impl Theme {
+    pub fn built_in(name: ThemeName) -> Theme {
+        match name {
+            ThemeName::Dark => Theme {
+                text: Color::Blue,
+                accent: Color::Green,
+                heading: Color::Yellow,
+                hint: Color::Red,
+                error: Color::Red,
+                warning: Color::Yellow,
+                popup: Color::LightRed,
+                muted: Color::DarkGray,
+                progress: Color::Cyan,
+                debug: Color::Magenta,
+                low: Color::Blue,
+                medium: Color::Green,
+                high: Color::Rgb(255, 165, 0),
+                critical: Color::Red,
+            },
+            // darker shades that stay readable on a white background
+            ThemeName::Light => Theme {
+                text: Color::Rgb(0, 0, 160),
+                accent: Color::Rgb(0, 110, 0),
+                heading: Color::Rgb(130, 80, 0),
+                hint: Color::Rgb(160, 0, 0),
+                error: Color::Rgb(160, 0, 0),
+                warning: Color::Rgb(130, 80, 0),
+                popup: Color::Rgb(140, 0, 140),
+                muted: Color::Rgb(90, 90, 90),
+                progress: Color::Rgb(0, 100, 130),
+                debug: Color::Rgb(140, 0, 140),
+                low: Color::Rgb(0, 0, 160),
+                medium: Color::Rgb(0, 110, 0),
+                high: Color::Rgb(190, 90, 0),
+                critical: Color::Rgb(160, 0, 0),
+            },
+            ThemeName::HighContrast => Theme {
+                text: Color::White,
+                accent: Color::LightGreen,
+                heading: Color::LightYellow,
+                hint: Color::LightCyan,
+                error: Color::LightRed,
+                warning: Color::LightYellow,
+                popup: Color::LightMagenta,
+                muted: Color::Gray,
+                progress: Color::LightCyan,
+                debug: Color::LightMagenta,
+                low: Color::LightBlue,
+                medium: Color::LightGreen,
+                high: Color::LightYellow,
+                critical: Color::LightRed,
+            },
+        }
+    }
+
+    pub fn severity_color(&self, severity: DependabotSeverity) -> Color {
+        match severity {
+            DependabotSeverity::Low => self.low,
+            DependabotSeverity::Medium => self.medium,
+            DependabotSeverity::High => self.high,
+            DependabotSeverity::Critical => self.critical,
+        }
+    }
+}
+
+/// The config's `[theme]` section: which built-in theme to start with, and colors to use in
+/// place of the built-in ones.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct ThemeConfig {
+    pub name: ThemeName,
+    pub text: Option<ThemeColor>,
+    pub accent: Option<ThemeColor>,
+    pub heading: Option<ThemeColor>,
+    pub hint: Option<ThemeColor>,
+    pub error: Option<ThemeColor>,
+    pub warning: Option<ThemeColor>,
+    pub popup: Option<ThemeColor>,
+    pub muted: Option<ThemeColor>,
+    pub progress: Option<ThemeColor>,
+    pub debug: Option<ThemeColor>,
+    pub low: Option<ThemeColor>,
+    pub medium: Option<ThemeColor>,
+    pub high: Option<ThemeColor>,
+    pub critical: Option<ThemeColor>,
+}
+
+impl ThemeConfig {
+    /// The built-in theme with the configured colors swapped in.
+    pub fn resolve(&self, name: ThemeName) -> Theme {
+        let mut theme = Theme::built_in(name);
+        for (color, configured) in [
+            (&mut theme.text, self.text),
+            (&mut theme.accent, self.accent),
+            (&mut theme.heading, self.heading),
+            (&mut theme.hint, self.hint),
+            (&mut theme.error, self.error),
+            (&mut theme.warning, self.warning),
+            (&mut theme.popup, self.popup),
+            (&mut theme.muted, self.muted),
+            (&mut theme.progress, self.progress),
+            (&mut theme.debug, self.debug),
+            (&mut theme.low, self.low),
+            (&mut theme.medium, self.medium),
+            (&mut theme.high, self.high),
+            (&mut theme.critical, self.critical),
+        ] {
+            if let Some(configured) = configured {
+                *color = configured.0;
+            }
+        }
+
+        theme
+    }
+}
+
+/// A color written as a name like `"red"` or `"lightblue"`, or as hex like `"#ffa500"`.
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(try_from = "String")]
+pub struct ThemeColor(pub Color);
+
+impl TryFrom<String> for ThemeColor {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Color::from_str(&value)
+            .map(ThemeColor)
+            .map_err(|_| format!("Unknown color: {}", value))
+    }
+}
@@ -1,15 +1,24 @@
+use chrono::{Duration, Utc};
 use ratatui::{
-    layout::{Constraint, Direction, Layout},
-    style::{Color, Style},
-    text::{Line, Text},
-    widgets::{Block, Borders, Paragraph, Wrap},
+    layout::{Alignment, Constraint, Direction, Layout, Margin, Rect},
+    style::{Style, Stylize},
+    text::{Line, Span, Text},
+    widgets::{Block, Borders, Clear, List, ListItem, Paragraph, Wrap},
     Frame,
 };
+use throbber_widgets_tui::Throbber;
 
 use crate::app::App;
-use crate::current_screen::{get_key_hint_text, get_navigation_text, render_popup, render_screen};
+use crate::current_screen::{
+    get_key_hint_text, get_navigation_text, render_help_popup, render_popup, render_screen,
+    CurrentScreen,
+};
+use crate::mouse::HitAreas;
+use crate::performance::estimate_data_size;
 
 pub fn ui(f: &mut Frame, app: &mut App) {
+    // the screens note where their clickable parts are as they render
+    app.hit_areas = HitAreas::default();
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
@@ -23,22 +32,82 @@ pub fn ui(f: &mut Frame, app: &mut App) {
         .borders(Borders::ALL)
         .style(Style::default());
 
-    let title = Paragraph::new(Text::styled(
-        "Dependabot Tracker",
-        Style::default().fg(Color::Green),
-    ))
-    .block(title_block);
+    let mut title_text = String::from("Dependabot Tracker");
+    if !app.profile.is_default() {
+        title_text.push_str(&format!(" [profile: {}]", app.profile.name));
+    }
+    if let Some(group) = &app.selected_group {
+        title_text.push_str(&format!(" [group: {}]", group));
+    }
+    if let Some(topic) = &app.selected_topic {
+        title_text.push_str(&format!(" [topic: {}]", topic));
+    }
+    if !app.repository_search.query.is_empty() {
+        title_text.push_str(&format!(" [search: {}]", app.repository_search.query));
+    }
+    if app.fixable_only {
+        title_text.push_str(" [fixable only]");
+    }
+    if matches!(app.current_screen, CurrentScreen::ProjectList) {
+        title_text.push_str(&format!(
+            " [sorted by {}]",
+            app.repositories.sort_mode().label()
+        ));
+    }
+    let mut title_spans = vec![Span::styled(
+        title_text,
+        Style::default().fg(app.theme.accent),
+    )];
+    if let Some(data_age) = data_age_span(app) {
+        title_spans.push(Span::raw(" "));
+        title_spans.push(data_age);
+    }
+    let title = Paragraph::new(Line::from(title_spans)).block(title_block);
 
     f.render_widget(title, chunks[0]);
 
+    if let Some(warning) = app.token_expiration_warning() {
+        let token_warning = Paragraph::new(Text::styled(
+            warning,
+            Style::default().fg(app.theme.warning),
+        ))
+        .alignment(Alignment::Right);
+
+        f.render_widget(
+            token_warning,
+            chunks[0].inner(&Margin {
+                vertical: 1,
+                horizontal: 1,
+            }),
+        );
+    }
+
+    if app.fetching.is_some() && app.background_fetch {
+        render_background_refresh_spinner(app, f, chunks[0]);
+    }
+
     render_screen(app, f, &chunks);
 
-    let current_navigation_text = vec![
+    let mut current_navigation_text = vec![
         // The first half of the text
         get_navigation_text(app),
     ];
+    if let Some(status) = app.status.current() {
+        current_navigation_text.push(Span::styled(
+            format!("  {}", status.message),
+            Style::default().fg(status.level.color(&app.theme)).bold(),
+        ));
+    }
+    let mut footer_lines = vec![Line::from(current_navigation_text)];
+    if let Some(time_until_refresh) = app.time_until_refresh() {
+        let seconds = time_until_refresh.as_secs();
+        footer_lines.push(Line::from(Span::styled(
+            format!("Refreshing in {}:{:02}", seconds / 60, seconds % 60),
+            Style::default().fg(app.theme.muted),
+        )));
+    }
 
-    let mode_footer = Paragraph::new(Line::from(current_navigation_text))
+    let mode_footer = Paragraph::new(footer_lines)
         .block(Block::default().borders(Borders::ALL))
         .wrap(Wrap { trim: true });
 
@@ -56,5 +125,154 @@ pub fn ui(f: &mut Frame, app: &mut App) {
     f.render_widget(mode_footer, footer_chunks[0]);
     f.render_widget(key_notes_footer, footer_chunks[1]);
 
+    if app.performance.visible {
+        render_performance_overlay(app, f, chunks[1]);
+    }
+
+    if app.notifications.visible {
+        render_notification_drawer(app, f, chunks[1]);
+    }
+
     render_popup(app, f);
+
+    if app.help.visible {
+        render_help_popup(app, f);
+    }
+}
+
+/// How long ago the repositories were fetched, in the warning color once it's been longer than
+/// `stale_after_hours` or while offline.
+fn data_age_span(app: &App) -> Option<Span<'static>> {
+    let Some(last_updated) = app.last_updated else {
+        // offline data that was never fetched can't say how old it is, but still say it's offline
+        return app
+            .config
+            .offline
+            .then(|| Span::styled("[offline]", Style::default().fg(app.theme.warning)));
+    };
+    let age = Utc::now() - last_updated;
+    let age_text = if age.num_days() > 0 {
+        format!("{}d ago", age.num_days())
+    } else if age.num_hours() > 0 {
+        format!("{}h ago", age.num_hours())
+    } else if age.num_minutes() > 0 {
+        format!("{}m ago", age.num_minutes())
+    } else {
+        "just now".to_string()
+    };
+    let color = if app.config.offline || age > Duration::hours(app.config.stale_after_hours) {
+        app.theme.warning
+    } else {
+        app.theme.muted
+    };
+    let label = if app.config.offline {
+        format!("[offline, data as of {}]", age_text)
+    } else {
+        format!("[data as of {}]", age_text)
+    };
+
+    Some(Span::styled(label, Style::default().fg(color)))
+}
+
+/// A spinner on the title bar's top border while a periodic refresh runs.
+fn render_background_refresh_spinner(app: &mut App, f: &mut Frame, area: Rect) {
+    let label = match app.fetch_progress.summary() {
+        Some(summary) => format!("Refreshing... {} ", summary),
+        None => "Refreshing... ".to_string(),
+    };
+    let width = (label.chars().count() as u16 + 2).min(area.width.saturating_sub(2));
+    let spinner_area = Rect {
+        x: area.right().saturating_sub(width + 1),
+        y: area.y,
+        width,
+        height: 1,
+    };
+    let spinner = Throbber::default()
+        .label(label)
+        .style(Style::default().fg(app.theme.progress))
+        .throbber_set(throbber_widgets_tui::BRAILLE_SIX);
+
+    f.render_stateful_widget(spinner, spinner_area, &mut app.spinner_state);
 }
+
+fn render_performance_overlay(app: &App, f: &mut Frame, area: Rect) {
+    let overlay_area = Rect {
+        x: area.x + area.width.saturating_sub(36),
+        y: area.y,
+        width: area.width.min(36),
+        height: area.height.min(5),
+    };
+
+    let alert_count: usize = app
+        .repositories
+        .repos
+        .iter()
+        .map(|repo| repo.dependabots.len())
+        .sum();
+    let lines = vec![
+        Line::from(format!(
+            "Render time: {:.2}ms",
+            app.performance.render_time.as_secs_f64() * 1000.0
+        )),
+        Line::from(format!(
+            "Event latency: {:.2}ms",
+            app.performance.event_latency.as_secs_f64() * 1000.0
+        )),
+        Line::from(format!(
+            "Data: ~{} KiB ({} alerts)",
+            estimate_data_size(&app.repositories.repos) / 1024,
+            alert_count
+        )),
+    ];
+
+    let overlay = Paragraph::new(lines)
+        .style(Style::default().fg(app.theme.debug))
+        .block(Block::default().title("Debug").borders(Borders::ALL));
+
+    f.render_widget(Clear, overlay_area);
+    f.render_widget(overlay, overlay_area);
+}
+
+fn render_notification_drawer(app: &App, f: &mut Frame, area: Rect) {
+    let drawer_area = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(55), Constraint::Percentage(45)])
+        .split(area)[1];
+
+    let notifications: Vec<ListItem> = app
+        .notifications
+        .entries
+        .iter()
+        .rev()
+        .map(|notification| {
+            ListItem::new(Line::from(vec![
+                Span::styled(
+                    format!(
+                        "{} ",
+                        app.config
+                            .timezone
+                            .format(notification.timestamp, "%H:%M:%S")
+                    ),
+                    Style::default().fg(app.theme.muted),
+                ),
+                Span::styled(
+                    notification.message.clone(),
+                    Style::default().fg(notification.level.color(&app.theme)),
+                ),
+            ]))
+        })
+        .collect();
+
+    let drawer = List::new(notifications).block(
+        Block::default()
+            .title("Notifications (n to close)")
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(app.theme.text)),
+    );
+
+    f.render_widget(Clear, drawer_area);
+    f.render_widget(drawer, drawer_area);
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,534 @@
+//! Each screen rendered with fixture data, compared against the snapshots in `snapshots/`.
+//! Run `cargo insta review` to look over and accept the changes after changing a layout.
+
+use std::path::PathBuf;
+
+use chrono::{Duration, Utc};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{backend::TestBackend, Terminal};
+use serde_json::json;
+
+use super::ui;
+use crate::app::App;
+use crate::audit::{AuditFinding, AuditReport};
+use crate::config::Config;
+use crate::current_screen::CurrentScreen;
+use crate::dependabot::DependabotSeverity;
+use crate::event_log::{AlertEvent, AlertEventKind};
+use crate::osv::OsvLookup;
+use crate::profile::{Profile, DEFAULT_PROFILE};
+use crate::provider::ProviderKind;
+use crate::repository::Repository;
+use crate::setup::{SetupStep, SetupWizard};
+use crate::storage::SavedData;
+use crate::timezone::DisplayTimezone;
+
+const WIDTH: u16 = 160;
+const HEIGHT: u16 = 40;
+
+/// A timestamp `days` ago. The fixtures are dated relative to now so the alert ages they show
+/// stay the same from one day to the next.
+fn days_ago(days: i64) -> String {
+    (Utc::now() - Duration::days(days)).to_rfc3339()
+}
+
+fn alert(number: u32, severity: &str, package: &str, days: i64) -> serde_json::Value {
+    json!({
+        "number": number,
+        "state": "open",
+        "severity": severity,
+        "html_url": format!("https://github.com/octocat/api-server/security/dependabot/{}", number),
+        "created_at": days_ago(days),
+        "updated_at": days_ago(days),
+        "dismissed_at": null,
+        "manifest_path": "package-lock.json",
+        "scope": "runtime",
+        "dependency_ecosystem": "npm",
+        "dependency_name": package,
+        "vulnerable_version_range": "< 2.0.0",
+        "ghsa_id": format!("GHSA-{:04}-test-0000", number),
+        "summary": format!("Prototype pollution in {}", package),
+        "description": "An attacker can modify the prototype of Object.",
+        "first_patched_version": "2.0.0",
+    })
+}
+
+fn repositories() -> Vec<Repository> {
+    let mut fixed = alert(3, "medium", "minimist", 60);
+    fixed["state"] = json!("fixed");
+    fixed["fixed_at"] = json!(days_ago(5));
+    let mut copyleft = alert(1, "low", "readline-sync", 3);
+    copyleft["license"] = json!("GPL-3.0");
+
+    let repositories = json!([
+        {
+            "id": 1,
+            "name": "api-server",
+            "full_name": "octocat/api-server",
+            "private": true,
+            "url": "https://github.com/octocat/api-server",
+            "archived": false,
+            "visibility": "private",
+            "dependabots": [
+                alert(1, "critical", "lodash", 40),
+                alert(2, "high", "axios", 10),
+                fixed,
+            ],
+            "low_alerts": 0,
+            "medium_alerts": 0,
+            "high_alerts": 0,
+            "critical_alerts": 0,
+            "total_active_alerts": 0,
+            "has_security_policy": true,
+            "code_scanning_alerts": [{
+                "number": 7,
+                "state": "open",
+                "severity": "high",
+                "html_url": "https://github.com/octocat/api-server/security/code-scanning/7",
+                "created_at": days_ago(12),
+                "rule_id": "js/sql-injection",
+                "description": "Database query built from user-controlled sources",
+                "tool": "CodeQL",
+                "location": "src/db.js:42",
+            }],
+            "secret_scanning_alerts": [{
+                "number": 2,
+                "state": "open",
+                "html_url": "https://github.com/octocat/api-server/security/secret-scanning/2",
+                "created_at": days_ago(8),
+                "secret_type_display_name": "GitHub Personal Access Token",
+                "resolution": null,
+            }],
+        },
+        {
+            "id": 2,
+            "name": "web-frontend",
+            "full_name": "octocat/web-frontend",
+            "private": false,
+            "url": "https://github.com/octocat/web-frontend",
+            "archived": false,
+            "visibility": "public",
+            "dependabots": [copyleft],
+            "low_alerts": 0,
+            "medium_alerts": 0,
+            "high_alerts": 0,
+            "critical_alerts": 0,
+            "total_active_alerts": 0,
+            "topics": ["production"],
+        },
+        {
+            "id": 3,
+            "name": "legacy-tool",
+            "full_name": "octocat/legacy-tool",
+            "private": false,
+            "url": "https://github.com/octocat/legacy-tool",
+            "archived": true,
+            "visibility": "public",
+            "dependabots": [],
+            "low_alerts": 0,
+            "medium_alerts": 0,
+            "high_alerts": 0,
+            "critical_alerts": 0,
+            "total_active_alerts": 0,
+            "alerts_status": "disabled",
+        },
+    ]);
+
+    let mut repositories: Vec<Repository> = serde_json::from_value(repositories).unwrap();
+    for repository in repositories.iter_mut() {
+        repository.update_alert_counts();
+    }
+    repositories
+}
+
+fn profile(name: &str) -> Profile {
+    Profile {
+        name: name.to_string(),
+        username: "octocat".to_string(),
+        token: "ghp_test".to_string(),
+        data_dir: PathBuf::from("/data").join(name),
+        provider: ProviderKind::GitHub,
+        gitlab_url: None,
+    }
+}
+
+/// An app showing the fixture repositories, with nothing read from the environment or disk.
+fn app() -> App {
+    let config = Config {
+        timezone: DisplayTimezone::Utc,
+        ..Config::default()
+    };
+    let saved_data = SavedData {
+        repositories: repositories(),
+        ..SavedData::default()
+    };
+    let mut app = App::with_saved_data(config, profile(DEFAULT_PROFILE), saved_data);
+    app.config_path = Some(PathBuf::from("/config/dependabot-tracker/config.toml"));
+    app
+}
+
+/// The app opened on the first repository, `api-server`.
+fn project_app() -> App {
+    let mut app = app();
+    app.repositories.select(0);
+    app.open_selected_repository();
+    app
+}
+
+fn render(app: &mut App) -> String {
+    let mut terminal = Terminal::new(TestBackend::new(WIDTH, HEIGHT)).unwrap();
+    terminal.draw(|f| ui(f, app)).unwrap();
+
+    terminal.backend().to_string()
+}
+
+/// Assert that the app renders the same as its snapshot, with the dates masked as they're
+/// relative to now.
+macro_rules! assert_screen {
+    ($name:expr, $app:expr) => {
+        insta::with_settings!({
+            filters => vec![(
+                r"\d{4}-\d{2}-\d{2}([ T]\d{2}:\d{2}(:\d{2})?(\.\d+)?(Z| UTC|[+-]\d{2}:\d{2})?)?",
+                "[date]",
+            )],
+        }, {
+            insta::assert_snapshot!($name, render($app));
+        });
+    };
+}
+
+#[test]
+fn overview() {
+    assert_screen!("overview", &mut app());
+}
+
+#[test]
+fn project_list() {
+    let mut app = app();
+    app.current_screen = CurrentScreen::ProjectList;
+    app.repositories.select(0);
+
+    assert_screen!("project_list", &mut app);
+}
+
+#[test]
+fn project() {
+    assert_screen!("project", &mut project_app());
+}
+
+#[test]
+fn dependabot_details() {
+    let mut app = project_app();
+    app.open_tab(CurrentScreen::DependabotDetails);
+
+    assert_screen!("dependabot_details", &mut app);
+}
+
+#[test]
+fn alert_detail() {
+    let mut app = project_app();
+    app.current_screen = CurrentScreen::AlertDetail;
+    app.alert_detail = Some(1);
+    app.osv_lookup = OsvLookup::Done(None);
+
+    assert_screen!("alert_detail", &mut app);
+}
+
+#[test]
+fn resolved_alerts() {
+    let mut app = project_app();
+    app.open_tab(CurrentScreen::ResolvedAlerts);
+
+    assert_screen!("resolved_alerts", &mut app);
+}
+
+#[test]
+fn code_scanning() {
+    let mut app = project_app();
+    app.open_tab(CurrentScreen::CodeScanning);
+
+    assert_screen!("code_scanning", &mut app);
+}
+
+#[test]
+fn secret_scanning() {
+    let mut app = project_app();
+    app.open_tab(CurrentScreen::SecretScanning);
+
+    assert_screen!("secret_scanning", &mut app);
+}
+
+#[test]
+fn update() {
+    let mut app = app();
+    app.current_screen = CurrentScreen::Update;
+
+    assert_screen!("update", &mut app);
+}
+
+#[test]
+fn updating() {
+    let mut app = app();
+    app.current_screen = CurrentScreen::Updating;
+
+    assert_screen!("updating", &mut app);
+}
+
+#[test]
+fn credentials() {
+    let mut app = app();
+    app.current_screen = CurrentScreen::Credentials;
+
+    assert_screen!("credentials", &mut app);
+}
+
+#[test]
+fn setup() {
+    let mut app = app();
+    app.setup = SetupWizard::new(&PathBuf::from("/data/default"));
+    app.setup.username = "octocat".to_string();
+    app.current_screen = CurrentScreen::Setup;
+
+    assert_screen!("setup", &mut app);
+}
+
+#[test]
+fn setup_data_location() {
+    let mut app = app();
+    app.setup = SetupWizard::new(&PathBuf::from("/data/default"));
+    app.setup.step = SetupStep::DataDir;
+    app.current_screen = CurrentScreen::Setup;
+
+    assert_screen!("setup_data_location", &mut app);
+}
+
+#[test]
+fn setup_takes_keys_bound_to_global_actions_as_text() {
+    let mut app = app();
+    app.open_setup();
+    let theme_name = app.theme_name;
+    let notifications_visible = app.notifications.visible;
+
+    for c in "nfT?".chars() {
+        crate::handle_key(
+            &mut app,
+            KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE),
+        );
+    }
+
+    assert_eq!(app.setup.username, "nfT?");
+    assert_eq!(app.current_screen, CurrentScreen::Setup);
+    assert!(!app.help.visible);
+    assert!(!app.fixable_only);
+    assert_eq!(app.notifications.visible, notifications_visible);
+    assert_eq!(app.theme_name, theme_name);
+}
+
+#[test]
+fn dismiss() {
+    let mut app = project_app();
+    app.open_tab(CurrentScreen::DependabotDetails);
+    app.dismissal_input.alert_number = 1;
+    app.current_screen = CurrentScreen::Dismiss;
+
+    assert_screen!("dismiss", &mut app);
+}
+
+#[test]
+fn distinct_alerts() {
+    let mut app = app();
+    app.open_distinct_alerts();
+
+    assert_screen!("distinct_alerts", &mut app);
+}
+
+#[test]
+fn copyleft_report() {
+    let mut app = app();
+    app.open_copyleft_report();
+
+    assert_screen!("copyleft_report", &mut app);
+}
+
+#[test]
+fn confirm_open_alerts() {
+    let mut app = project_app();
+    app.open_tab(CurrentScreen::DependabotDetails);
+    app.marked_alerts = (1..=12).collect();
+    app.current_screen = CurrentScreen::ConfirmOpenAlerts;
+
+    assert_screen!("confirm_open_alerts", &mut app);
+}
+
+#[test]
+fn audit_comparison() {
+    let mut app = project_app();
+    app.audit_reports = vec![AuditReport {
+        repository: "octocat/api-server".to_string(),
+        findings: vec![
+            AuditFinding {
+                package: "lodash".to_string(),
+                ecosystem: "npm".to_string(),
+                advisory_ids: vec!["GHSA-0001-test-0000".to_string()],
+            },
+            AuditFinding {
+                package: "left-pad".to_string(),
+                ecosystem: "npm".to_string(),
+                advisory_ids: Vec::new(),
+            },
+        ],
+    }];
+    app.open_audit_comparison();
+
+    assert_screen!("audit_comparison", &mut app);
+}
+
+#[test]
+fn error() {
+    let mut app = app();
+    app.report_error("Failed to fetch repositories: connection refused".to_string());
+
+    assert_screen!("error", &mut app);
+}
+
+#[test]
+fn login() {
+    let mut app = app();
+    app.current_screen = CurrentScreen::Login;
+
+    assert_screen!("login", &mut app);
+}
+
+#[test]
+fn dependencies() {
+    let mut app = app();
+    app.open_dependencies();
+
+    assert_screen!("dependencies", &mut app);
+}
+
+#[test]
+fn export() {
+    let mut app = app();
+    app.current_screen = CurrentScreen::Export;
+
+    assert_screen!("export", &mut app);
+}
+
+fn changes() -> Vec<AlertEvent> {
+    vec![
+        AlertEvent {
+            timestamp: Utc::now(),
+            kind: AlertEventKind::Opened,
+            repository: "octocat/api-server".to_string(),
+            alert_number: 2,
+            dependency_name: "axios".to_string(),
+            severity: DependabotSeverity::High,
+            previous_severity: None,
+        },
+        AlertEvent {
+            timestamp: Utc::now(),
+            kind: AlertEventKind::Fixed,
+            repository: "octocat/api-server".to_string(),
+            alert_number: 3,
+            dependency_name: "minimist".to_string(),
+            severity: DependabotSeverity::Medium,
+            previous_severity: None,
+        },
+    ]
+}
+
+#[test]
+fn whats_new() {
+    let mut app = app();
+    app.last_changes = changes();
+    app.current_screen = CurrentScreen::WhatsNew;
+
+    assert_screen!("whats_new", &mut app);
+}
+
+#[test]
+fn changes_screen() {
+    let mut app = app();
+    app.last_changes = changes();
+    app.open_changes();
+
+    assert_screen!("changes", &mut app);
+}
+
+#[test]
+fn profiles() {
+    let mut app = app();
+    app.profiles = vec![profile(DEFAULT_PROFILE), profile("work")];
+    app.profiles_state.select(Some(0));
+    app.current_screen = CurrentScreen::Profiles;
+
+    assert_screen!("profiles", &mut app);
+}
+
+#[test]
+fn credentials_asked_for_only_when_updating() {
+    let config = Config {
+        timezone: DisplayTimezone::Utc,
+        ..Config::default()
+    };
+    let profile = Profile {
+        token: String::new(),
+        ..profile(DEFAULT_PROFILE)
+    };
+    let saved_data = SavedData {
+        repositories: repositories(),
+        ..SavedData::default()
+    };
+    let mut app = App::with_saved_data(config, profile, saved_data);
+    assert_eq!(app.current_screen, CurrentScreen::Overview);
+
+    app.open_update_prompt();
+    assert_eq!(app.current_screen, CurrentScreen::Credentials);
+
+    app.close_credentials_prompt();
+    assert_eq!(app.current_screen, CurrentScreen::Overview);
+}
+
+#[test]
+fn updating_during_a_background_refresh_shows_it_instead_of_starting_another() {
+    let mut app = app();
+    let (_tx, rx) = tokio::sync::mpsc::unbounded_channel();
+    app.fetching = Some(rx);
+    app.background_fetch = true;
+
+    app.open_update_prompt();
+
+    assert_eq!(app.current_screen, CurrentScreen::Updating);
+    assert!(!app.background_fetch);
+    assert!(app.fetching.is_some());
+}
+
+#[test]
+fn cancelling_an_update_goes_back_to_where_it_was_started() {
+    let mut app = app();
+    app.current_screen = CurrentScreen::Project;
+    app.open_update_prompt();
+    assert_eq!(app.current_screen, CurrentScreen::Update);
+    // stands in for the fetch the prompt would start
+    let (_tx, rx) = tokio::sync::mpsc::unbounded_channel();
+    app.fetching = Some(rx);
+    app.current_screen = CurrentScreen::Updating;
+
+    app.cancel_fetch();
+
+    assert_eq!(app.current_screen, CurrentScreen::Project);
+    // the cancelled fetch is still waited on, for the repositories it already has
+    assert!(app.fetching.is_some());
+    assert!(app.background_fetch);
+}
+
+#[test]
+fn worst_first_leaves_the_setup_wizard_in_place() {
+    let mut app = app();
+    app.open_setup();
+
+    app.open_highest_risk_repository();
+
+    assert_eq!(app.current_screen, CurrentScreen::Setup);
+}
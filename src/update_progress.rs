@@ -0,0 +1,61 @@
+use crate::repository::{FetchProgress, PageProgress};
+
+// how many finished repositories the Updating popup's log keeps
+const LOG_LENGTH: usize = 100;
+
+/// What a running fetch has reported so far, built up from its `FetchProgress` steps.
+#[derive(Default)]
+pub struct UpdateProgress {
+    // the latest page read of a paginated listing
+    pub page: Option<PageProgress>,
+    // how many repositories have been queued to have their alerts fetched
+    pub total: usize,
+    pub completed: usize,
+    pub failed: usize,
+    // the repositories that have finished and whether they failed, most recent last
+    pub log: Vec<(String, bool)>,
+}
+
+impl UpdateProgress {
+    pub fn apply(&mut self, progress: FetchProgress) {
+        match progress {
+            FetchProgress::Page(page) => self.page = Some(page),
+            FetchProgress::RepositoriesQueued(count) => self.total += count,
+            FetchProgress::RepositoryDone { name, failed } => {
+                self.completed += 1;
+                if failed {
+                    self.failed += 1;
+                }
+                self.log.push((name, failed));
+                if self.log.len() > LOG_LENGTH {
+                    self.log.remove(0);
+                }
+            }
+            // the app adds the repository to its list itself
+            FetchProgress::RepositoryFetched(_) => {}
+        }
+    }
+
+    /// The share of the queued repositories that have finished.
+    pub fn ratio(&self) -> f64 {
+        if self.total == 0 {
+            0.0
+        } else {
+            (self.completed as f64 / self.total as f64).min(1.0)
+        }
+    }
+
+    /// A one-line summary, e.g. `42/180 repos, 3 failed`, or the latest page before any
+    /// repositories are queued.
+    pub fn summary(&self) -> Option<String> {
+        if self.total > 0 {
+            let mut summary = format!("{}/{} repos", self.completed, self.total);
+            if self.failed > 0 {
+                summary.push_str(&format!(", {} failed", self.failed));
+            }
+            return Some(summary);
+        }
+
+        self.page.as_ref().map(ToString::to_string)
+    }
+}
@@ -0,0 +1,183 @@
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::dependabot::{Dependabot, DependabotSeverity, DependabotState};
+use crate::error::DependabotTrackerError;
+use crate::repository::Repository;
+use crate::repository_list::RepositoryList;
+use crate::templates::{render_template, TemplateContext};
+
+// how many alerts of each kind a Slack message lists before summarizing the rest
+const LISTED_ALERT_COUNT: usize = 10;
+
+/// The shape of the body posted to the webhook.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookFormat {
+    // a Slack incoming webhook message
+    #[default]
+    Slack,
+    // the new and resolved alerts as plain JSON, for other services to consume
+    Json,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct WebhookConfig {
+    // nothing is posted unless this is set
+    pub url: Option<String>,
+    // overrides the channel a Slack incoming webhook posts to
+    pub channel: Option<String>,
+    // the least severe alert included in the summary
+    pub min_severity: DependabotSeverity,
+    pub format: WebhookFormat,
+}
+
+impl Default for WebhookConfig {
+    fn default() -> Self {
+        WebhookConfig {
+            url: None,
+            channel: None,
+            min_severity: DependabotSeverity::High,
+            format: WebhookFormat::Slack,
+        }
+    }
+}
+
+/// An alert that opened or was resolved between two fetches.
+#[derive(Debug, Clone, Serialize)]
+pub struct AlertChange {
+    pub repository: String,
+    pub number: u32,
+    pub package: String,
+    pub severity: DependabotSeverity,
+    pub state: DependabotState,
+    pub html_url: String,
+}
+
+impl AlertChange {
+    fn new(repo: &Repository, dependabot: &Dependabot) -> Self {
+        AlertChange {
+            repository: repo.full_name.clone(),
+            number: dependabot.number,
+            package: dependabot.dependency_name.clone(),
+            severity: dependabot.severity,
+            state: dependabot.state.clone(),
+            html_url: dependabot.html_url.clone(),
+        }
+    }
+
+    fn to_slack_line(&self) -> String {
+        format!(
+            "• <{}|{}#{}> {} ({})",
+            self.html_url, self.repository, self.number, self.package, self.severity
+        )
+    }
+}
+
+/// The alerts at least as severe as the configured threshold that opened or were resolved in a
+/// fetch, ready to be posted once the fetch is done.
+#[derive(Debug, Clone)]
+pub struct WebhookSummary {
+    pub new_alerts: Vec<AlertChange>,
+    pub resolved_alerts: Vec<AlertChange>,
+    // the Slack message, rendered from the `webhook_summary` template
+    text: String,
+}
+
+impl WebhookSummary {
+    /// The changes between `previous` and the fetched `repositories`, or `None` when there's
+    /// no webhook or nothing worth posting. The Slack message is rendered from `template`, with
+    /// the placeholders in `context` and the changes' own.
+    pub fn new(
+        config: &WebhookConfig,
+        template: &str,
+        context: TemplateContext,
+        repositories: &RepositoryList,
+        previous: &[Repository],
+    ) -> Option<Self> {
+        config.url.as_ref()?;
+        let changes = |alerts: Vec<(&Repository, &Dependabot)>| -> Vec<AlertChange> {
+            alerts
+                .into_iter()
+                .filter(|(_, dependabot)| dependabot.severity >= config.min_severity)
+                .map(|(repo, dependabot)| AlertChange::new(repo, dependabot))
+                .collect()
+        };
+        let new_alerts = changes(repositories.new_open_alerts(previous));
+        let resolved_alerts = changes(repositories.resolved_alerts(previous));
+        if new_alerts.is_empty() && resolved_alerts.is_empty() {
+            return None;
+        }
+
+        let context = context
+            .with("new_count", new_alerts.len().to_string())
+            .with("resolved_count", resolved_alerts.len().to_string())
+            .with("new_alerts", slack_section("New", &new_alerts))
+            .with(
+                "resolved_alerts",
+                slack_section("Resolved", &resolved_alerts),
+            );
+        Some(WebhookSummary {
+            text: render_template(template, &context),
+            new_alerts,
+            resolved_alerts,
+        })
+    }
+
+    fn to_payload(&self, config: &WebhookConfig) -> serde_json::Value {
+        match config.format {
+            WebhookFormat::Slack => {
+                let mut payload = json!({ "text": self.text });
+                if let Some(channel) = config.channel.as_ref() {
+                    payload["channel"] = json!(channel);
+                }
+                payload
+            }
+            WebhookFormat::Json => json!({
+                "new_alerts": self.new_alerts,
+                "resolved_alerts": self.resolved_alerts,
+            }),
+        }
+    }
+}
+
+/// A heading and the first `LISTED_ALERT_COUNT` alerts under it, starting on a new paragraph, or
+/// nothing if there aren't any.
+fn slack_section(heading: &str, alerts: &[AlertChange]) -> String {
+    if alerts.is_empty() {
+        return String::new();
+    }
+
+    let mut section = format!("\n\n*{}*", heading);
+    for alert in alerts.iter().take(LISTED_ALERT_COUNT) {
+        section.push('\n');
+        section.push_str(&alert.to_slack_line());
+    }
+    if alerts.len() > LISTED_ALERT_COUNT {
+        section.push_str(&format!("\nand {} more", alerts.len() - LISTED_ALERT_COUNT));
+    }
+
+    section
+}
+
+/// POST the summary to the configured webhook URL.
+pub async fn post_webhook_summary(
+    config: &WebhookConfig,
+    summary: &WebhookSummary,
+    client: &Client,
+) -> Result<(), DependabotTrackerError> {
+    let Some(url) = config.url.as_ref() else {
+        return Ok(());
+    };
+
+    client
+        .post(url)
+        .json(&summary.to_payload(config))
+        .send()
+        .await
+        .and_then(|response| response.error_for_status())?;
+
+    Ok(())
+}